@@ -0,0 +1,121 @@
+//! Diff engine benchmarks on full-page 3x screenshots
+//!
+//! Run with `cargo bench --bench diff`; compare against a saved baseline with
+//! `cargo bench --bench diff -- --save-baseline main` and `--baseline main`.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use fgm::diff::{compare_rgba, render_rgba_diff, DiffMethod, DiffOptions};
+use image::{Rgba, RgbaImage};
+use std::hint::black_box;
+
+/// iPhone 15 screenshot size (393x852pt at 3x)
+const WIDTH: u32 = 1179;
+const HEIGHT: u32 = 2556;
+
+/// A screen of text-like stripes on a light background
+fn design() -> RgbaImage {
+    RgbaImage::from_fn(WIDTH, HEIGHT, |x, y| {
+        let line = (y / 48) % 3 != 0 && (x / 6 + y / 48) % 7 != 0;
+        let value = if line && y % 48 < 30 { 40 } else { 245 };
+        Rgba([value, value, value.saturating_add(8), 255])
+    })
+}
+
+/// The design with a shifted, recolored block and one-level noise everywhere
+fn screenshot(design: &RgbaImage) -> RgbaImage {
+    RgbaImage::from_fn(WIDTH, HEIGHT, |x, y| {
+        let mut pixel = *design.get_pixel(x, y);
+        if (200..600).contains(&x) && (900..1100).contains(&y) {
+            pixel = *design.get_pixel(x - 2, y);
+            pixel[0] = pixel[0].saturating_add(60);
+        }
+        if (x + y) % 5 == 0 {
+            pixel[1] = pixel[1].saturating_sub(1);
+        }
+        pixel
+    })
+}
+
+fn options(method: DiffMethod) -> DiffOptions {
+    DiffOptions {
+        method,
+        ..DiffOptions::default()
+    }
+}
+
+fn bench_methods(c: &mut Criterion) {
+    let design = design();
+    let screenshot = screenshot(&design);
+    let mut group = c.benchmark_group("compare");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(u64::from(WIDTH * HEIGHT)));
+
+    group.bench_function("identical", |b| {
+        b.iter(|| {
+            compare_rgba(
+                black_box(&design),
+                black_box(&design),
+                &options(DiffMethod::Pixel),
+                None,
+                false,
+            )
+        })
+    });
+    for method in [
+        DiffMethod::Pixel,
+        DiffMethod::Antialias,
+        DiffMethod::DeltaE,
+        DiffMethod::Ssim,
+    ] {
+        let options = options(method);
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format!("{:?}", method)),
+            &options,
+            |b, options| {
+                b.iter(|| {
+                    compare_rgba(
+                        black_box(&design),
+                        black_box(&screenshot),
+                        options,
+                        None,
+                        false,
+                    )
+                })
+            },
+        );
+    }
+    group.bench_function("pixel-early-exit", |b| {
+        b.iter(|| {
+            compare_rgba(
+                black_box(&design),
+                black_box(&screenshot),
+                &options(DiffMethod::Pixel),
+                Some(0.1),
+                true,
+            )
+        })
+    });
+    group.finish();
+}
+
+fn bench_render(c: &mut Criterion) {
+    let design = design();
+    let screenshot = screenshot(&design);
+    let mut group = c.benchmark_group("render");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(u64::from(WIDTH * HEIGHT)));
+    for method in [DiffMethod::Pixel, DiffMethod::Ssim] {
+        let options = options(method);
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format!("{:?}", method)),
+            &options,
+            |b, options| {
+                b.iter(|| render_rgba_diff(black_box(&design), black_box(&screenshot), options))
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_methods, bench_render);
+criterion_main!(benches);
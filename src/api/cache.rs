@@ -16,6 +16,8 @@ use std::time::Duration;
 pub enum CacheKey {
     /// Full file metadata by file key
     File(String),
+    /// Full file at a specific version by file key and version ID
+    FileVersion(String, String),
     /// Light file metadata by file key
     FileMeta(String),
     /// Specific nodes by file_key and node_ids hash
@@ -34,6 +36,10 @@ pub enum CacheKey {
     TeamStyles(String),
     /// Component detail by key
     Component(String),
+    /// Local variables and collections by file key
+    LocalVariables(String),
+    /// Published variables by file key
+    PublishedVariables(String),
 }
 
 impl CacheKey {
@@ -41,6 +47,7 @@ impl CacheKey {
     pub fn as_string(&self) -> String {
         match self {
             CacheKey::File(key) => format!("file:{}", key),
+            CacheKey::FileVersion(key, version) => format!("file_version:{}:{}", key, version),
             CacheKey::FileMeta(key) => format!("file_meta:{}", key),
             CacheKey::Nodes(file, nodes) => format!("nodes:{}:{}", file, nodes),
             CacheKey::Images(file, params) => format!("images:{}:{}", file, params),
@@ -50,6 +57,8 @@ impl CacheKey {
             CacheKey::TeamComponents(team) => format!("team_components:{}", team),
             CacheKey::TeamStyles(team) => format!("team_styles:{}", team),
             CacheKey::Component(key) => format!("component:{}", key),
+            CacheKey::LocalVariables(key) => format!("variables_local:{}", key),
+            CacheKey::PublishedVariables(key) => format!("variables_published:{}", key),
         }
     }
 
@@ -143,6 +152,10 @@ impl CacheTTL {
     pub const TEAM_DATA: Duration = Duration::from_secs(3600);
     /// Component info - 30 minutes
     pub const COMPONENTS: Duration = Duration::from_secs(1800);
+    /// Variables - 5 minutes (edited alongside the file)
+    pub const VARIABLES: Duration = Duration::from_secs(300);
+    /// File at a saved version - 1 day (versions never change)
+    pub const FILE_VERSION: Duration = Duration::from_secs(86400);
 }
 
 /// Cache statistics
@@ -361,11 +374,7 @@ impl FigmaCache {
     fn disk_key_path(&self, key: &str) -> Option<PathBuf> {
         self.disk_path.as_ref().map(|p| {
             // Sanitize key for filesystem - replace problematic characters
-            let safe_key = key
-                .replace(':', "_")
-                .replace('/', "_")
-                .replace('\\', "_")
-                .replace(' ', "_");
+            let safe_key = key.replace([':', '/', '\\', ' '], "_");
             p.join(format!("{}.json", safe_key))
         })
     }
@@ -421,6 +430,12 @@ mod tests {
 
         let key = CacheKey::Images("abc".to_string(), "hash".to_string());
         assert_eq!(key.as_string(), "images:abc:hash");
+
+        let key = CacheKey::LocalVariables("abc".to_string());
+        assert_eq!(key.as_string(), "variables_local:abc");
+
+        let key = CacheKey::FileVersion("abc".to_string(), "123".to_string());
+        assert_eq!(key.as_string(), "file_version:abc:123");
     }
 
     #[test]
@@ -1,7 +1,9 @@
 //! Figma API client with caching and rate limiting
 
 use super::cache::{create_shared_cache, CacheStats, FigmaCache};
+use super::fixtures::{self, FixtureMode, FixtureStore};
 use super::rate_limit::{RateLimitTelemetry, RateLimiter, RequestClass};
+use crate::config::Config;
 use anyhow::Result;
 use reqwest::header::{HeaderMap, HeaderValue};
 use reqwest::{Client, Response, StatusCode};
@@ -14,11 +16,106 @@ const FIGMA_API_BASE: &str = "https://api.figma.com/v1";
 const DEFAULT_API_CONCURRENCY: usize = 3;
 const DEFAULT_DOWNLOAD_CONCURRENCY: usize = 10;
 
+/// Environment variable overriding the REST API base URL
+pub const API_BASE_ENV: &str = "FIGMA_API_BASE";
+/// Environment variable overriding the host used for image downloads
+pub const IMAGE_HOST_ENV: &str = "FIGMA_IMAGE_HOST";
+
+/// Non-success response from the REST API
+#[derive(Debug, thiserror::Error)]
+#[error("API error {status}: {body}")]
+pub struct ApiError {
+    pub status: StatusCode,
+    pub body: String,
+}
+
+/// Endpoints the client talks to
+///
+/// Defaults to the public Figma API. Point these at a local stub server to
+/// run commands offline or in CI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FigmaEndpoints {
+    /// REST API base URL (e.g. `https://api.figma.com/v1`)
+    pub api_base: String,
+    /// Optional origin that replaces the scheme/host/port of image download URLs
+    pub image_host: Option<String>,
+}
+
+impl Default for FigmaEndpoints {
+    fn default() -> Self {
+        Self {
+            api_base: FIGMA_API_BASE.to_string(),
+            image_host: None,
+        }
+    }
+}
+
+impl FigmaEndpoints {
+    /// Create endpoints with a custom API base URL
+    pub fn new(api_base: impl Into<String>, image_host: Option<String>) -> Self {
+        Self {
+            api_base: api_base.into().trim_end_matches('/').to_string(),
+            image_host: image_host.map(|host| host.trim_end_matches('/').to_string()),
+        }
+    }
+
+    /// Resolve endpoints from the environment, then config, then defaults
+    ///
+    /// Priority: `FIGMA_API_BASE`/`FIGMA_IMAGE_HOST` env vars > `[api]` config > api.figma.com
+    pub fn resolve() -> Self {
+        let config = Config::load().unwrap_or_default();
+        Self::from_sources(
+            std::env::var(API_BASE_ENV).ok(),
+            std::env::var(IMAGE_HOST_ENV).ok(),
+            &config,
+        )
+    }
+
+    fn from_sources(
+        env_api_base: Option<String>,
+        env_image_host: Option<String>,
+        config: &Config,
+    ) -> Self {
+        let non_empty = |value: Option<String>| value.filter(|v| !v.trim().is_empty());
+        let api_base = non_empty(env_api_base)
+            .or_else(|| non_empty(config.api.base_url.clone()))
+            .unwrap_or_else(|| FIGMA_API_BASE.to_string());
+        let image_host =
+            non_empty(env_image_host).or_else(|| non_empty(config.api.image_host.clone()));
+        Self::new(api_base, image_host)
+    }
+
+    /// Whether these endpoints differ from the public Figma API
+    pub fn is_custom(&self) -> bool {
+        *self != Self::default()
+    }
+
+    /// Rewrite an image download URL onto the configured image host
+    ///
+    /// Keeps the path and query of the original URL. Returns the URL unchanged
+    /// when no image host is configured or either URL fails to parse.
+    pub fn download_url(&self, url: &str) -> String {
+        let Some(host) = &self.image_host else {
+            return url.to_string();
+        };
+        let (Ok(original), Ok(target)) = (url::Url::parse(url), url::Url::parse(host)) else {
+            return url.to_string();
+        };
+
+        let mut rewritten = target.clone();
+        let prefix = target.path().trim_end_matches('/');
+        rewritten.set_path(&format!("{}{}", prefix, original.path()));
+        rewritten.set_query(original.query());
+        rewritten.to_string()
+    }
+}
+
 /// Figma API client with integrated caching and rate limiting
 pub struct FigmaClient {
     client: Client,
     #[allow(dead_code)]
     token: String,
+    endpoints: FigmaEndpoints,
     cache: Arc<FigmaCache>,
     rate_limiter: Arc<Mutex<RateLimiter>>,
     inflight_requests: Arc<Mutex<HashMap<String, Arc<Mutex<()>>>>>,
@@ -26,6 +123,7 @@ pub struct FigmaClient {
     download_semaphore: Arc<Semaphore>,
     download_parallelism: usize,
     stale_while_revalidate: bool,
+    fixture_mode: FixtureMode,
 }
 
 impl Clone for FigmaClient {
@@ -33,6 +131,7 @@ impl Clone for FigmaClient {
         Self {
             client: self.client.clone(),
             token: self.token.clone(),
+            endpoints: self.endpoints.clone(),
             cache: self.cache.clone(),
             rate_limiter: self.rate_limiter.clone(),
             inflight_requests: self.inflight_requests.clone(),
@@ -40,6 +139,7 @@ impl Clone for FigmaClient {
             download_semaphore: self.download_semaphore.clone(),
             download_parallelism: self.download_parallelism,
             stale_while_revalidate: self.stale_while_revalidate,
+            fixture_mode: self.fixture_mode.clone(),
         }
     }
 }
@@ -47,14 +147,30 @@ impl Clone for FigmaClient {
 impl FigmaClient {
     /// Create a new Figma client with the given access token
     ///
-    /// Uses a shared cache with disk persistence by default.
+    /// Uses a shared cache with disk persistence by default. When recording or
+    /// replaying fixtures the cache is memory only, so every request is seen.
     pub fn new(token: String) -> Result<Self> {
-        let cache = create_shared_cache();
+        let cache = if fixtures::global_mode().is_live() {
+            create_shared_cache()
+        } else {
+            Arc::new(FigmaCache::memory_only())
+        };
         Self::with_cache(token, cache)
     }
 
     /// Create a new Figma client with a custom cache
+    ///
+    /// Endpoints are resolved from env vars and config (see [`FigmaEndpoints::resolve`]).
     pub fn with_cache(token: String, cache: Arc<FigmaCache>) -> Result<Self> {
+        Self::with_endpoints(token, cache, FigmaEndpoints::resolve())
+    }
+
+    /// Create a new Figma client with a custom cache and explicit endpoints
+    pub fn with_endpoints(
+        token: String,
+        cache: Arc<FigmaCache>,
+        endpoints: FigmaEndpoints,
+    ) -> Result<Self> {
         let mut headers = HeaderMap::new();
         // Figma uses X-Figma-Token header, not Bearer auth
         headers.insert("X-Figma-Token", HeaderValue::from_str(&token)?);
@@ -68,6 +184,7 @@ impl FigmaClient {
         Ok(Self {
             client,
             token,
+            endpoints,
             cache,
             rate_limiter: Arc::new(Mutex::new(RateLimiter::new())),
             inflight_requests: Arc::new(Mutex::new(HashMap::new())),
@@ -75,9 +192,16 @@ impl FigmaClient {
             download_semaphore: Arc::new(Semaphore::new(DEFAULT_DOWNLOAD_CONCURRENCY)),
             download_parallelism: DEFAULT_DOWNLOAD_CONCURRENCY,
             stale_while_revalidate: true,
+            fixture_mode: fixtures::global_mode(),
         })
     }
 
+    /// Override the fixture mode (defaults to the process-wide `--record`/`--replay` setting)
+    pub fn with_fixture_mode(mut self, mode: FixtureMode) -> Self {
+        self.fixture_mode = mode;
+        self
+    }
+
     /// Create a client without caching (memory only, no persistence)
     pub fn without_cache(token: String) -> Result<Self> {
         let cache = Arc::new(FigmaCache::memory_only());
@@ -86,7 +210,12 @@ impl FigmaClient {
 
     /// Get the base URL for the API
     pub fn base_url(&self) -> &str {
-        FIGMA_API_BASE
+        &self.endpoints.api_base
+    }
+
+    /// Get the endpoints this client talks to
+    pub fn endpoints(&self) -> &FigmaEndpoints {
+        &self.endpoints
     }
 
     /// Get a reference to the underlying HTTP client
@@ -111,7 +240,7 @@ impl FigmaClient {
 
     /// Check if the token is valid by making a test request
     pub async fn validate_token(&self) -> Result<bool> {
-        let url = format!("{}/me", FIGMA_API_BASE);
+        let url = format!("{}/me", self.base_url());
         let response = self
             .execute_request(RequestClass::Other, || self.client.get(&url))
             .await?;
@@ -153,6 +282,10 @@ impl FigmaClient {
         self.download_parallelism
     }
 
+    pub fn fixture_mode(&self) -> &FixtureMode {
+        &self.fixture_mode
+    }
+
     fn classify_url(url: &str) -> RequestClass {
         if url.contains("/images/") {
             return RequestClass::Images;
@@ -175,10 +308,17 @@ impl FigmaClient {
     /// - Proactive throttling when approaching rate limits
     /// - Automatic retry with exponential backoff on HTTP 429
     /// - Rate limit header parsing
+    /// - Fixture recording/replay when `--record`/`--replay` is active
     pub async fn execute_request<F>(&self, class: RequestClass, request_fn: F) -> Result<Response>
     where
         F: Fn() -> reqwest::RequestBuilder,
     {
+        // Replay never touches the network, semaphores, or the rate limiter
+        if let FixtureMode::Replay(dir) = &self.fixture_mode {
+            let (method, url) = fixtures::describe_request(&request_fn().build()?);
+            return FixtureStore::new(dir).replay(class, &method, &url);
+        }
+
         loop {
             let _permit = match class {
                 RequestClass::Download => self
@@ -232,6 +372,13 @@ impl FigmaClient {
                 limiter.reset();
             }
 
+            if let FixtureMode::Record(dir) = &self.fixture_mode {
+                let (method, url) = fixtures::describe_request(&request_fn().build()?);
+                return FixtureStore::new(dir)
+                    .record(class, &method, &url, response)
+                    .await;
+            }
+
             return Ok(response);
         }
     }
@@ -246,8 +393,8 @@ impl FigmaClient {
 
         if !response.status().is_success() {
             let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
-            anyhow::bail!("API error {}: {}", status, error_text);
+            let body = response.text().await.unwrap_or_default();
+            return Err(ApiError { status, body }.into());
         }
 
         let result = response.json().await?;
@@ -260,3 +407,56 @@ impl FigmaClient {
         limiter.telemetry_snapshot()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn endpoints_default_to_public_api() {
+        let endpoints = FigmaEndpoints::from_sources(None, None, &Config::default());
+        assert_eq!(endpoints.api_base, "https://api.figma.com/v1");
+        assert!(endpoints.image_host.is_none());
+        assert!(!endpoints.is_custom());
+    }
+
+    #[test]
+    fn endpoints_prefer_env_over_config() {
+        let mut config = Config::default();
+        config.api.base_url = Some("http://config.local/v1".to_string());
+        config.api.image_host = Some("http://config-images.local".to_string());
+
+        let from_config = FigmaEndpoints::from_sources(None, None, &config);
+        assert_eq!(from_config.api_base, "http://config.local/v1");
+
+        let from_env = FigmaEndpoints::from_sources(
+            Some("http://127.0.0.1:8080/v1/".to_string()),
+            Some(String::new()),
+            &config,
+        );
+        assert_eq!(from_env.api_base, "http://127.0.0.1:8080/v1");
+        assert_eq!(
+            from_env.image_host.as_deref(),
+            Some("http://config-images.local")
+        );
+    }
+
+    #[test]
+    fn download_url_is_rewritten_onto_image_host() {
+        let endpoints = FigmaEndpoints::new(
+            "http://127.0.0.1:8080/v1",
+            Some("http://127.0.0.1:8080/s3/".to_string()),
+        );
+        assert_eq!(
+            endpoints
+                .download_url("https://figma-alpha-api.s3.us-west-2.amazonaws.com/images/abc?x=1"),
+            "http://127.0.0.1:8080/s3/images/abc?x=1"
+        );
+
+        let passthrough = FigmaEndpoints::default();
+        assert_eq!(
+            passthrough.download_url("https://example.com/a.png"),
+            "https://example.com/a.png"
+        );
+    }
+}
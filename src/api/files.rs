@@ -4,6 +4,7 @@ use super::cache::{CacheEntryFreshness, CacheKey, CacheTTL};
 use super::client::FigmaClient;
 use super::types::*;
 use anyhow::Result;
+use std::collections::HashMap;
 use std::time::Duration;
 
 impl FigmaClient {
@@ -77,6 +78,21 @@ impl FigmaClient {
             .await
     }
 
+    /// Get a file as it was at a saved version (from `get_versions`)
+    ///
+    /// Versions are immutable, so these are cached for a day.
+    pub async fn get_file_version(&self, file_key: &str, version: &str) -> Result<File> {
+        let cache_key = CacheKey::FileVersion(file_key.to_string(), version.to_string());
+        let url = format!(
+            "{}/files/{}?version={}",
+            self.base_url(),
+            file_key,
+            urlencoding::encode(version)
+        );
+        self.get_cached_endpoint(cache_key, url, CacheTTL::FILE_VERSION)
+            .await
+    }
+
     /// Get file with explicit cache control
     ///
     /// # Arguments
@@ -107,6 +123,37 @@ impl FigmaClient {
             .await
     }
 
+    /// Get nodes with their layer trees, keyed by node ID
+    ///
+    /// IDs the API returns no document for are left out.
+    pub async fn get_node_documents(
+        &self,
+        file_key: &str,
+        node_ids: &[String],
+    ) -> Result<HashMap<String, Node>> {
+        let response = self.get_nodes(file_key, node_ids).await?;
+        let mut nodes = HashMap::new();
+        if let Some(entries) = response.get("nodes").and_then(|nodes| nodes.as_object()) {
+            for (id, entry) in entries {
+                if let Some(document) = entry.get("document") {
+                    nodes.insert(id.clone(), serde_json::from_value(document.clone())?);
+                }
+            }
+        }
+        Ok(nodes)
+    }
+
+    /// Get one node with its layer tree
+    pub async fn get_node(&self, file_key: &str, node_id: &str) -> Result<Node> {
+        let node_id = Self::canonical_node_ids(&[node_id.to_string()])
+            .pop()
+            .unwrap_or_default();
+        self.get_node_documents(file_key, std::slice::from_ref(&node_id))
+            .await?
+            .remove(&node_id)
+            .ok_or_else(|| anyhow::anyhow!("Node {} not found", node_id))
+    }
+
     /// Get file metadata only (lighter endpoint, cached longer)
     pub async fn get_file_meta(&self, file_key: &str) -> Result<serde_json::Value> {
         let cache_key = CacheKey::FileMeta(file_key.to_string());
@@ -155,6 +202,27 @@ impl FigmaClient {
             .await
     }
 
+    /// Get local variables and collections with values for every mode (cached for 5 min)
+    ///
+    /// Requires the `file_variables:read` scope (Enterprise plans).
+    pub async fn get_local_variables(&self, file_key: &str) -> Result<LocalVariablesResponse> {
+        let cache_key = CacheKey::LocalVariables(file_key.to_string());
+        let url = format!("{}/files/{}/variables/local", self.base_url(), file_key);
+        self.get_cached_endpoint(cache_key, url, CacheTTL::VARIABLES)
+            .await
+    }
+
+    /// Get variables published from a file's library (cached for 5 min)
+    pub async fn get_published_variables(
+        &self,
+        file_key: &str,
+    ) -> Result<PublishedVariablesResponse> {
+        let cache_key = CacheKey::PublishedVariables(file_key.to_string());
+        let url = format!("{}/files/{}/variables/published", self.base_url(), file_key);
+        self.get_cached_endpoint(cache_key, url, CacheTTL::VARIABLES)
+            .await
+    }
+
     /// Get component by key (cached for 30 min)
     pub async fn get_component(&self, component_key: &str) -> Result<ComponentDetailResponse> {
         let cache_key = CacheKey::Component(component_key.to_string());
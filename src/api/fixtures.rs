@@ -0,0 +1,279 @@
+//! Record/replay HTTP fixtures for the Figma client
+//!
+//! In record mode every response that passes through `execute_request` is
+//! written to a fixture directory (metadata JSON + raw body). In replay mode
+//! responses are served from that directory and no network calls are made;
+//! a request without a matching fixture is a hard error.
+
+use super::rate_limit::RequestClass;
+use crate::util::hash::fnv1a64;
+use anyhow::{anyhow, Context, Result};
+use reqwest::{Request, Response};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+/// How the client should treat HTTP traffic
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum FixtureMode {
+    /// Talk to the network normally
+    #[default]
+    Live,
+    /// Talk to the network and save every response to this directory
+    Record(PathBuf),
+    /// Serve responses from this directory, never touching the network
+    Replay(PathBuf),
+}
+
+impl FixtureMode {
+    /// Build a mode from the `--record` / `--replay` CLI flags
+    pub fn from_flags(record: Option<PathBuf>, replay: Option<PathBuf>) -> Self {
+        match (record, replay) {
+            (_, Some(dir)) => FixtureMode::Replay(dir),
+            (Some(dir), None) => FixtureMode::Record(dir),
+            (None, None) => FixtureMode::Live,
+        }
+    }
+
+    pub fn is_replay(&self) -> bool {
+        matches!(self, FixtureMode::Replay(_))
+    }
+
+    pub fn is_live(&self) -> bool {
+        matches!(self, FixtureMode::Live)
+    }
+}
+
+static GLOBAL_MODE: OnceLock<FixtureMode> = OnceLock::new();
+
+/// Set the process-wide fixture mode (used by clients created via `FigmaClient::new`)
+pub fn set_global_mode(mode: FixtureMode) {
+    let _ = GLOBAL_MODE.set(mode);
+}
+
+/// Get the process-wide fixture mode
+pub fn global_mode() -> FixtureMode {
+    GLOBAL_MODE.get().cloned().unwrap_or_default()
+}
+
+/// Metadata stored next to each recorded response body
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FixtureMeta {
+    pub method: String,
+    pub url: String,
+    pub class: RequestClass,
+    pub status: u16,
+    #[serde(default)]
+    pub headers: Vec<(String, String)>,
+    pub body_file: String,
+    pub recorded_at: String,
+}
+
+/// Fixture directory on disk
+#[derive(Debug, Clone)]
+pub struct FixtureStore {
+    dir: PathBuf,
+}
+
+impl FixtureStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Stable fixture name for a request
+    ///
+    /// Only the path and query take part, so fixtures recorded against
+    /// api.figma.com replay unchanged against a custom `FIGMA_API_BASE`.
+    pub fn fixture_name(class: RequestClass, method: &str, url: &str) -> String {
+        let key = match ::url::Url::parse(url) {
+            Ok(parsed) => match parsed.query() {
+                Some(query) => format!("{} {}?{}", method, parsed.path(), query),
+                None => format!("{} {}", method, parsed.path()),
+            },
+            Err(_) => format!("{} {}", method, url),
+        };
+        format!("{}-{:016x}", class_label(class), fnv1a64(key.as_bytes()))
+    }
+
+    fn meta_path(&self, name: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", name))
+    }
+
+    /// Save a response, returning an equivalent response built from the captured bytes
+    pub async fn record(
+        &self,
+        class: RequestClass,
+        method: &str,
+        url: &str,
+        response: Response,
+    ) -> Result<Response> {
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create {}", self.dir.display()))?;
+
+        let status = response.status().as_u16();
+        let headers: Vec<(String, String)> = response
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| {
+                value
+                    .to_str()
+                    .ok()
+                    .map(|v| (name.as_str().to_string(), v.to_string()))
+            })
+            .collect();
+        let body = response.bytes().await?.to_vec();
+
+        let name = Self::fixture_name(class, method, url);
+        let body_file = format!("{}.body", name);
+        fs::write(self.dir.join(&body_file), &body)
+            .with_context(|| format!("Failed to write fixture body {}", body_file))?;
+
+        let meta = FixtureMeta {
+            method: method.to_string(),
+            url: url.to_string(),
+            class,
+            status,
+            headers,
+            body_file,
+            recorded_at: chrono::Utc::now().to_rfc3339(),
+        };
+        let meta_path = self.meta_path(&name);
+        fs::write(&meta_path, serde_json::to_string_pretty(&meta)?)
+            .with_context(|| format!("Failed to write {}", meta_path.display()))?;
+
+        build_response(&meta, body)
+    }
+
+    /// Load the recorded response for a request, failing if none exists
+    pub fn replay(&self, class: RequestClass, method: &str, url: &str) -> Result<Response> {
+        let name = Self::fixture_name(class, method, url);
+        let meta_path = self.meta_path(&name);
+        if !meta_path.exists() {
+            return Err(anyhow!(
+                "Replay fixture not found for {} {} (expected {})",
+                method,
+                url,
+                meta_path.display()
+            ));
+        }
+
+        let meta: FixtureMeta = serde_json::from_str(&fs::read_to_string(&meta_path)?)
+            .with_context(|| format!("Invalid fixture {}", meta_path.display()))?;
+        let body = fs::read(self.dir.join(&meta.body_file))
+            .with_context(|| format!("Missing fixture body {}", meta.body_file))?;
+        build_response(&meta, body)
+    }
+}
+
+/// Extract the method and URL a request builder would send
+pub fn describe_request(request: &Request) -> (String, String) {
+    (request.method().to_string(), request.url().to_string())
+}
+
+fn build_response(meta: &FixtureMeta, body: Vec<u8>) -> Result<Response> {
+    let mut builder = http::Response::builder().status(meta.status);
+    for (name, value) in &meta.headers {
+        builder = builder.header(name.as_str(), value.as_str());
+    }
+    let response = builder
+        .body(body)
+        .map_err(|err| anyhow!("Invalid fixture response for {}: {}", meta.url, err))?;
+    Ok(Response::from(response))
+}
+
+fn class_label(class: RequestClass) -> &'static str {
+    match class {
+        RequestClass::Metadata => "metadata",
+        RequestClass::Nodes => "nodes",
+        RequestClass::Images => "images",
+        RequestClass::Team => "team",
+        RequestClass::Other => "other",
+        RequestClass::Download => "download",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn fixture_name_ignores_host() {
+        let a = FixtureStore::fixture_name(
+            RequestClass::Metadata,
+            "GET",
+            "https://api.figma.com/v1/files/abc",
+        );
+        let b = FixtureStore::fixture_name(
+            RequestClass::Metadata,
+            "GET",
+            "http://127.0.0.1:8080/v1/files/abc",
+        );
+        let c = FixtureStore::fixture_name(
+            RequestClass::Metadata,
+            "GET",
+            "https://api.figma.com/v1/files/abc?depth=2",
+        );
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert!(a.starts_with("metadata-"));
+    }
+
+    #[test]
+    fn replay_fails_loudly_on_unmatched_request() {
+        let dir = tempdir().expect("tempdir");
+        let store = FixtureStore::new(dir.path());
+        let err = store
+            .replay(
+                RequestClass::Metadata,
+                "GET",
+                "https://api.figma.com/v1/files/missing",
+            )
+            .expect_err("missing fixture should fail");
+        assert!(err.to_string().contains("Replay fixture not found"));
+    }
+
+    #[tokio::test]
+    async fn recorded_response_replays_with_same_body() {
+        let dir = tempdir().expect("tempdir");
+        let store = FixtureStore::new(dir.path());
+        let url = "https://s3.example.com/images/abc.png";
+        let original = Response::from(
+            http::Response::builder()
+                .status(200)
+                .header("content-type", "image/png")
+                .body(vec![1u8, 2, 3, 255])
+                .expect("response"),
+        );
+
+        let passthrough = store
+            .record(RequestClass::Download, "GET", url, original)
+            .await
+            .expect("record");
+        assert_eq!(
+            passthrough.bytes().await.expect("bytes").to_vec(),
+            vec![1, 2, 3, 255]
+        );
+
+        let replayed = store
+            .replay(RequestClass::Download, "GET", url)
+            .expect("replay");
+        assert_eq!(replayed.status().as_u16(), 200);
+        assert_eq!(replayed.headers().get("content-type").unwrap(), "image/png");
+        assert_eq!(
+            replayed.bytes().await.expect("bytes").to_vec(),
+            vec![1, 2, 3, 255]
+        );
+    }
+
+    #[test]
+    fn mode_from_flags_prefers_replay() {
+        assert_eq!(FixtureMode::from_flags(None, None), FixtureMode::Live);
+        assert_eq!(
+            FixtureMode::from_flags(Some("rec".into()), None),
+            FixtureMode::Record("rec".into())
+        );
+        assert!(FixtureMode::from_flags(Some("rec".into()), Some("rep".into())).is_replay());
+    }
+}
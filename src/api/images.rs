@@ -128,10 +128,12 @@ impl FigmaClient {
     ///
     /// This downloads from Figma's S3 bucket, not the API, so rate limiting
     /// is less of a concern. We still use the execute_request wrapper for
-    /// consistent error handling.
+    /// consistent error handling. The URL is rewritten onto the configured
+    /// image host, if any.
     pub async fn download_image(&self, url: &str) -> Result<Vec<u8>> {
+        let url = self.endpoints().download_url(url);
         let response = self
-            .execute_request(RequestClass::Download, || self.http().get(url))
+            .execute_request(RequestClass::Download, || self.http().get(&url))
             .await?;
 
         if !response.status().is_success() {
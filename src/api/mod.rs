@@ -1,12 +1,13 @@
 pub mod cache;
 pub mod client;
 pub mod files;
+pub mod fixtures;
 pub mod images;
 pub mod rate_limit;
 pub mod types;
 pub mod url;
 
 pub use cache::{create_shared_cache, CacheKey, CacheStats, CacheTTL, FigmaCache};
-pub use client::FigmaClient;
+pub use client::{ApiError, FigmaClient, FigmaEndpoints};
 pub use rate_limit::RateLimiter;
 pub use url::FigmaUrl;
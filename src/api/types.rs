@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 
 /// User information returned by /v1/me
 #[derive(Debug, Deserialize)]
@@ -33,6 +34,11 @@ pub struct Document {
 }
 
 /// Generic node in the Figma document tree
+///
+/// Properties not modelled here are kept in `extras` so nothing from the API
+/// response is lost. Typed layout, effect and variable fields are read
+/// leniently: a value of an unexpected shape is dropped rather than failing
+/// the whole file.
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Node {
@@ -45,6 +51,295 @@ pub struct Node {
     pub fills: Option<Vec<Paint>>,
     pub strokes: Option<Vec<Paint>>,
     pub style: Option<TypeStyle>,
+
+    // Visibility and compositing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub visible: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub opacity: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blend_mode: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub effects: Option<Vec<Effect>>,
+
+    // Geometry
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub corner_radius: Option<f64>,
+    /// Per-corner radii: top-left, top-right, bottom-right, bottom-left
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub rectangle_corner_radii: Option<[f64; 4]>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stroke_weight: Option<f64>,
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub individual_stroke_weights: Option<StrokeWeights>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stroke_align: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub constraints: Option<LayoutConstraint>,
+
+    // Export panel
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub export_settings: Option<Vec<ExportSetting>>,
+
+    // Auto layout (frame)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout_mode: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout_wrap: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub primary_axis_sizing_mode: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub counter_axis_sizing_mode: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub primary_axis_align_items: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub counter_axis_align_items: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub item_spacing: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub counter_axis_spacing: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub padding_left: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub padding_right: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub padding_top: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub padding_bottom: Option<f64>,
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub layout_grids: Option<Vec<LayoutGrid>>,
+
+    // Auto layout (child)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout_align: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout_grow: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout_positioning: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout_sizing_horizontal: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout_sizing_vertical: Option<String>,
+
+    // Text
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub characters: Option<String>,
+
+    // Components, styles and variables
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub component_id: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub component_properties: Option<HashMap<String, ComponentProperty>>,
+    /// Style references keyed by usage (`fill`, `text`, `effect`, ...) -> style ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub styles: Option<HashMap<String, String>>,
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub bound_variables: Option<HashMap<String, BoundVariable>>,
+
+    /// Any other properties returned by the API
+    #[serde(flatten)]
+    pub extras: HashMap<String, serde_json::Value>,
+}
+
+/// Visual effect (shadow or blur)
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Effect {
+    /// DROP_SHADOW, INNER_SHADOW, LAYER_BLUR or BACKGROUND_BLUR
+    #[serde(default, rename = "type")]
+    pub effect_type: String,
+    #[serde(default = "default_true")]
+    pub visible: bool,
+    #[serde(default)]
+    pub radius: f64,
+    pub color: Option<Color>,
+    pub blend_mode: Option<String>,
+    pub offset: Option<Vector>,
+    pub spread: Option<f64>,
+    pub show_shadow_behind_node: Option<bool>,
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub bound_variables: Option<HashMap<String, BoundVariable>>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// 2D vector
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+pub struct Vector {
+    #[serde(default)]
+    pub x: f64,
+    #[serde(default)]
+    pub y: f64,
+}
+
+/// Per-side stroke weights
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+pub struct StrokeWeights {
+    #[serde(default)]
+    pub top: f64,
+    #[serde(default)]
+    pub right: f64,
+    #[serde(default)]
+    pub bottom: f64,
+    #[serde(default)]
+    pub left: f64,
+}
+
+/// How a node is pinned when its parent resizes
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct LayoutConstraint {
+    /// TOP, BOTTOM, CENTER, TOP_BOTTOM or SCALE
+    #[serde(default)]
+    pub vertical: String,
+    /// LEFT, RIGHT, CENTER, LEFT_RIGHT or SCALE
+    #[serde(default)]
+    pub horizontal: String,
+}
+
+/// Export configured by a designer in a node's Export panel
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ExportSetting {
+    /// Appended to the node name, e.g. `@2x` or `-dark`
+    #[serde(default)]
+    pub suffix: String,
+    /// JPG, PNG, SVG or PDF
+    #[serde(default)]
+    pub format: String,
+    #[serde(default)]
+    pub constraint: ExportConstraint,
+}
+
+/// Size of an export: a scale factor, or a target width or height in pixels
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ExportConstraint {
+    /// SCALE, WIDTH or HEIGHT
+    #[serde(rename = "type")]
+    pub constraint_type: String,
+    pub value: f64,
+}
+
+impl Default for ExportConstraint {
+    /// Figma's default export size, 1x
+    fn default() -> Self {
+        Self {
+            constraint_type: "SCALE".to_string(),
+            value: 1.0,
+        }
+    }
+}
+
+/// Layout grid applied to a frame
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LayoutGrid {
+    /// COLUMNS, ROWS or GRID
+    #[serde(default)]
+    pub pattern: String,
+    pub section_size: Option<f64>,
+    #[serde(default = "default_true")]
+    pub visible: bool,
+    pub color: Option<Color>,
+    pub alignment: Option<String>,
+    pub gutter_size: Option<f64>,
+    pub offset: Option<f64>,
+    pub count: Option<i64>,
+}
+
+/// Value of a component property on an instance
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentProperty {
+    /// BOOLEAN, TEXT, INSTANCE_SWAP or VARIANT
+    #[serde(default, rename = "type")]
+    pub property_type: String,
+    #[serde(default)]
+    pub value: serde_json::Value,
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub bound_variables: Option<HashMap<String, BoundVariable>>,
+}
+
+/// Reference to a variable bound to a node property
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct VariableAlias {
+    #[serde(rename = "type")]
+    pub alias_type: String,
+    pub id: String,
+}
+
+/// A `boundVariables` entry: a single alias, a list (fills/strokes), or a nested map
+///
+/// Any other shape is kept as raw JSON in `Other`.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum BoundVariable {
+    Alias(VariableAlias),
+    List(Vec<VariableAlias>),
+    Map(HashMap<String, VariableAlias>),
+    Other(serde_json::Value),
+}
+
+impl BoundVariable {
+    /// All variable aliases in this entry; `Other` entries have none
+    pub fn aliases(&self) -> Vec<&VariableAlias> {
+        match self {
+            BoundVariable::Alias(alias) => vec![alias],
+            BoundVariable::List(list) => list.iter().collect(),
+            BoundVariable::Map(map) => map.values().collect(),
+            BoundVariable::Other(_) => Vec::new(),
+        }
+    }
+}
+
+/// `None` instead of an error when a value does not have the expected shape
+fn lenient<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: serde::de::DeserializeOwned,
+{
+    let value = Option::<serde_json::Value>::deserialize(deserializer)?;
+    Ok(value.and_then(|value| serde_json::from_value(value).ok()))
 }
 
 /// Bounding box for a node
@@ -62,12 +357,32 @@ pub struct BoundingBox {
 pub struct Paint {
     #[serde(rename = "type")]
     pub paint_type: String,
+    #[serde(default = "default_true")]
+    pub visible: bool,
     pub color: Option<Color>,
     pub opacity: Option<f64>,
+    /// Gradient start, end and width handles, normalized to the node bounds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gradient_handle_positions: Option<Vec<Vector>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gradient_stops: Option<Vec<ColorStop>>,
+    /// Image hash for IMAGE paints, resolved through the file images endpoint
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_ref: Option<String>,
+    /// FILL, FIT, TILE or STRETCH for IMAGE paints
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scale_mode: Option<String>,
+}
+
+/// Color at a position (0..1) of a gradient
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct ColorStop {
+    pub position: f64,
+    pub color: Color,
 }
 
 /// RGBA color
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct Color {
     pub r: f64,
     pub g: f64,
@@ -76,23 +391,47 @@ pub struct Color {
 }
 
 impl Color {
-    /// Convert to hex string (#RRGGBB)
+    /// Convert to hex string (#RRGGBB), ignoring alpha
     pub fn to_hex(&self) -> String {
-        format!(
-            "#{:02X}{:02X}{:02X}",
-            (self.r * 255.0) as u8,
-            (self.g * 255.0) as u8,
-            (self.b * 255.0) as u8
-        )
+        let [r, g, b] = self.to_rgb();
+        format!("#{:02X}{:02X}{:02X}", r, g, b)
+    }
+
+    /// Convert to #RRGGBB, or #RRGGBBAA when the color is translucent
+    pub fn to_hex_alpha(&self) -> String {
+        if self.a >= 1.0 {
+            self.to_hex()
+        } else {
+            format!("{}{:02X}", self.to_hex(), channel(self.a))
+        }
     }
 
-    /// Convert to RGB array [r, g, b]
+    /// Convert to RGB array [r, g, b], rounded like Figma's color picker
     pub fn to_rgb(&self) -> [u8; 3] {
-        [
-            (self.r * 255.0) as u8,
-            (self.g * 255.0) as u8,
-            (self.b * 255.0) as u8,
-        ]
+        [channel(self.r), channel(self.g), channel(self.b)]
+    }
+
+    /// Same color with a paint or layer opacity folded into alpha
+    pub fn with_opacity(&self, opacity: f64) -> Color {
+        Color {
+            a: self.a * opacity.clamp(0.0, 1.0),
+            ..self.clone()
+        }
+    }
+}
+
+fn channel(value: f64) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+impl Paint {
+    /// Color of a visible solid paint, with the paint opacity applied
+    pub fn solid_color(&self) -> Option<Color> {
+        if self.paint_type != "SOLID" || !self.visible {
+            return None;
+        }
+        let color = self.color.as_ref()?;
+        Some(color.with_opacity(self.opacity.unwrap_or(1.0)))
     }
 }
 
@@ -101,10 +440,25 @@ impl Color {
 #[serde(rename_all = "camelCase")]
 pub struct TypeStyle {
     pub font_family: Option<String>,
+    pub font_post_script_name: Option<String>,
     pub font_weight: Option<f64>,
     pub font_size: Option<f64>,
+    #[serde(default)]
+    pub italic: bool,
     pub line_height_px: Option<f64>,
+    /// Line height as a percentage of the font size
+    pub line_height_percent_font_size: Option<f64>,
+    /// `PIXELS`, `FONT_SIZE_%` or `INTRINSIC_%` (auto)
+    pub line_height_unit: Option<String>,
     pub letter_spacing: Option<f64>,
+    pub paragraph_spacing: Option<f64>,
+    /// `UPPER`, `LOWER`, `TITLE`, `SMALL_CAPS` or `SMALL_CAPS_FORCED`
+    pub text_case: Option<String>,
+    /// `NONE`, `UNDERLINE` or `STRIKETHROUGH`
+    pub text_decoration: Option<String>,
+    /// OpenType feature tags that differ from the font defaults, 1 on and 0 off
+    #[serde(default, rename = "opentypeFlags")]
+    pub opentype_flags: BTreeMap<String, u32>,
 }
 
 /// Published component
@@ -126,6 +480,126 @@ pub struct Style {
     pub description: Option<String>,
 }
 
+/// Local variables response from /v1/files/:key/variables/local
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct LocalVariablesResponse {
+    pub status: Option<u16>,
+    pub error: Option<bool>,
+    #[serde(default)]
+    pub meta: Option<LocalVariablesMeta>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalVariablesMeta {
+    #[serde(default)]
+    pub variables: HashMap<String, Variable>,
+    #[serde(default)]
+    pub variable_collections: HashMap<String, VariableCollection>,
+}
+
+/// A variable with its value in every mode of its collection
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Variable {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub key: String,
+    pub variable_collection_id: String,
+    /// COLOR, FLOAT, STRING or BOOLEAN
+    pub resolved_type: String,
+    #[serde(default)]
+    pub values_by_mode: HashMap<String, VariableValue>,
+    #[serde(default)]
+    pub remote: bool,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub hidden_from_publishing: bool,
+    #[serde(default)]
+    pub scopes: Vec<String>,
+}
+
+/// A raw variable value; aliases point at another variable by ID
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum VariableValue {
+    Alias(VariableAlias),
+    Color(Color),
+    Float(f64),
+    Boolean(bool),
+    String(String),
+}
+
+/// A group of variables sharing the same set of modes
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VariableCollection {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub key: String,
+    pub modes: Vec<VariableMode>,
+    pub default_mode_id: String,
+    #[serde(default)]
+    pub remote: bool,
+    #[serde(default)]
+    pub hidden_from_publishing: bool,
+    #[serde(default)]
+    pub variable_ids: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VariableMode {
+    pub mode_id: String,
+    pub name: String,
+}
+
+/// Published variables response from /v1/files/:key/variables/published
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct PublishedVariablesResponse {
+    pub status: Option<u16>,
+    pub error: Option<bool>,
+    #[serde(default)]
+    pub meta: Option<PublishedVariablesMeta>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PublishedVariablesMeta {
+    #[serde(default)]
+    pub variables: HashMap<String, PublishedVariable>,
+    #[serde(default)]
+    pub variable_collections: HashMap<String, PublishedVariableCollection>,
+}
+
+/// Published variable metadata (values are only available from the local endpoint)
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PublishedVariable {
+    pub id: String,
+    #[serde(rename = "subscribed_id", default)]
+    pub subscribed_id: Option<String>,
+    pub name: String,
+    pub key: String,
+    pub variable_collection_id: String,
+    pub resolved_data_type: String,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PublishedVariableCollection {
+    pub id: String,
+    #[serde(rename = "subscribed_id", default)]
+    pub subscribed_id: Option<String>,
+    pub name: String,
+    pub key: String,
+    pub updated_at: Option<String>,
+}
+
 /// Image export response
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct ImageResponse {
@@ -273,3 +747,139 @@ pub struct ContainingFrame {
     pub page_id: Option<String>,
     pub page_name: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn node_keeps_layout_effects_and_unknown_fields() {
+        let node: Node = serde_json::from_value(serde_json::json!({
+            "id": "1:2",
+            "name": "Card",
+            "type": "FRAME",
+            "layoutMode": "VERTICAL",
+            "itemSpacing": 12,
+            "paddingLeft": 16,
+            "cornerRadius": 8,
+            "rectangleCornerRadii": [8, 8, 0, 0],
+            "opacity": 0.5,
+            "blendMode": "PASS_THROUGH",
+            "constraints": { "vertical": "TOP", "horizontal": "LEFT_RIGHT" },
+            "exportSettings": [{
+                "suffix": "@2x",
+                "format": "PNG",
+                "constraint": { "type": "SCALE", "value": 2 }
+            }],
+            "effects": [{
+                "type": "DROP_SHADOW",
+                "visible": true,
+                "radius": 4,
+                "color": { "r": 0, "g": 0, "b": 0, "a": 0.25 },
+                "offset": { "x": 0, "y": 2 }
+            }],
+            "styles": { "fill": "S:abc" },
+            "boundVariables": {
+                "itemSpacing": { "type": "VARIABLE_ALIAS", "id": "VariableID:1" },
+                "fills": [{ "type": "VARIABLE_ALIAS", "id": "VariableID:2" }]
+            },
+            "clipsContent": true
+        }))
+        .expect("node should deserialize");
+
+        assert_eq!(node.layout_mode.as_deref(), Some("VERTICAL"));
+        assert_eq!(node.item_spacing, Some(12.0));
+        assert_eq!(node.rectangle_corner_radii, Some([8.0, 8.0, 0.0, 0.0]));
+        assert_eq!(node.effects.as_ref().unwrap()[0].offset.unwrap().y, 2.0);
+        assert_eq!(node.constraints.as_ref().unwrap().horizontal, "LEFT_RIGHT");
+        let export = &node.export_settings.as_ref().unwrap()[0];
+        assert_eq!(export.suffix, "@2x");
+        assert_eq!(export.constraint.constraint_type, "SCALE");
+        let bound = node.bound_variables.as_ref().unwrap();
+        assert_eq!(bound["fills"].aliases()[0].id, "VariableID:2");
+        assert_eq!(node.extras["clipsContent"], serde_json::json!(true));
+
+        let round_trip = serde_json::to_value(&node).expect("serialize");
+        assert_eq!(round_trip["clipsContent"], serde_json::json!(true));
+        assert_eq!(round_trip["itemSpacing"], serde_json::json!(12.0));
+    }
+
+    #[test]
+    fn unexpected_typed_fields_do_not_fail_the_node() {
+        let node: Node = serde_json::from_value(serde_json::json!({
+            "id": "1:4",
+            "name": "Odd",
+            "type": "FRAME",
+            "constraints": { "vertical": "TOP" },
+            "layoutGrids": [{ "sectionSize": 8 }],
+            "rectangleCornerRadii": [4, 4, 4],
+            "effects": "unexpected",
+            "exportSettings": [{ "format": "PNG" }],
+            "boundVariables": {
+                "size": { "x": [{ "type": "VARIABLE_ALIAS", "id": "VariableID:3" }] },
+                "fills": [{ "type": "VARIABLE_ALIAS", "id": "VariableID:2" }]
+            }
+        }))
+        .expect("node should deserialize");
+
+        assert_eq!(node.constraints.unwrap().horizontal, "");
+        assert_eq!(node.layout_grids.unwrap()[0].pattern, "");
+        assert!(node.rectangle_corner_radii.is_none());
+        assert!(node.effects.is_none());
+        assert_eq!(node.export_settings.unwrap()[0].constraint.value, 1.0);
+        let bound = node.bound_variables.unwrap();
+        assert!(matches!(bound["size"], BoundVariable::Other(_)));
+        assert!(bound["size"].aliases().is_empty());
+        assert_eq!(bound["fills"].aliases()[0].id, "VariableID:2");
+    }
+
+    #[test]
+    fn text_node_keeps_characters_and_component_properties() {
+        let node: Node = serde_json::from_value(serde_json::json!({
+            "id": "1:3",
+            "name": "Label",
+            "type": "INSTANCE",
+            "characters": "Hello",
+            "componentId": "2:1",
+            "componentProperties": {
+                "Disabled": { "type": "BOOLEAN", "value": false }
+            }
+        }))
+        .expect("node should deserialize");
+
+        assert_eq!(node.characters.as_deref(), Some("Hello"));
+        let props = node.component_properties.unwrap();
+        assert_eq!(props["Disabled"].property_type, "BOOLEAN");
+        assert!(node.extras.is_empty());
+    }
+
+    #[test]
+    fn colors_round_and_fold_paint_opacity() {
+        let gray = Color {
+            r: 0.5,
+            g: 0.5,
+            b: 0.5,
+            a: 1.0,
+        };
+        assert_eq!(gray.to_hex(), "#808080");
+        assert_eq!(gray.to_rgb(), [128, 128, 128]);
+
+        let paint: Paint = serde_json::from_value(serde_json::json!({
+            "type": "SOLID",
+            "opacity": 0.5,
+            "color": { "r": 1.0, "g": 0.0, "b": 0.0, "a": 1.0 }
+        }))
+        .expect("paint");
+        let color = paint.solid_color().expect("solid color");
+        assert_eq!(color.to_hex(), "#FF0000");
+        assert_eq!(color.to_hex_alpha(), "#FF000080");
+
+        let hidden: Paint = serde_json::from_value(serde_json::json!({
+            "type": "SOLID",
+            "visible": false,
+            "color": { "r": 1.0, "g": 0.0, "b": 0.0, "a": 1.0 }
+        }))
+        .expect("paint");
+        assert!(hidden.solid_color().is_none());
+    }
+}
@@ -5,10 +5,12 @@ use std::sync::atomic::{AtomicBool, Ordering};
 
 use crate::output;
 
+use crate::api::fixtures;
 use crate::config::Config;
 
 const SERVICE_NAME: &str = "fgm";
 const USERNAME: &str = "figma_token";
+const REPLAY_TOKEN: &str = "fgm-replay";
 static KEYCHAIN_ENABLED: AtomicBool = AtomicBool::new(true);
 
 /// Token source information for debugging
@@ -17,6 +19,7 @@ pub enum TokenSource {
     Environment,
     Keychain,
     ConfigFile,
+    ReplayFixtures,
 }
 
 impl std::fmt::Display for TokenSource {
@@ -25,6 +28,7 @@ impl std::fmt::Display for TokenSource {
             TokenSource::Environment => write!(f, "environment variable (FIGMA_TOKEN)"),
             TokenSource::Keychain => write!(f, "system keychain"),
             TokenSource::ConfigFile => write!(f, "config file"),
+            TokenSource::ReplayFixtures => write!(f, "replay fixtures (no token needed)"),
         }
     }
 }
@@ -62,14 +66,11 @@ pub fn get_token_with_source() -> Result<TokenResult> {
     }
 
     // 2. Then check config file (default storage)
-    match get_token_from_config() {
-        Ok(token) => {
-            return Ok(TokenResult {
-                token,
-                source: TokenSource::ConfigFile,
-            });
-        }
-        Err(_) => {}
+    if let Ok(token) = get_token_from_config() {
+        return Ok(TokenResult {
+            token,
+            source: TokenSource::ConfigFile,
+        });
     }
 
     // 3. Finally check keychain (unless disabled)
@@ -88,6 +89,14 @@ pub fn get_token_with_source() -> Result<TokenResult> {
         }
     }
 
+    // 4. Replaying fixtures never hits the network, so any token will do
+    if fixtures::global_mode().is_replay() {
+        return Ok(TokenResult {
+            token: REPLAY_TOKEN.to_string(),
+            source: TokenSource::ReplayFixtures,
+        });
+    }
+
     Err(anyhow!(
         "No Figma token found. Set FIGMA_TOKEN environment variable or run 'fgm auth login'"
     ))
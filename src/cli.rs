@@ -1,6 +1,8 @@
 use clap::{Parser, Subcommand};
 use std::path::PathBuf;
 
+use crate::commands::mask::IgnoreRect;
+use crate::commands::svg_optimize::SvgOptimizeOptions;
 use crate::output::OutputFormat;
 use crate::reporting::ReportFormat;
 
@@ -77,6 +79,23 @@ pub struct Cli {
     /// Disable all keychain access (avoid macOS prompts)
     #[arg(long, global = true, help = "Disable all keychain access")]
     pub no_keychain: bool,
+    /// Record API responses as fixtures in this directory
+    #[arg(
+        long,
+        global = true,
+        value_name = "DIR",
+        conflicts_with = "replay",
+        help = "Record API responses to a fixture directory"
+    )]
+    pub record: Option<PathBuf>,
+    /// Serve API responses from recorded fixtures (no network)
+    #[arg(
+        long,
+        global = true,
+        value_name = "DIR",
+        help = "Replay API responses from a fixture directory"
+    )]
+    pub replay: Option<PathBuf>,
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -113,12 +132,26 @@ pub enum Commands {
 Calculates the percentage of pixels that differ between images.
 Useful for visual regression testing and design-to-code verification.
 
+--method picks how pixels are judged: pixel (per-channel tolerance, the
+default), antialias (pixelmatch-style color distance that ignores anti-aliased
+edges, drawn yellow in the diff image), delta-e (CIEDE2000 color distance) or
+ssim (structural similarity of 8x8 blocks, with the block scores in the JSON
+output and as a heat map in the diff image). Transparent pixels are compared
+as if drawn on white.
+
+--ignore rectangles and --ignore-mask PNGs (white = ignored) leave regions such
+as clocks or avatars out of both the diff percentage and the diff image.
+
 Exit code is 1 if difference exceeds threshold (for CI integration).")]
     #[command(after_help = "EXAMPLES:
     fgm compare design.png screenshot.png
     fgm compare design.png screenshot.png --threshold 3
     fgm compare design.png screenshot.png --output diff.png
-    fgm compare ./designs/ ./screenshots/ --batch --report report.json")]
+    fgm compare design.png screenshot.png --method antialias --output diff.png
+    fgm compare design.png screenshot.png --method ssim --min-ssim 0.9
+    fgm compare design.png screenshot.png --ignore 0,0,750,88 --ignore-mask avatars.png
+    fgm compare ./designs/ ./screenshots/ --batch --report report.json
+    fgm compare ./designs/ ./screenshots/ --batch --jobs 8")]
     Compare(CompareArgs),
 
     /// Export from Figma URL and compare against a screenshot in one step
@@ -129,12 +162,27 @@ Exit code is 1 if difference exceeds threshold (for CI integration).")]
 This is a convenience command that combines 'export' and 'compare' into one step.
 The Figma URL must include a node-id parameter to specify which frame to export.
 
+Before diffing, the screenshot is normalized: its device-pixel ratio to the export
+is detected from the widths and it is resampled to the export's size, --device
+crops the status bar and safe areas from both images, and with --align-search N
+the screenshot is shifted by up to N pixels to the best-matching offset. Alignment
+is off by default since it hides layout shifts of that size; a warning is printed
+whenever a non-zero offset is applied.
+
+--ignore, --ignore-mask and --ignore-node (a Figma node inside the frame) leave
+regions out of the comparison; coordinates are in export pixels.
+
+Differing pixels are clustered into regions, each named after the smallest layer
+of the frame that covers it (\"Button/Primary (1:234) differs in a 120x44 area\").
+
 Exit code is 1 if difference exceeds threshold (for CI integration)."
     )]
     #[command(after_help = "EXAMPLES:
     fgm compare-url \"https://figma.com/design/abc?node-id=1-2\" screenshot.png
     fgm compare-url \"https://figma.com/design/abc?node-id=1-2\" dev.png --threshold 3
-    fgm compare-url \"https://figma.com/design/abc?node-id=1-2\" dev.png -o diff.png -s 3")]
+    fgm compare-url \"https://figma.com/design/abc?node-id=1-2\" dev.png -o diff.png -s 3
+    fgm compare-url \"https://figma.com/design/abc?node-id=1-2\" dev.png --ignore-node 1:234
+    fgm compare-url \"https://figma.com/design/abc?node-id=1-2\" iphone.png --device iphone-dynamic-island")]
     CompareUrl(CompareUrlArgs),
 
     /// Extract design tokens (colors, typography, spacing)
@@ -143,6 +191,12 @@ Exit code is 1 if difference exceeds threshold (for CI integration)."
         command: TokensCommands,
     },
 
+    /// Audit a Figma file for accessibility issues
+    Audit {
+        #[command(subcommand)]
+        command: AuditCommands,
+    },
+
     /// Browse published library components
     Components {
         #[command(subcommand)]
@@ -209,7 +263,9 @@ MANIFEST FORMAT:
     fgm config show
     fgm config get defaults.output_format
     fgm config set defaults.output_format table
-    fgm config set defaults.team_id 123456789")]
+    fgm config set defaults.team_id 123456789
+    fgm config set api.base_url http://127.0.0.1:8080/v1
+    fgm config set api.base_url --unset")]
     Config {
         #[command(subcommand)]
         command: ConfigCommands,
@@ -391,6 +447,11 @@ You can export specific nodes by ID, or use --all-frames to export all
 top-level frames in the file. Supports PNG, SVG, PDF, and JPG formats.
 
 Use --platform to generate all required sizes for iOS, Android, or Web.
+iOS exports are written as an Assets.xcassets catalog of imagesets (add
+--colorsets for a colorset per named color style); with --format svg or pdf the
+imagesets hold a single vector image that preserves its vector representation. Android
+exports with --format svg are converted to VectorDrawable XML under drawable/;
+nodes using masks, filters or unsupported gradients fall back to PNG densities.
 Use --llm-pack to emit a manifest.json for LLM workflows.")]
     #[command(after_help = "EXAMPLES:
     # LLM-first export from a URL (all top-level screens + manifest)
@@ -405,6 +466,15 @@ Use --llm-pack to emit a manifest.json for LLM workflows.")]
     # Low-rate profile for aggressive cache-first exports
     fgm export file \"https://www.figma.com/design/abc123/MyFile\" --all-frames --profile low-rate --delta -o ./exports/
 
+    # WebP for the web (PNG render transcoded locally; also --format avif)
+    fgm export file \"https://www.figma.com/design/abc123/MyFile\" --all-frames --format webp --quality 75 -o ./web/
+
+    # Use the export settings designers configured on each node
+    fgm export file abc123 --all-frames --use-export-settings -o ./assets/
+
+    # Optimized SVG icon that inherits the text color
+    fgm export file abc123 --node \"1:2\" --format svg --optimize-svg --svg-current-color -o ./icons/
+
     # Export a single node from URL
     fgm export file \"https://www.figma.com/design/abc123/MyFile?node-id=1-2\" -o ./out/
 
@@ -420,8 +490,17 @@ Use --llm-pack to emit a manifest.json for LLM workflows.")]
     # Export for iOS (generates @1x, @2x, @3x)
     fgm export file abc123 --node \"1:2\" --platform ios -o ./ios/
 
+    # Export vector imagesets for iOS
+    fgm export file abc123 --node \"1:2\" --platform ios --format pdf -o ./ios/
+
+    # Add colorsets for the file's color styles to the iOS catalog
+    fgm export file abc123 --node \"1:2\" --platform ios --colorsets -o ./ios/
+
     # Export for Android (generates drawable-mdpi through xxxhdpi)
-    fgm export file abc123 --node \"1:2\" --platform android -o ./android/")]
+    fgm export file abc123 --node \"1:2\" --platform android -o ./android/
+
+    # Export Android VectorDrawables (drawable/*.xml)
+    fgm export file abc123 --node \"1:2\" --platform android --format svg -o ./res/")]
     File {
         /// Figma file key or URL (node-id in URL will be used automatically)
         #[arg(help = "File key (abc123) or URL with optional ?node-id=")]
@@ -440,9 +519,16 @@ Use --llm-pack to emit a manifest.json for LLM workflows.")]
         /// Interactively pick node IDs from the top-level frame list
         #[arg(long, conflicts_with = "all_frames", help = "Pick nodes interactively")]
         pick: bool,
-        /// Image format: png, svg, pdf, jpg
+        /// Image format: png, svg, pdf, jpg, webp, avif
         #[arg(short, long, help = "Output format")]
         format: Option<ExportFormat>,
+        /// Encoder quality for webp, avif and jpg (1-100)
+        #[arg(
+            long,
+            value_parser = clap::value_parser!(u8).range(1..=100),
+            help = "Encoder quality for webp, avif and jpg (1-100)"
+        )]
+        quality: Option<u8>,
         /// Scale factor (1-4, default: 2)
         #[arg(
             short,
@@ -460,6 +546,9 @@ Use --llm-pack to emit a manifest.json for LLM workflows.")]
         /// Generate platform-specific sizes (ios, android, web)
         #[arg(long, help = "Export all sizes for platform")]
         platform: Option<Platform>,
+        /// Also write a colorset per named color style into the iOS asset catalog
+        #[arg(long, help = "Write colorsets for color styles (--platform ios)")]
+        colorsets: bool,
         /// Emit an LLM-focused manifest JSON alongside exported images
         #[arg(long, help = "Write manifest JSON with metadata for LLM workflows")]
         llm_pack: bool,
@@ -480,6 +569,15 @@ Use --llm-pack to emit a manifest.json for LLM workflows.")]
         /// Apply a preset export profile
         #[arg(long, value_enum, help = "Export profile preset")]
         profile: Option<ExportProfile>,
+        #[command(flatten)]
+        svg: SvgOptimizeArgs,
+        /// Export what designers configured in each node's Export panel
+        #[arg(
+            long,
+            conflicts_with_all = ["format", "quality", "scale", "name", "platform"],
+            help = "Use the nodes' Figma export settings (format, scale, suffix)"
+        )]
+        use_export_settings: bool,
         /// Re-run the export when the Figma file version changes
         #[arg(long, help = "Watch for file version changes and re-run the export")]
         watch: bool,
@@ -519,15 +617,104 @@ EXAMPLE:
         /// Path to manifest file (TOML format)
         #[arg(help = "Path to the TOML manifest file")]
         manifest: PathBuf,
+        #[command(flatten)]
+        svg: SvgOptimizeArgs,
+    },
+
+    /// Build an SVG sprite and typed manifests from a page of icon components
+    #[command(
+        long_about = "Export every component under a page or frame as one SVG sprite.
+
+Each COMPONENT (including component set variants) is exported as SVG in
+adaptive batches and written to svg/<name>.svg. The icons are combined into a
+sprite of <symbol> elements named after the components, plus typed manifests
+of the icon names: a TypeScript union type, a Swift enum and a Kotlin object.
+
+Icons whose node is unchanged since the last run are reused from disk instead
+of being exported and downloaded again."
+    )]
+    #[command(after_help = "EXAMPLES:
+    # All components on the Icons page
+    fgm export icons abc123 --page Icons -o ./icons/
+
+    # Components inside one frame, optimized to follow the text color
+    fgm export icons \"https://www.figma.com/design/abc123/MyFile?node-id=1-2\" --optimize-svg --svg-current-color -o ./icons/
+
+    # Only the TypeScript manifest
+    fgm export icons abc123 --page Icons --manifest ts -o ./src/icons/
+
+USAGE:
+    <svg><use href=\"icons.svg#arrow-left\"/></svg>")]
+    Icons {
+        /// Figma file key or URL (node-id in URL selects the icon frame)
+        #[arg(help = "File key (abc123) or URL with optional ?node-id=")]
+        file_key_or_url: String,
+        /// Page holding the icon components
+        #[arg(long, help = "Page to collect components from (e.g. Icons)")]
+        page: Option<String>,
+        /// Frame or section holding the icon components
+        #[arg(
+            short,
+            long,
+            conflicts_with = "page",
+            help = "Node ID to collect components from"
+        )]
+        node: Option<String>,
+        /// Output directory
+        #[arg(short, long, help = "Where to save the sprite, icons and manifests")]
+        output: Option<PathBuf>,
+        /// Sprite filename
+        #[arg(long, default_value = "icons.svg", help = "Sprite filename")]
+        sprite_name: String,
+        /// Typed manifests to write (default: all)
+        #[arg(
+            long,
+            value_enum,
+            help = "Manifest to write (repeatable: ts, swift, kotlin)"
+        )]
+        manifest: Vec<IconManifest>,
+        /// Skip rewriting icon files when content is unchanged
+        #[arg(
+            long,
+            default_value_t = true,
+            action = clap::ArgAction::Set,
+            help = "Resume mode: skip unchanged files"
+        )]
+        resume: bool,
+        /// Skip exporting and downloading icons whose node is unchanged
+        #[arg(
+            long,
+            default_value_t = true,
+            action = clap::ArgAction::Set,
+            help = "Delta mode: reuse unchanged icons"
+        )]
+        delta: bool,
+        #[command(flatten)]
+        svg: SvgOptimizeArgs,
     },
 }
 
+/// Typed icon name manifest written by `export icons`
+#[derive(Debug, Clone, PartialEq, clap::ValueEnum)]
+pub enum IconManifest {
+    /// `icons.ts` with an `IconName` union type
+    Ts,
+    /// `Icons.swift` with an `Icon` enum
+    Swift,
+    /// `Icons.kt` with an `Icons` object
+    Kotlin,
+}
+
 #[derive(Debug, Clone, clap::ValueEnum)]
 pub enum ExportFormat {
     Png,
     Svg,
     Pdf,
     Jpg,
+    /// Transcoded locally from a PNG render
+    Webp,
+    /// Transcoded locally from a PNG render
+    Avif,
 }
 
 impl std::fmt::Display for ExportFormat {
@@ -537,6 +724,8 @@ impl std::fmt::Display for ExportFormat {
             ExportFormat::Svg => write!(f, "svg"),
             ExportFormat::Pdf => write!(f, "pdf"),
             ExportFormat::Jpg => write!(f, "jpg"),
+            ExportFormat::Webp => write!(f, "webp"),
+            ExportFormat::Avif => write!(f, "avif"),
         }
     }
 }
@@ -548,6 +737,8 @@ impl ExportFormat {
             "svg" => Some(ExportFormat::Svg),
             "pdf" => Some(ExportFormat::Pdf),
             "jpg" | "jpeg" => Some(ExportFormat::Jpg),
+            "webp" => Some(ExportFormat::Webp),
+            "avif" => Some(ExportFormat::Avif),
             _ => None,
         }
     }
@@ -559,6 +750,8 @@ pub enum ExportProfile {
     PixelPerfect,
     /// Low-rate defaults optimized for cache-first operation
     LowRate,
+    /// WebP at quality 80 and 2x for shipping on the web
+    Web,
 }
 
 #[derive(clap::Args, Debug)]
@@ -627,9 +820,54 @@ pub struct CompareArgs {
         help = "Per-channel pixel tolerance"
     )]
     pub tolerance: u8,
+    /// Comparison algorithm
+    #[arg(long, value_enum, default_value_t = DiffMethod::Pixel, help = "Comparison method")]
+    pub method: DiffMethod,
+    /// YIQ color threshold for --method antialias (0-1)
+    #[arg(
+        long,
+        default_value = "0.1",
+        value_parser = clap::value_parser!(f32),
+        help = "Color threshold for --method antialias (0-1)"
+    )]
+    pub aa_threshold: f32,
+    /// Largest CIEDE2000 distance still treated as equal for --method delta-e
+    #[arg(
+        long,
+        default_value = "2.3",
+        value_parser = clap::value_parser!(f32),
+        help = "Max CIEDE2000 distance for --method delta-e"
+    )]
+    pub max_delta_e: f32,
+    /// Smallest SSIM score a block may have for --method ssim (0-1)
+    #[arg(
+        long,
+        default_value = "0.95",
+        value_parser = clap::value_parser!(f32),
+        help = "Min block SSIM score for --method ssim (0-1)"
+    )]
+    pub min_ssim: f32,
+    /// Regions to leave out of the comparison, as x,y,width,height in pixels
+    #[arg(
+        long = "ignore",
+        value_name = "X,Y,W,H",
+        help = "Ignore a rectangle in image pixels (repeatable)"
+    )]
+    pub ignore: Vec<IgnoreRect>,
+    /// Mask image whose light, opaque pixels are left out of the comparison
+    #[arg(long, help = "PNG mask of regions to ignore (white = ignored)")]
+    pub ignore_mask: Option<PathBuf>,
     /// Stop early once threshold is exceeded (faster, approximate diff)
     #[arg(long, help = "Stop early once threshold is exceeded (faster)")]
     pub fast: bool,
+    /// Image pairs compared at once with --batch
+    #[arg(
+        long,
+        default_value = "4",
+        value_parser = clap::value_parser!(u16).range(1..),
+        help = "Max image pairs compared concurrently in --batch mode"
+    )]
+    pub jobs: u16,
     /// Compare all images in two directories
     #[arg(long, help = "Treat paths as directories, compare matching filenames")]
     pub batch: bool,
@@ -646,6 +884,104 @@ pub struct CompareArgs {
     pub report_format: ReportFormat,
 }
 
+// Audit subcommands
+#[derive(Subcommand)]
+pub enum AuditCommands {
+    /// Check text contrast against the background behind it
+    #[command(
+        long_about = "Check the contrast of every visible TEXT node against its background.
+
+The background is composited from the fills of the node's ancestors and of
+the sibling layers painted beneath it, on top of the page color. Text fills,
+paint opacity and layer opacity are blended in before the WCAG 2.x contrast
+ratio is computed.
+
+Large text (24px, or 18.66px at weight 700 and up) needs 3:1 for AA and 4.5:1
+for AAA; other text needs 4.5:1 for AA and 7:1 for AAA. Failures make the
+command exit non-zero. Text over gradients or images is reported as a warning
+to check by hand.
+
+With --apca, the APCA lightness contrast (Lc) is added to each result and text
+below the APCA minimum for its size and weight is reported as a warning.
+
+A node-id in the URL limits the audit to that frame."
+    )]
+    #[command(after_help = "EXAMPLES:
+    fgm audit contrast \"https://www.figma.com/design/abc123/MyFile\"
+    fgm audit contrast \"https://www.figma.com/design/abc123/MyFile?node-id=1-2\" --level aaa
+    fgm audit contrast abc123 --apca --report contrast.xml --report-format junit")]
+    Contrast {
+        /// Figma file key or URL
+        #[arg(help = "File key (abc123) or Figma URL, optionally with a node-id")]
+        file_key_or_url: String,
+        /// WCAG conformance level to check
+        #[arg(long, value_enum, default_value = "aa", help = "WCAG level: aa, aaa")]
+        level: WcagLevel,
+        /// Also compute APCA contrast
+        #[arg(long, help = "Add APCA Lc values and warnings")]
+        apca: bool,
+        /// Save audit results to a report file
+        #[arg(long, help = "Write an audit report to this path")]
+        report: Option<PathBuf>,
+        /// Report format for --report
+        #[arg(
+            long,
+            default_value = "json",
+            requires = "report",
+            help = "Report format for --report"
+        )]
+        report_format: ReportFormat,
+    },
+}
+
+/// How `compare` decides two pixels differ
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    serde::Deserialize,
+    clap::ValueEnum,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum DiffMethod {
+    /// Any channel (including alpha) differs by more than --tolerance
+    #[default]
+    Pixel,
+    /// YIQ color distance above --aa-threshold, ignoring anti-aliased edges
+    Antialias,
+    /// CIEDE2000 color distance above --max-delta-e
+    DeltaE,
+    /// Structural similarity of 8x8 blocks below --min-ssim
+    Ssim,
+}
+
+/// Status bar and safe-area insets cropped by `compare-url --device`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum DevicePreset {
+    /// iPhone with a home button: 20pt status bar
+    IphoneSe,
+    /// iPhone with a notch: 47pt status bar, 34pt home indicator
+    IphoneNotch,
+    /// iPhone with the Dynamic Island: 59pt status bar, 34pt home indicator
+    IphoneDynamicIsland,
+    /// Android with three-button navigation: 24dp status bar, 48dp navigation bar
+    Android,
+    /// Android with gesture navigation: 24dp status bar, 24dp gesture bar
+    AndroidGesture,
+}
+
+/// WCAG 2.x conformance level
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum WcagLevel {
+    Aa,
+    Aaa,
+}
+
 // Tokens subcommands
 #[derive(Subcommand)]
 pub enum TokensCommands {
@@ -675,8 +1011,9 @@ Shows font family, size, weight, and line height for each style."
     /// Extract spacing values from auto-layout frames
     #[command(long_about = "Analyze auto-layout frames to extract spacing values.
 
-Finds consistent padding and gap values used throughout the file.
-Useful for building a spacing scale.")]
+Finds consistent padding and gap values used throughout the file,
+infers the scale base (8, 4 or 2px) and shows how often each value is used.
+Spacing is also included in `fgm tokens export` for every format.")]
     Spacing {
         /// Figma file key or URL
         #[arg(help = "File key (abc123) or Figma URL")]
@@ -685,27 +1022,133 @@ Useful for building a spacing scale.")]
 
     /// Export all design tokens to a file
     #[command(
-        long_about = "Export all design tokens to JSON, CSS, Swift, or Kotlin format.
-
-Combines colors, typography, and spacing into a single output file.
-Useful for syncing design tokens to code."
+        long_about = "Export all design tokens to JSON, CSS, Swift, Kotlin, Tailwind, Style
+Dictionary, Android XML, or DTCG (.tokens.json) format.
+
+Combines colors, gradients, typography, spacing, shadows, blurs, corner radii
+and stroke widths into a single output file. Useful for syncing design tokens
+to code.
+
+Shadows and blurs are named after effect styles; unstyled ones are named after
+a hash of their layers (shadow-5d41402abc4b2a76) or after their radius (blur-8). Blur radii in code
+formats are half the Figma value to match how Figma renders them.
+
+Gradients become CSS linear/radial/conic-gradient() values, SwiftUI
+LinearGradient/EllipticalGradient/AngularGradient and Compose Brushes. Image
+fills are listed by image hash in the JSON output.
+
+Colors and text styles are named after their Figma styles (primary/500 is
+nested as primary.500 where the format allows). Values used without a style
+are exported separately as unnamed tokens.
+
+Colors keep their alpha (#RRGGBBAA) with paint opacity applied, so translucent
+variants are separate tokens. CSS, Tailwind and Style Dictionary colors can be
+written as hsl(), oklch() or color(display-p3) with --color-space.
+
+Figma Variables are exported per mode: CSS gets [data-theme] blocks, Swift
+gets per-mode enums with dynamic light/dark colors, Kotlin gets per-mode
+objects, and Android dark-mode overrides are written to values-night/.
+Variables are kept apart from style tokens: --var-* properties, var_* Android
+resources and <Collection>Variables Swift and Kotlin types."
     )]
     #[command(after_help = "EXAMPLES:
     fgm tokens export abc123 --format json -o tokens.json
     fgm tokens export abc123 --format css -o tokens.css
     fgm tokens export abc123 --format swift -o DesignTokens.swift
-    fgm tokens export abc123 --format kotlin -o DesignTokens.kt")]
+    fgm tokens export abc123 --format kotlin -o DesignTokens.kt
+    fgm tokens export abc123 --format dtcg -o design.tokens.json
+    fgm tokens export abc123 --format css --variables local --preserve-aliases
+    fgm tokens export abc123 --format css --color-space oklch
+    fgm tokens export abc123 --format android-xml -o res/values/fgm_tokens.xml")]
     Export {
         /// Figma file key or URL
         #[arg(help = "File key (abc123) or Figma URL")]
         file_key: String,
-        /// Output format: json, css, swift, kotlin
+        /// Output format: json, css, swift, kotlin, tailwind, style-dictionary, android-xml, dtcg
         #[arg(short, long, default_value = "json", help = "Token output format")]
         format: TokenFormat,
         /// Output file path (prints to stdout if not specified)
         #[arg(short, long, help = "Save to file instead of stdout")]
         output: Option<PathBuf>,
+        /// Where to read Figma Variables from
+        #[arg(
+            long,
+            value_enum,
+            default_value = "auto",
+            help = "Variables source: auto, local, published, none"
+        )]
+        variables: VariablesSource,
+        /// Keep alias variables as references instead of resolving them
+        #[arg(long, help = "Emit alias variables as references")]
+        preserve_aliases: bool,
+        /// Color notation for css, tailwind and style-dictionary (defaults to tokens.color_space)
+        #[arg(long, value_enum, help = "Color notation: hex, hsl, oklch, display-p3")]
+        color_space: Option<ColorSpace>,
     },
+
+    /// Compare tokens between two file versions or exported token files
+    #[command(long_about = "Compare colors and typography between two token sources.
+
+Each side is a file key or URL (current version), a file key pinned to a
+version ID from `fgm files versions` (abc123@4567), or a tokens file written
+by `fgm tokens export --format json` or `--format dtcg`.
+
+Removed tokens fail the diff (non-zero exit); changed values are warnings
+unless --fail-on-change is set. Unnamed colors are named after their value, so
+a retouched raw color is paired with the closest new one (within CIEDE2000 10
+and 25% alpha) and shown as changed.")]
+    #[command(after_help = "EXAMPLES:
+    fgm tokens diff abc123@4567 abc123
+    fgm tokens diff tokens.json abc123 --report tokens-diff.md --report-format md
+    fgm tokens diff release.tokens.json next.tokens.json --fail-on-change --report diff.xml --report-format junit")]
+    Diff {
+        /// Baseline token source
+        #[arg(help = "Baseline: file key, key@version, URL, or tokens JSON file")]
+        from: String,
+        /// Token source to compare against the baseline
+        #[arg(help = "Comparison: file key, key@version, URL, or tokens JSON file")]
+        to: String,
+        /// Treat changed values as failures
+        #[arg(long, help = "Fail when token values change, not only when removed")]
+        fail_on_change: bool,
+        /// Write a diff report to this path
+        #[arg(long, help = "Save diff results to a report file")]
+        report: Option<PathBuf>,
+        /// Report format for --report
+        #[arg(
+            long,
+            default_value = "json",
+            requires = "report",
+            help = "Report format for --report"
+        )]
+        report_format: ReportFormat,
+    },
+}
+
+/// Source of Figma Variables for token export
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum VariablesSource {
+    /// Local variables when the plan and token allow it, otherwise skip (warning on other errors)
+    Auto,
+    /// Local variables; fail if unavailable
+    Local,
+    /// Local variables that are published to the team library
+    Published,
+    /// Do not export variables
+    None,
+}
+
+/// Color notation for CSS, Tailwind and Style Dictionary color tokens
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum ColorSpace {
+    /// #RRGGBB, or #RRGGBBAA for translucent colors
+    Hex,
+    /// hsl(h s% l% / a)
+    Hsl,
+    /// oklch(l% c h / a)
+    Oklch,
+    /// color(display-p3 r g b / a)
+    DisplayP3,
 }
 
 #[derive(Clone, clap::ValueEnum)]
@@ -717,6 +1160,8 @@ pub enum TokenFormat {
     Tailwind,
     StyleDictionary,
     AndroidXml,
+    /// W3C Design Tokens Community Group format (.tokens.json)
+    Dtcg,
 }
 
 // Components subcommands
@@ -830,6 +1275,72 @@ pub struct CompareUrlArgs {
         help = "Per-channel pixel tolerance"
     )]
     pub tolerance: u8,
+    /// Comparison algorithm
+    #[arg(long, value_enum, default_value_t = DiffMethod::Pixel, help = "Comparison method")]
+    pub method: DiffMethod,
+    /// YIQ color threshold for --method antialias (0-1)
+    #[arg(
+        long,
+        default_value = "0.1",
+        value_parser = clap::value_parser!(f32),
+        help = "Color threshold for --method antialias (0-1)"
+    )]
+    pub aa_threshold: f32,
+    /// Largest CIEDE2000 distance still treated as equal for --method delta-e
+    #[arg(
+        long,
+        default_value = "2.3",
+        value_parser = clap::value_parser!(f32),
+        help = "Max CIEDE2000 distance for --method delta-e"
+    )]
+    pub max_delta_e: f32,
+    /// Smallest SSIM score a block may have for --method ssim (0-1)
+    #[arg(
+        long,
+        default_value = "0.95",
+        value_parser = clap::value_parser!(f32),
+        help = "Min block SSIM score for --method ssim (0-1)"
+    )]
+    pub min_ssim: f32,
+    /// Regions to leave out of the comparison, as x,y,width,height in pixels
+    #[arg(
+        long = "ignore",
+        value_name = "X,Y,W,H",
+        help = "Ignore a rectangle in export pixels (repeatable)"
+    )]
+    pub ignore: Vec<IgnoreRect>,
+    /// Mask image whose light, opaque pixels are left out of the comparison
+    #[arg(long, help = "PNG mask of regions to ignore (white = ignored)")]
+    pub ignore_mask: Option<PathBuf>,
+    /// Figma nodes whose bounding boxes are left out of the comparison
+    #[arg(
+        long,
+        value_name = "NODE_ID",
+        help = "Ignore the bounds of a Figma node in the frame (repeatable)"
+    )]
+    pub ignore_node: Vec<String>,
+    /// Crop the status bar and safe-area insets of this device from both images
+    #[arg(
+        long,
+        value_enum,
+        help = "Crop status bar and safe areas for this device"
+    )]
+    pub device: Option<DevicePreset>,
+    /// Largest shift in pixels tried when aligning the screenshot
+    #[arg(
+        long,
+        default_value = "0",
+        value_parser = clap::value_parser!(u32),
+        help = "Max alignment offset in pixels (0 disables alignment)"
+    )]
+    pub align_search: u32,
+    /// Compare the images as they are, without resampling, cropping or alignment
+    #[arg(
+        long,
+        conflicts_with_all = ["device", "align_search"],
+        help = "Skip scale detection, cropping and alignment"
+    )]
+    pub no_normalize: bool,
     /// Stop early once threshold is exceeded (faster, approximate diff)
     #[arg(long, help = "Stop early once threshold is exceeded (faster)")]
     pub fast: bool,
@@ -966,6 +1477,30 @@ Optionally generates visual diff images for changed frames.")]
     },
 }
 
+/// SVG optimizer flags shared by the export commands and sync
+#[derive(clap::Args, Debug, Clone, Default)]
+pub struct SvgOptimizeArgs {
+    /// Run the built-in optimizer over SVG exports
+    #[arg(
+        long,
+        help = "Optimize SVG exports (precision, ids, groups, empty defs)"
+    )]
+    pub optimize_svg: bool,
+    /// Replace solid fills and strokes with currentColor when optimizing SVGs
+    #[arg(
+        long,
+        requires = "optimize_svg",
+        help = "Use currentColor for SVG fills and strokes (icons)"
+    )]
+    pub svg_current_color: bool,
+}
+
+impl SvgOptimizeArgs {
+    pub fn options(&self) -> Option<SvgOptimizeOptions> {
+        SvgOptimizeOptions::from_flags(self.optimize_svg, self.svg_current_color)
+    }
+}
+
 // Sync arguments - declarative asset management
 #[derive(clap::Args)]
 pub struct SyncArgs {
@@ -978,6 +1513,14 @@ pub struct SyncArgs {
     /// Force re-download even if files exist
     #[arg(long, help = "Re-download all assets, even if unchanged")]
     pub force: bool,
+    #[command(flatten)]
+    pub svg: SvgOptimizeArgs,
+    /// Export what designers configured in each node's Export panel
+    #[arg(
+        long,
+        help = "Use the nodes' Figma export settings instead of the manifest format/scale"
+    )]
+    pub use_export_settings: bool,
     /// Write a sync report to this path
     #[arg(long, help = "Save sync results to a report file")]
     pub report: Option<PathBuf>,
@@ -1135,7 +1678,7 @@ the Figma component, enabling coverage tracking."
 pub enum Platform {
     /// iOS asset catalog (@1x, @2x, @3x)
     Ios,
-    /// Android drawable resources (mdpi, hdpi, xhdpi, xxhdpi, xxxhdpi)
+    /// Android drawable resources (mdpi to xxxhdpi, or VectorDrawable XML with --format svg)
     Android,
     /// Web (1x, 2x)
     Web,
@@ -1204,6 +1747,18 @@ mod tests {
         assert!(help.contains("--no-color"));
     }
 
+    #[test]
+    fn record_and_replay_flags_conflict() {
+        let parsed = Cli::try_parse_from(["fgm", "--replay", "fixtures", "files", "get", "abc"])
+            .expect("replay should parse");
+        assert_eq!(parsed.replay, Some(PathBuf::from("fixtures")));
+
+        let conflict = Cli::try_parse_from([
+            "fgm", "--record", "a", "--replay", "b", "files", "get", "abc",
+        ]);
+        assert!(conflict.is_err());
+    }
+
     #[test]
     fn help_includes_quick_mode_url_examples_and_defaults() {
         let mut cmd = Cli::command();
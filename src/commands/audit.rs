@@ -0,0 +1,681 @@
+//! Accessibility audits over the Figma document tree
+//!
+//! `contrast` resolves the color behind each TEXT node from the layers painted
+//! beneath it: the fills of its ancestors and of earlier siblings and their
+//! descendants (at every level) whose bounds cover the text, composited over
+//! the page color. Text
+//! over gradients or images cannot be judged from fills alone and is reported
+//! for a manual check.
+
+use crate::api::types::{BoundingBox, Color, Document, Node, Paint};
+use crate::api::{FigmaClient, FigmaUrl};
+use crate::auth::get_token;
+use crate::cli::{AuditCommands, WcagLevel};
+use crate::output;
+use crate::reporting::{write_report, ReportFormat, ReportItem, ReportStatus, ReportSummary};
+use anyhow::{bail, Result};
+use colored::Colorize;
+use serde::Serialize;
+use std::path::Path;
+
+pub async fn run(command: AuditCommands) -> Result<()> {
+    match command {
+        AuditCommands::Contrast {
+            file_key_or_url,
+            level,
+            apca,
+            report,
+            report_format,
+        } => {
+            contrast(
+                &file_key_or_url,
+                level,
+                apca,
+                report.as_deref(),
+                report_format,
+            )
+            .await
+        }
+    }
+}
+
+/// Contrast of one TEXT node against the background behind it
+#[derive(Debug, Clone, Serialize)]
+pub struct ContrastFinding {
+    pub node_id: String,
+    pub name: String,
+    pub page: String,
+    /// Start of the text content
+    pub text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub font_size: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub font_weight: Option<f64>,
+    /// WCAG large text: 24px, or 18.66px at weight 700 and up
+    pub large_text: bool,
+    /// Text color blended over the background
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub foreground: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background: Option<String>,
+    /// WCAG 2.x contrast ratio (1 to 21)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ratio: Option<f64>,
+    /// APCA lightness contrast (Lc), negative for light text on dark backgrounds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub apca: Option<f64>,
+    /// Why the contrast could not be computed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unresolved: Option<String>,
+}
+
+impl ContrastFinding {
+    /// WCAG minimum contrast ratio for this text at the given level
+    pub fn required_ratio(&self, level: WcagLevel) -> f64 {
+        match (level, self.large_text) {
+            (WcagLevel::Aa, true) => 3.0,
+            (WcagLevel::Aa, false) | (WcagLevel::Aaa, true) => 4.5,
+            (WcagLevel::Aaa, false) => 7.0,
+        }
+    }
+
+    /// APCA minimum |Lc| for fluent reading at this size and weight
+    pub fn required_apca(&self) -> f64 {
+        let size = self.font_size.unwrap_or(16.0);
+        let bold = self.font_weight.unwrap_or(400.0) >= 700.0;
+        if size >= 36.0 || (size >= 24.0 && bold) {
+            45.0
+        } else if size >= 24.0 || (size >= 16.0 && bold) {
+            60.0
+        } else {
+            75.0
+        }
+    }
+}
+
+/// A layer that may be painted beneath a text node
+struct Layer {
+    bounds: Option<BoundingBox>,
+    fills: Vec<Fill>,
+}
+
+enum Fill {
+    Solid([f64; 4]),
+    /// Gradient, image or video paint, described for the report
+    Other(&'static str),
+}
+
+/// Check every visible TEXT node, optionally only those under `scope` (a node ID)
+pub fn audit_contrast(
+    document: &Document,
+    scope: Option<&str>,
+    apca: bool,
+) -> Vec<ContrastFinding> {
+    let mut findings = Vec::new();
+    for page in document.children.iter().flatten() {
+        let canvas = page
+            .extras
+            .get("backgroundColor")
+            .and_then(|value| serde_json::from_value::<Color>(value.clone()).ok())
+            .map(|color| [color.r, color.g, color.b])
+            .unwrap_or([1.0, 1.0, 1.0]);
+        let mut walker = Walker {
+            page: &page.name,
+            canvas,
+            scope,
+            apca,
+            layers: Vec::new(),
+            findings: &mut findings,
+        };
+        for child in page.children.iter().flatten() {
+            walker.walk(
+                child,
+                1.0,
+                scope.is_none() || scope == Some(page.id.as_str()),
+            );
+            walker.push_painted(child, 1.0);
+        }
+    }
+    findings
+}
+
+struct Walker<'a> {
+    page: &'a str,
+    canvas: [f64; 3],
+    scope: Option<&'a str>,
+    apca: bool,
+    layers: Vec<Layer>,
+    findings: &'a mut Vec<ContrastFinding>,
+}
+
+impl Walker<'_> {
+    fn walk(&mut self, node: &Node, parent_opacity: f64, in_scope: bool) {
+        if node.visible == Some(false) {
+            return;
+        }
+        let opacity = parent_opacity * node.opacity.unwrap_or(1.0);
+        let in_scope = in_scope || self.scope == Some(node.id.as_str());
+
+        if node.node_type == "TEXT" {
+            if in_scope {
+                if let Some(finding) = self.check_text(node, opacity) {
+                    self.findings.push(finding);
+                }
+            }
+            return;
+        }
+
+        let depth = self.layers.len();
+        self.layers.push(layer(node, opacity));
+        for child in node.children.iter().flatten() {
+            self.walk(child, opacity, in_scope);
+            // Earlier siblings are painted beneath later ones
+            self.push_painted(child, opacity);
+        }
+        self.layers.truncate(depth);
+    }
+
+    /// Push the layers of `node` and everything inside it, in paint order
+    fn push_painted(&mut self, node: &Node, parent_opacity: f64) {
+        if node.node_type == "TEXT" || node.visible == Some(false) {
+            return;
+        }
+        let opacity = parent_opacity * node.opacity.unwrap_or(1.0);
+        self.layers.push(layer(node, opacity));
+        for child in node.children.iter().flatten() {
+            self.push_painted(child, opacity);
+        }
+    }
+
+    fn check_text(&self, node: &Node, opacity: f64) -> Option<ContrastFinding> {
+        let text = node.characters.as_deref().unwrap_or_default().trim();
+        if text.is_empty() {
+            return None;
+        }
+        let style = node.style.as_ref();
+        let font_size = style.and_then(|style| style.font_size);
+        let font_weight = style.and_then(|style| style.font_weight);
+        let large_text = font_size.is_some_and(|size| {
+            size >= 24.0 || (size >= 18.66 && font_weight.unwrap_or(400.0) >= 700.0)
+        });
+        let mut finding = ContrastFinding {
+            node_id: node.id.clone(),
+            name: node.name.clone(),
+            page: self.page.to_string(),
+            text: text.chars().take(40).collect(),
+            font_size,
+            font_weight,
+            large_text,
+            foreground: None,
+            background: None,
+            ratio: None,
+            apca: None,
+            unresolved: None,
+        };
+
+        // The last paint is the topmost one in Figma's paint stack
+        let fill = node.fills.iter().flatten().rfind(|paint| paint.visible);
+        let Some(text_color) = fill.and_then(Paint::solid_color) else {
+            finding.unresolved = Some("Text fill is not a solid color".to_string());
+            return Some(finding);
+        };
+        let background = match self.background(node.absolute_bounding_box.as_ref()) {
+            Ok(background) => background,
+            Err(reason) => {
+                finding.unresolved = Some(reason);
+                return Some(finding);
+            }
+        };
+        let foreground = blend(
+            [
+                text_color.r,
+                text_color.g,
+                text_color.b,
+                text_color.a * opacity,
+            ],
+            background,
+        );
+
+        finding.foreground = Some(hex(foreground));
+        finding.background = Some(hex(background));
+        finding.ratio = Some(contrast_ratio(foreground, background));
+        if self.apca {
+            finding.apca = Some(apca_contrast(foreground, background));
+        }
+        Some(finding)
+    }
+
+    /// Opaque color behind `bounds`, or why it can't be determined
+    fn background(&self, bounds: Option<&BoundingBox>) -> Result<[f64; 3], String> {
+        let mut color = self.canvas;
+        let mut unresolved: Option<String> = None;
+        for layer in &self.layers {
+            let covers = match (&layer.bounds, bounds) {
+                (Some(outer), Some(inner)) => contains(outer, inner),
+                // Ancestors without bounds (groups) paint nothing of their own
+                _ => true,
+            };
+            if !covers {
+                continue;
+            }
+            for fill in &layer.fills {
+                match fill {
+                    Fill::Solid(rgba) => {
+                        color = blend(*rgba, color);
+                        if rgba[3] >= 1.0 {
+                            unresolved = None;
+                        }
+                    }
+                    Fill::Other(kind) => {
+                        unresolved = Some(format!("Background is {} fill", kind));
+                    }
+                }
+            }
+        }
+        match unresolved {
+            Some(reason) => Err(reason),
+            None => Ok(color),
+        }
+    }
+}
+
+fn layer(node: &Node, opacity: f64) -> Layer {
+    let fills = node
+        .fills
+        .iter()
+        .flatten()
+        .filter(|paint| paint.visible)
+        .filter_map(|paint| match paint.solid_color() {
+            Some(color) => Some(Fill::Solid([color.r, color.g, color.b, color.a * opacity])),
+            None if paint.paint_type == "SOLID" => None,
+            None => Some(Fill::Other(match paint.paint_type.as_str() {
+                "IMAGE" => "an image",
+                "VIDEO" => "a video",
+                _ => "a gradient",
+            })),
+        })
+        .collect();
+    Layer {
+        bounds: node.absolute_bounding_box.clone(),
+        fills,
+    }
+}
+
+fn contains(outer: &BoundingBox, inner: &BoundingBox) -> bool {
+    // Allow for sub-pixel rounding in the API's bounds
+    const SLACK: f64 = 0.5;
+    inner.x >= outer.x - SLACK
+        && inner.y >= outer.y - SLACK
+        && inner.x + inner.width <= outer.x + outer.width + SLACK
+        && inner.y + inner.height <= outer.y + outer.height + SLACK
+}
+
+/// Source-over blend of a translucent color onto an opaque one
+fn blend(top: [f64; 4], bottom: [f64; 3]) -> [f64; 3] {
+    let alpha = top[3].clamp(0.0, 1.0);
+    [0, 1, 2].map(|i| top[i] * alpha + bottom[i] * (1.0 - alpha))
+}
+
+fn hex(rgb: [f64; 3]) -> String {
+    Color {
+        r: rgb[0],
+        g: rgb[1],
+        b: rgb[2],
+        a: 1.0,
+    }
+    .to_hex()
+}
+
+/// WCAG 2.x relative luminance of an sRGB color
+fn relative_luminance(rgb: [f64; 3]) -> f64 {
+    let [r, g, b] = rgb.map(|channel| {
+        if channel <= 0.04045 {
+            channel / 12.92
+        } else {
+            ((channel + 0.055) / 1.055).powf(2.4)
+        }
+    });
+    0.2126 * r + 0.7152 * g + 0.0722 * b
+}
+
+/// WCAG 2.x contrast ratio between two opaque colors (1 to 21)
+pub fn contrast_ratio(a: [f64; 3], b: [f64; 3]) -> f64 {
+    let (a, b) = (relative_luminance(a), relative_luminance(b));
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+/// APCA 0.0.98G lightness contrast (Lc) of text on a background
+pub fn apca_contrast(text: [f64; 3], background: [f64; 3]) -> f64 {
+    let luminance = |rgb: [f64; 3]| {
+        let y = 0.2126729 * rgb[0].powf(2.4)
+            + 0.7151522 * rgb[1].powf(2.4)
+            + 0.0721750 * rgb[2].powf(2.4);
+        // Soft clamp for near-black colors
+        if y < 0.022 {
+            y + (0.022 - y).powf(1.414)
+        } else {
+            y
+        }
+    };
+    let (text, background) = (luminance(text), luminance(background));
+    let lc = if background > text {
+        let contrast = (background.powf(0.56) - text.powf(0.57)) * 1.14;
+        if contrast < 0.1 {
+            0.0
+        } else {
+            contrast - 0.027
+        }
+    } else {
+        let contrast = (background.powf(0.65) - text.powf(0.62)) * 1.14;
+        if contrast > -0.1 {
+            0.0
+        } else {
+            contrast + 0.027
+        }
+    };
+    lc * 100.0
+}
+
+/// One report item per text node: WCAG failures fail, unresolved backgrounds
+/// and APCA shortfalls warn
+pub fn contrast_report(
+    findings: &[ContrastFinding],
+    title: String,
+    level: WcagLevel,
+) -> ReportSummary {
+    let level_name = match level {
+        WcagLevel::Aa => "AA",
+        WcagLevel::Aaa => "AAA",
+    };
+    let mut items: Vec<ReportItem> = findings
+        .iter()
+        .map(|finding| {
+            let name = format!("{} / {} ({})", finding.page, finding.name, finding.node_id);
+            let (Some(ratio), Some(foreground), Some(background)) =
+                (finding.ratio, &finding.foreground, &finding.background)
+            else {
+                let reason = finding.unresolved.as_deref().unwrap_or("Unknown colors");
+                return ReportItem::warn(name, format!("{}; check manually", reason));
+            };
+            let required = finding.required_ratio(level);
+            let kind = if finding.large_text {
+                "large text"
+            } else {
+                "normal text"
+            };
+            let mut message = format!(
+                "{:.2}:1 {} {} {}:1 for {} ({} on {})",
+                ratio,
+                if ratio >= required { "meets" } else { "below" },
+                level_name,
+                required,
+                kind,
+                foreground,
+                background
+            );
+            let mut status = if ratio >= required {
+                ReportStatus::Ok
+            } else {
+                ReportStatus::Fail
+            };
+            if let Some(lc) = finding.apca {
+                let minimum = finding.required_apca();
+                message.push_str(&format!("; APCA Lc {:.1}", lc));
+                if lc.abs() < minimum {
+                    message.push_str(&format!(" below {}", minimum));
+                    if status == ReportStatus::Ok {
+                        status = ReportStatus::Warn;
+                    }
+                }
+            }
+            ReportItem::new(name, status, message)
+        })
+        .collect();
+
+    if items.is_empty() {
+        items.push(ReportItem::ok(
+            title.clone(),
+            "No visible text found".to_string(),
+        ));
+    }
+    ReportSummary { title, items }
+}
+
+async fn contrast(
+    file_key_or_url: &str,
+    level: WcagLevel,
+    apca: bool,
+    report: Option<&Path>,
+    report_format: ReportFormat,
+) -> Result<()> {
+    let parsed = FigmaUrl::parse(file_key_or_url)?;
+    let client = FigmaClient::new(get_token()?)?;
+    let file = client.get_file(&parsed.file_key).await?;
+
+    let findings = audit_contrast(&file.document, parsed.node_id.as_deref(), apca);
+    if let (Some(node_id), true) = (&parsed.node_id, findings.is_empty()) {
+        output::print_warning(&format!("No visible text found under node {}", node_id));
+    }
+    let summary = contrast_report(
+        &findings,
+        format!("fgm audit contrast {}", file.name),
+        level,
+    );
+
+    output::print_status(&summary.title.bold().to_string());
+    for item in &summary.items {
+        let marker = match item.status {
+            ReportStatus::Ok => continue,
+            ReportStatus::Warn => "warn".yellow(),
+            ReportStatus::Fail => "fail".red(),
+        };
+        output::print_status(&format!("  {:<6} {}: {}", marker, item.name, item.message));
+    }
+    let count = |status: ReportStatus| {
+        summary
+            .items
+            .iter()
+            .filter(|item| item.status == status)
+            .count()
+    };
+    output::print_status(&format!(
+        "\n  Passed: {} | Warnings: {} | Failed: {}",
+        count(ReportStatus::Ok),
+        count(ReportStatus::Warn),
+        count(ReportStatus::Fail)
+    ));
+
+    if let Some(report_path) = report {
+        write_report(report_path, report_format, &summary)?;
+        output::print_status(&format!("  Report: {}", report_path.display()));
+    }
+
+    if output::format() == output::OutputFormat::Json {
+        output::print_json(&findings)?;
+    }
+
+    if summary.exit_code() != 0 {
+        bail!("Contrast audit found failures");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solid(gray: f64) -> serde_json::Value {
+        serde_json::json!([{ "type": "SOLID", "color": { "r": gray, "g": gray, "b": gray, "a": 1.0 } }])
+    }
+
+    fn bounds(x: f64, y: f64, width: f64, height: f64) -> serde_json::Value {
+        serde_json::json!({ "x": x, "y": y, "width": width, "height": height })
+    }
+
+    fn document() -> Document {
+        let headline = serde_json::json!({
+            "id": "1:6",
+            "name": "Headline",
+            "type": "TEXT",
+            "characters": "Hello",
+            "absoluteBoundingBox": bounds(210.0, 110.0, 100.0, 40.0),
+            "style": { "fontSize": 32, "fontWeight": 700 },
+            "fills": solid(1.0)
+        });
+        let card_children = serde_json::json!([{
+            "id": "1:2",
+            "name": "Caption",
+            "type": "TEXT",
+            "characters": "Muted caption",
+            "absoluteBoundingBox": bounds(16.0, 16.0, 200.0, 20.0),
+            "style": { "fontSize": 14, "fontWeight": 400 },
+            "fills": solid(0.6)
+        }, {
+            "id": "1:3",
+            "name": "Button",
+            "type": "RECTANGLE",
+            "absoluteBoundingBox": bounds(16.0, 200.0, 120.0, 48.0),
+            "fills": solid(0.0)
+        }, {
+            "id": "1:4",
+            "name": "Label",
+            "type": "TEXT",
+            "characters": "Continue",
+            "absoluteBoundingBox": bounds(24.0, 212.0, 80.0, 24.0),
+            "style": { "fontSize": 16, "fontWeight": 700 },
+            "fills": solid(1.0)
+        }, {
+            "id": "1:5",
+            "name": "Hero",
+            "type": "FRAME",
+            "absoluteBoundingBox": bounds(200.0, 100.0, 200.0, 100.0),
+            "fills": [{ "type": "IMAGE", "imageRef": "abc" }],
+            "children": [headline]
+        }, {
+            "id": "1:7",
+            "name": "Hidden",
+            "type": "TEXT",
+            "visible": false,
+            "characters": "Hidden",
+            "fills": solid(1.0)
+        }]);
+        serde_json::from_value(serde_json::json!({
+            "id": "0:0",
+            "name": "Document",
+            "type": "DOCUMENT",
+            "children": [{
+                "id": "0:1",
+                "name": "Page 1",
+                "type": "CANVAS",
+                "backgroundColor": { "r": 0.9, "g": 0.9, "b": 0.9, "a": 1.0 },
+                "children": [{
+                    "id": "1:1",
+                    "name": "Card",
+                    "type": "FRAME",
+                    "absoluteBoundingBox": bounds(0.0, 0.0, 400.0, 300.0),
+                    "fills": solid(1.0),
+                    "children": card_children
+                }]
+            }]
+        }))
+        .expect("document")
+    }
+
+    #[test]
+    fn computes_wcag_and_apca_contrast() {
+        let (black, white) = ([0.0; 3], [1.0; 3]);
+        assert!((contrast_ratio(black, white) - 21.0).abs() < 1e-9);
+        assert!((contrast_ratio(white, black) - 21.0).abs() < 1e-9);
+        let gray = [119.0 / 255.0; 3];
+        assert_eq!(format!("{:.2}", contrast_ratio(gray, white)), "4.48");
+
+        assert_eq!(format!("{:.1}", apca_contrast(black, white)), "106.0");
+        assert_eq!(format!("{:.1}", apca_contrast(white, black)), "-107.9");
+        assert_eq!(apca_contrast(gray, gray), 0.0);
+    }
+
+    #[test]
+    fn resolves_backgrounds_from_ancestors_and_siblings() {
+        let findings = audit_contrast(&document(), None, true);
+        let ids: Vec<&str> = findings.iter().map(|f| f.node_id.as_str()).collect();
+        assert_eq!(ids, vec!["1:2", "1:4", "1:6"]);
+
+        let caption = &findings[0];
+        assert_eq!(caption.background.as_deref(), Some("#FFFFFF"));
+        assert_eq!(caption.foreground.as_deref(), Some("#999999"));
+        assert!(!caption.large_text);
+
+        let label = &findings[1];
+        assert_eq!(label.background.as_deref(), Some("#000000"));
+        assert!((label.ratio.unwrap() - 21.0).abs() < 1e-9);
+
+        let headline = &findings[2];
+        assert!(headline.large_text);
+        assert_eq!(
+            headline.unresolved.as_deref(),
+            Some("Background is an image fill")
+        );
+
+        let scoped = audit_contrast(&document(), Some("1:5"), false);
+        assert_eq!(scoped.len(), 1);
+        assert_eq!(scoped[0].node_id, "1:6");
+    }
+
+    #[test]
+    fn grouped_backgrounds_are_painted_beneath_later_siblings() {
+        let document: Document = serde_json::from_value(serde_json::json!({
+            "id": "0:0",
+            "name": "Document",
+            "type": "DOCUMENT",
+            "children": [{
+                "id": "0:1",
+                "name": "Page 1",
+                "type": "CANVAS",
+                "children": [{
+                    "id": "2:1",
+                    "name": "Background",
+                    "type": "GROUP",
+                    "absoluteBoundingBox": bounds(0.0, 0.0, 200.0, 100.0),
+                    "children": [{
+                        "id": "2:2",
+                        "name": "Fill",
+                        "type": "RECTANGLE",
+                        "absoluteBoundingBox": bounds(0.0, 0.0, 200.0, 100.0),
+                        "fills": solid(0.0)
+                    }]
+                }, {
+                    "id": "2:3",
+                    "name": "Title",
+                    "type": "TEXT",
+                    "characters": "Welcome",
+                    "absoluteBoundingBox": bounds(16.0, 16.0, 100.0, 24.0),
+                    "style": { "fontSize": 16, "fontWeight": 400 },
+                    "fills": solid(1.0)
+                }]
+            }]
+        }))
+        .expect("document");
+
+        let findings = audit_contrast(&document, None, false);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].background.as_deref(), Some("#000000"));
+        assert!((findings[0].ratio.unwrap() - 21.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn report_fails_below_the_wcag_level() {
+        let findings = audit_contrast(&document(), None, false);
+
+        let aa = contrast_report(&findings, "contrast".to_string(), WcagLevel::Aa);
+        let statuses: Vec<ReportStatus> = aa.items.iter().map(|item| item.status).collect();
+        assert_eq!(
+            statuses,
+            vec![ReportStatus::Fail, ReportStatus::Ok, ReportStatus::Warn]
+        );
+        assert!(aa.items[0]
+            .message
+            .starts_with("2.85:1 below AA 4.5:1 for normal text"));
+        assert_ne!(aa.exit_code(), 0);
+    }
+}
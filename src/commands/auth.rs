@@ -30,7 +30,7 @@ async fn login(store_in_keychain_only: bool) -> Result<()> {
     output::print_status("  4. Copy the generated token");
     output::print_status("");
 
-    if let Err(_) = open::that("https://www.figma.com/developers/api#access-tokens") {
+    if open::that("https://www.figma.com/developers/api#access-tokens").is_err() {
         output::print_warning("Could not open browser automatically.");
     }
 
@@ -1,17 +1,36 @@
-use crate::cli::CompareArgs;
+use crate::cli::{CompareArgs, DiffMethod};
+use crate::commands::mask::IgnoreMask;
+use crate::commands::perceptual::{self, SsimMap};
+use crate::commands::regions::{self, DiffRegion};
 use crate::output;
 use crate::reporting::{write_report, ReportItem, ReportSummary};
 use anyhow::{anyhow, Result};
 use colored::Colorize;
-use image::{GenericImageView, Rgba};
+use image::{GenericImageView, Pixel, Rgba};
+use rayon::prelude::*;
 use serde::Serialize;
+use std::borrow::Cow;
 use std::fs;
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
 
 pub async fn run(args: CompareArgs) -> Result<()> {
     if !(0.0..=100.0).contains(&args.threshold) {
         anyhow::bail!("Threshold must be between 0 and 100");
     }
+    let diff = DiffOptions {
+        ignore: IgnoreMask::load(&args.ignore, args.ignore_mask.as_deref())?,
+        ..DiffOptions::from_flags(
+            args.method,
+            args.tolerance,
+            args.aa_threshold,
+            args.max_delta_e,
+            args.min_ssim,
+        )?
+    };
     if args.batch {
         batch_compare(
             &args.image1,
@@ -20,8 +39,9 @@ pub async fn run(args: CompareArgs) -> Result<()> {
             args.report.as_deref(),
             args.report_format,
             args.threshold,
-            args.tolerance,
+            &diff,
             args.fast,
+            usize::from(args.jobs),
         )
         .await
     } else {
@@ -32,13 +52,14 @@ pub async fn run(args: CompareArgs) -> Result<()> {
             args.report.as_deref(),
             args.report_format,
             args.threshold,
-            args.tolerance,
+            &diff,
             args.fast,
         )
         .await
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn single_compare(
     image1_path: &Path,
     image2_path: &Path,
@@ -46,7 +67,7 @@ async fn single_compare(
     report_path: Option<&Path>,
     report_format: crate::reporting::ReportFormat,
     threshold: f32,
-    tolerance: u8,
+    diff: &DiffOptions,
     fast: bool,
 ) -> Result<()> {
     output::print_status(&"Comparing images...".bold().to_string());
@@ -71,17 +92,17 @@ async fn single_compare(
     output::print_status(&format!("  Dimensions: {}x{} vs {}x{}", w1, h1, w2, h2));
 
     // Calculate pixel diff
-    let result = calculate_diff_internal(
+    let result = compare_images(
         &img1,
         &img2,
-        tolerance,
+        diff,
         Some(threshold),
         fast && output_path.is_none(),
     )?;
 
     let diff_percent = result.diff_percent;
     let passed = diff_percent <= threshold;
-    let report_summary = ReportSummary {
+    let mut report_summary = ReportSummary {
         title: "fgm compare".to_string(),
         items: vec![ReportItem::new(
             image2_path.display().to_string(),
@@ -99,6 +120,12 @@ async fn single_compare(
             ),
         )],
     };
+    if !passed {
+        report_summary.items.extend(regions::report_items(
+            &image2_path.display().to_string(),
+            &result.regions,
+        ));
+    }
 
     if passed {
         output::print_status(&format!(
@@ -119,10 +146,12 @@ async fn single_compare(
             suffix
         ));
     }
+    print_method_details(&result);
+    print_regions(&result.regions);
 
     // Generate diff image if output path specified
     if let Some(output) = output_path {
-        let diff_img = generate_diff_image(&img1, &img2, tolerance);
+        let diff_img = render_diff_image(&img1, &img2, diff);
         diff_img.save(output)?;
         output::print_status(&format!(
             "  Diff image: {}",
@@ -140,6 +169,12 @@ async fn single_compare(
             dimensions_match: result.dimensions_match,
             early_exit: result.early_exit,
             diff_image: output_path.map(|p| p.display().to_string()),
+            method: result.method,
+            antialiased_pixels: result.antialiased_pixels,
+            ssim: result.ssim,
+            ssim_map: result.ssim_map,
+            ignored_pixels: result.ignored_pixels,
+            regions: result.regions,
         };
         output::print_json(&result)?;
     }
@@ -159,6 +194,7 @@ async fn single_compare(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn batch_compare(
     dir1: &Path,
     dir2: &Path,
@@ -166,8 +202,9 @@ async fn batch_compare(
     report_path: Option<&Path>,
     report_format: crate::reporting::ReportFormat,
     threshold: f32,
-    tolerance: u8,
+    diff: &DiffOptions,
     fast: bool,
+    jobs: usize,
 ) -> Result<()> {
     output::print_status(&"Batch comparing directories...".bold().to_string());
     output::print_status(&format!("  Design dir:     {}", dir1.display()));
@@ -178,57 +215,91 @@ async fn batch_compare(
         fs::create_dir_all(output)?;
     }
 
-    let mut results = Vec::new();
-    let mut passed = 0;
-    let mut failed = 0;
-
-    // Find matching images
+    // Find matching images, in name order so the output is stable
+    let mut designs = Vec::new();
     for entry in fs::read_dir(dir1)? {
-        let entry = entry?;
-        let path1 = entry.path();
-
-        if !is_image(&path1) {
-            continue;
+        let path1 = entry?.path();
+        if is_image(&path1) {
+            designs.push(path1);
         }
+    }
+    designs.sort();
+
+    let jobs = jobs.max(1);
+    output::print_status(&format!(
+        "  Comparing {} image(s) with up to {} workers",
+        designs.len(),
+        jobs
+    ));
+
+    let semaphore = Arc::new(Semaphore::new(jobs));
+    let options = Arc::new(diff.clone());
+    let early_exit = fast && output_dir.is_none();
+    let mut joins = JoinSet::new();
+    for (order, path1) in designs.into_iter().enumerate() {
+        let filename = path1.file_name().unwrap().to_string_lossy().to_string();
+        let path2 = dir2.join(&filename);
+        let diff_path = output_dir.map(|output| output.join(format!("diff-{}", filename)));
+        let sem = semaphore.clone();
+        let options = options.clone();
+        joins.spawn(async move {
+            let _permit = sem
+                .acquire_owned()
+                .await
+                .map_err(|_| anyhow!("compare semaphore closed"))?;
+            let outcome = tokio::task::spawn_blocking(move || {
+                compare_pair(
+                    &path1,
+                    &path2,
+                    diff_path.as_deref(),
+                    &options,
+                    threshold,
+                    early_exit,
+                )
+            })
+            .await
+            .map_err(|err| anyhow!("compare task failed: {}", err))??;
+            Ok::<_, anyhow::Error>((order, filename, outcome))
+        });
+    }
 
-        let filename = path1.file_name().unwrap();
-        let path2 = dir2.join(filename);
+    let mut outcomes = Vec::new();
+    while let Some(joined) = joins.join_next().await {
+        outcomes.push(joined.map_err(|err| anyhow!("compare task failed: {}", err))??);
+    }
+    outcomes.sort_by_key(|(order, _, _)| *order);
 
-        if !path2.exists() {
+    let mut results = Vec::new();
+    let mut passed = 0;
+    let mut failed = 0;
+
+    for (_, filename, outcome) in outcomes {
+        let Some(diff_result) = outcome else {
             output::print_status(&format!(
                 "  {} - {}",
-                filename.to_string_lossy().yellow(),
+                filename.yellow(),
                 "missing in screenshot dir"
             ));
             failed += 1;
             results.push(CompareResult {
-                file: filename.to_string_lossy().to_string(),
+                file: filename,
                 diff_percent: 100.0,
                 passed: false,
                 dimensions_match: false,
                 early_exit: false,
+                ssim: None,
+                regions: Vec::new(),
                 message: Some("missing in screenshot dir".to_string()),
             });
             continue;
-        }
-
-        let img1 = image::open(&path1)?;
-        let img2 = image::open(&path2)?;
-
-        let diff_result = calculate_diff_internal(
-            &img1,
-            &img2,
-            tolerance,
-            Some(threshold),
-            fast && output_dir.is_none(),
-        )?;
+        };
         let passes = diff_result.diff_percent <= threshold;
 
         if passes {
             passed += 1;
             output::print_status(&format!(
                 "  {} - {:.2}% {}",
-                filename.to_string_lossy(),
+                filename,
                 diff_result.diff_percent,
                 "OK".green()
             ));
@@ -241,26 +312,21 @@ async fn batch_compare(
             };
             output::print_status(&format!(
                 "  {} - {:.2}% {}{}",
-                filename.to_string_lossy(),
+                filename,
                 diff_result.diff_percent,
                 "FAIL".red(),
                 suffix
             ));
         }
 
-        // Generate diff image
-        if let Some(output) = output_dir {
-            let diff_img = generate_diff_image(&img1, &img2, tolerance);
-            let diff_filename = format!("diff-{}", filename.to_string_lossy());
-            diff_img.save(output.join(&diff_filename))?;
-        }
-
         results.push(CompareResult {
-            file: filename.to_string_lossy().to_string(),
+            file: filename,
             diff_percent: diff_result.diff_percent,
             passed: passes,
             dimensions_match: diff_result.dimensions_match,
             early_exit: diff_result.early_exit,
+            ssim: diff_result.ssim,
+            regions: diff_result.regions,
             message: None,
         });
     }
@@ -275,6 +341,7 @@ async fn batch_compare(
         passed,
         failed,
         threshold,
+        method: diff.method,
         results,
     };
 
@@ -284,8 +351,8 @@ async fn batch_compare(
             items: report_data
                 .results
                 .iter()
-                .map(|result| {
-                    ReportItem::new(
+                .flat_map(|result| {
+                    let item = ReportItem::new(
                         &result.file,
                         if result.passed {
                             crate::reporting::ReportStatus::Ok
@@ -298,7 +365,13 @@ async fn batch_compare(
                                 result.diff_percent, result.dimensions_match, result.early_exit
                             )
                         }),
-                    )
+                    );
+                    let regions = if result.passed {
+                        Vec::new()
+                    } else {
+                        regions::report_items(&result.file, &result.regions)
+                    };
+                    std::iter::once(item).chain(regions)
                 })
                 .collect(),
         };
@@ -320,11 +393,121 @@ async fn batch_compare(
     Ok(())
 }
 
+/// Compare one pair of a batch and save its diff image; `None` when the screenshot is missing
+fn compare_pair(
+    design: &Path,
+    screenshot: &Path,
+    diff_path: Option<&Path>,
+    options: &DiffOptions,
+    threshold: f32,
+    fast: bool,
+) -> Result<Option<DiffResult>> {
+    if !screenshot.exists() {
+        return Ok(None);
+    }
+    let img1 = image::open(design)?;
+    let img2 = image::open(screenshot)?;
+    let result = compare_images(&img1, &img2, options, Some(threshold), fast)?;
+    if let Some(diff_path) = diff_path {
+        render_diff_image(&img1, &img2, options).save(diff_path)?;
+    }
+    Ok(Some(result))
+}
+
+/// How pixels are judged different, from the `--method` family of flags
+#[derive(Debug, Clone)]
+pub struct DiffOptions {
+    pub method: DiffMethod,
+    /// Per-channel tolerance for `pixel` (0-255)
+    pub tolerance: u8,
+    /// YIQ color threshold for `antialias` (0-1)
+    pub aa_threshold: f32,
+    /// Largest CIEDE2000 distance still treated as equal for `delta-e`
+    pub max_delta_e: f32,
+    /// Smallest block score still treated as equal for `ssim` (0-1)
+    pub min_ssim: f32,
+    /// Regions left out of the comparison
+    pub ignore: IgnoreMask,
+}
+
+impl Default for DiffOptions {
+    fn default() -> Self {
+        Self {
+            method: DiffMethod::Pixel,
+            tolerance: 10,
+            aa_threshold: 0.1,
+            max_delta_e: 2.3,
+            min_ssim: 0.95,
+            ignore: IgnoreMask::default(),
+        }
+    }
+}
+
+impl DiffOptions {
+    /// Raw per-channel comparison
+    pub fn pixel(tolerance: u8) -> Self {
+        Self {
+            tolerance,
+            ..Self::default()
+        }
+    }
+
+    /// Options for the `compare` and `compare-url` flags
+    pub fn from_flags(
+        method: DiffMethod,
+        tolerance: u8,
+        aa_threshold: f32,
+        max_delta_e: f32,
+        min_ssim: f32,
+    ) -> Result<Self> {
+        if !(0.0..=1.0).contains(&aa_threshold) {
+            anyhow::bail!("--aa-threshold must be between 0 and 1");
+        }
+        if max_delta_e < 0.0 {
+            anyhow::bail!("--max-delta-e must not be negative");
+        }
+        if !(0.0..=1.0).contains(&min_ssim) {
+            anyhow::bail!("--min-ssim must be between 0 and 1");
+        }
+        Ok(Self {
+            method,
+            tolerance,
+            aa_threshold,
+            max_delta_e,
+            min_ssim,
+            ignore: IgnoreMask::default(),
+        })
+    }
+}
+
+/// Result of a pixel comparison
 #[derive(Debug, Serialize)]
 pub struct DiffResult {
     pub diff_percent: f32,
     pub dimensions_match: bool,
     pub early_exit: bool,
+    pub method: DiffMethod,
+    /// Differing pixels ignored as anti-aliasing (`antialias` only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub antialiased_pixels: Option<u64>,
+    /// Mean block score (`ssim` only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ssim: Option<f32>,
+    /// Per-block scores (`ssim` only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ssim_map: Option<SsimMap>,
+    /// Pixels left out by the ignore mask
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ignored_pixels: Option<u64>,
+    /// Clusters of differing pixels, largest first; empty after an early exit
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub regions: Vec<DiffRegion>,
+}
+
+enum PixelClass {
+    Same,
+    Different,
+    AntiAliased,
 }
 
 /// Calculate the percentage of pixels that differ between two images
@@ -345,47 +528,97 @@ pub fn generate_diff_image(
     img2: &image::DynamicImage,
     tolerance: u8,
 ) -> image::RgbaImage {
-    let (w1, h1) = img1.dimensions();
-    let (w2, h2) = img2.dimensions();
+    render_diff_image(img1, img2, &DiffOptions::pixel(tolerance))
+}
+
+/// Generate a visual diff image for any comparison method
+///
+/// Differences are red and anti-aliased pixels yellow; with `ssim`, blocks
+/// below `min_ssim` are tinted red by how far their score drops.
+pub fn render_diff_image(
+    img1: &image::DynamicImage,
+    img2: &image::DynamicImage,
+    options: &DiffOptions,
+) -> image::RgbaImage {
+    render_rgba_diff(&rgba8(img1), &rgba8(img2), options)
+}
+
+/// [`render_diff_image`] on RGBA8 buffers, rendered row-parallel
+pub fn render_rgba_diff(
+    image1: &image::RgbaImage,
+    image2: &image::RgbaImage,
+    options: &DiffOptions,
+) -> image::RgbaImage {
+    let (w1, h1) = image1.dimensions();
+    let (w2, h2) = image2.dimensions();
+    let mut image2 = Cow::Borrowed(image2);
+    apply_ignore_mask(options, image1, &mut image2, w1.min(w2), h1.min(h2));
+    let image2 = image2.as_ref();
 
     let width = w1.max(w2);
     let height = h1.max(h2);
+    let ssim_map =
+        (options.method == DiffMethod::Ssim).then(|| perceptual::ssim_map(image1, image2));
 
     let mut diff_img = image::RgbaImage::new(width, height);
+    if width == 0 {
+        return diff_img;
+    }
 
-    for y in 0..height {
-        for x in 0..width {
-            let in_bounds1 = x < w1 && y < h1;
-            let in_bounds2 = x < w2 && y < h2;
-
-            let pixel = if in_bounds1 && in_bounds2 {
-                let p1 = img1.get_pixel(x, y);
-                let p2 = img2.get_pixel(x, y);
-
-                if pixels_similar(&p1, &p2, tolerance) {
+    diff_img
+        .par_chunks_mut(width as usize * 4)
+        .enumerate()
+        .for_each(|(y, row)| {
+            let y = y as u32;
+            for (x, out) in row.chunks_exact_mut(4).enumerate() {
+                let x = x as u32;
+                let in_bounds1 = x < w1 && y < h1;
+                let in_bounds2 = x < w2 && y < h2;
+
+                let pixel = if options.ignore.is_ignored(x, y) {
+                    // Ignored - left transparent
+                    Rgba([0, 0, 0, 0])
+                } else if in_bounds1 && in_bounds2 {
+                    let p1 = pixel_at(image1, x, y);
                     // Same - show dimmed original
-                    Rgba([p1[0] / 2, p1[1] / 2, p1[2] / 2, 255])
+                    let dimmed = Rgba([p1[0] / 2, p1[1] / 2, p1[2] / 2, 255]);
+
+                    if let Some(map) = &ssim_map {
+                        let score = map.score_at(x, y);
+                        if score < options.min_ssim {
+                            let strength = (1.0 - score.max(0.0)).min(1.0);
+                            let tint = |c: u8| (c as f32 * (1.0 - strength)) as u8;
+                            Rgba([255, tint(dimmed[1]), tint(dimmed[2]), 255])
+                        } else {
+                            dimmed
+                        }
+                    } else {
+                        let p2 = pixel_at(image2, x, y);
+                        match classify(options, image1, image2, x, y, p1, p2) {
+                            PixelClass::Same => dimmed,
+                            // Different - highlight in red
+                            PixelClass::Different => Rgba([255, 0, 0, 200]),
+                            PixelClass::AntiAliased => Rgba([255, 255, 0, 200]),
+                        }
+                    }
+                } else if in_bounds1 {
+                    // Only in image 1 - show in blue
+                    Rgba([0, 0, 255, 200])
+                } else if in_bounds2 {
+                    // Only in image 2 - show in green
+                    Rgba([0, 255, 0, 200])
                 } else {
-                    // Different - highlight in red
-                    Rgba([255, 0, 0, 200])
-                }
-            } else if in_bounds1 {
-                // Only in image 1 - show in blue
-                Rgba([0, 0, 255, 200])
-            } else if in_bounds2 {
-                // Only in image 2 - show in green
-                Rgba([0, 255, 0, 200])
-            } else {
-                Rgba([0, 0, 0, 0])
-            };
+                    Rgba([0, 0, 0, 0])
+                };
 
-            diff_img.put_pixel(x, y, pixel);
-        }
-    }
+                out.copy_from_slice(&pixel.0);
+            }
+        });
 
     diff_img
 }
 
+/// Calculate the diff with dimension info, optionally stopping once `threshold` is exceeded
 pub fn calculate_diff_internal(
     img1: &image::DynamicImage,
     img2: &image::DynamicImage,
@@ -393,8 +626,36 @@ pub fn calculate_diff_internal(
     threshold: Option<f32>,
     fast: bool,
 ) -> Result<DiffResult> {
-    let (w1, h1) = img1.dimensions();
-    let (w2, h2) = img2.dimensions();
+    compare_images(img1, img2, &DiffOptions::pixel(tolerance), threshold, fast)
+}
+
+/// Compare two images with any method, optionally stopping once `threshold` is exceeded
+///
+/// Early exit applies to the per-pixel methods; `ssim` always scores every block.
+pub fn compare_images(
+    img1: &image::DynamicImage,
+    img2: &image::DynamicImage,
+    options: &DiffOptions,
+    threshold: Option<f32>,
+    fast: bool,
+) -> Result<DiffResult> {
+    compare_rgba(&rgba8(img1), &rgba8(img2), options, threshold, fast)
+}
+
+/// [`compare_images`] on RGBA8 buffers
+///
+/// Rows are diffed in parallel straight from the raw buffers; identical rows
+/// are skipped with one slice comparison. With an early exit, rows stop being
+/// scheduled once the completed ones exceed the threshold.
+pub fn compare_rgba(
+    image1: &image::RgbaImage,
+    image2: &image::RgbaImage,
+    options: &DiffOptions,
+    threshold: Option<f32>,
+    fast: bool,
+) -> Result<DiffResult> {
+    let (w1, h1) = image1.dimensions();
+    let (w2, h2) = image2.dimensions();
 
     let dimensions_match = w1 == w2 && h1 == h2;
 
@@ -407,50 +668,259 @@ pub fn calculate_diff_internal(
         return Err(anyhow!("Images have zero dimensions"));
     }
 
+    let mut image2 = Cow::Borrowed(image2);
+    let ignored = apply_ignore_mask(options, image1, &mut image2, width, height);
+    let image2 = image2.as_ref();
+    let ignored_pixels = ignored
+        .as_ref()
+        .map(|flags| flags.iter().filter(|&&flag| flag).count() as u64);
+    let compared_pixels = total_pixels - ignored_pixels.unwrap_or(0) as f64;
+    if compared_pixels == 0.0 {
+        anyhow::bail!("The ignore mask covers the whole image");
+    }
+
+    let mut result = DiffResult {
+        diff_percent: 0.0,
+        dimensions_match,
+        early_exit: false,
+        method: options.method,
+        antialiased_pixels: (options.method == DiffMethod::Antialias).then_some(0),
+        ssim: None,
+        ssim_map: None,
+        ignored_pixels,
+        regions: Vec::new(),
+    };
+
+    if options.method == DiffMethod::Ssim {
+        let map = perceptual::ssim_map(image1, image2);
+        let mut diff_pixels = 0u64;
+        let mut score_sum = 0.0f64;
+        for row in 0..map.rows {
+            for column in 0..map.columns {
+                let (x0, y0) = (column * map.block_size, row * map.block_size);
+                let (x1, y1) = (
+                    (x0 + map.block_size).min(width),
+                    (y0 + map.block_size).min(height),
+                );
+                let pixels = match &ignored {
+                    Some(flags) => (y0..y1)
+                        .flat_map(|y| (x0..x1).map(move |x| (y * width + x) as usize))
+                        .filter(|&index| !flags[index])
+                        .count() as u64,
+                    None => ((x1 - x0) * (y1 - y0)) as u64,
+                };
+                let score = map.scores[(row * map.columns + column) as usize];
+                score_sum += score as f64 * pixels as f64;
+                if score < options.min_ssim {
+                    diff_pixels += pixels;
+                }
+            }
+        }
+        result.diff_percent = (diff_pixels as f64 / compared_pixels * 100.0) as f32;
+        result.ssim = Some((score_sum / compared_pixels) as f32);
+        let differs: Vec<bool> = (0..width * height)
+            .into_par_iter()
+            .map(|index| {
+                let ignored = ignored.as_ref().is_some_and(|flags| flags[index as usize]);
+                !ignored && map.score_at(index % width, index / width) < options.min_ssim
+            })
+            .collect();
+        result.regions = regions::cluster_regions(&differs, width, height);
+        result.ssim_map = Some(map);
+        return Ok(result);
+    }
+
     let early_exit_limit = if fast {
-        threshold.map(|t| ((t as f64 / 100.0) * total_pixels).ceil() as u64)
+        threshold.map(|t| ((t as f64 / 100.0) * compared_pixels).ceil() as u64)
     } else {
         None
     };
 
-    let mut diff_pixels = 0u64;
-
-    for y in 0..height {
-        for x in 0..width {
-            let p1 = img1.get_pixel(x, y);
-            let p2 = img2.get_pixel(x, y);
-
-            if !pixels_similar(&p1, &p2, tolerance) {
-                diff_pixels += 1;
-                if let Some(limit) = early_exit_limit {
-                    if diff_pixels > limit {
-                        let diff_percent = (diff_pixels as f64 / total_pixels * 100.0) as f32;
-                        return Ok(DiffResult {
-                            diff_percent,
-                            dimensions_match,
-                            early_exit: true,
-                        });
-                    }
+    let stop = AtomicBool::new(false);
+    let counted = AtomicU64::new(0);
+    let rows: Vec<RowDiff> = (0..height)
+        .into_par_iter()
+        .map(|y| {
+            if stop.load(Ordering::Relaxed) {
+                return RowDiff::default();
+            }
+            let row = diff_row(options, image1, image2, y, width);
+            if let Some(limit) = early_exit_limit {
+                let differing = row.differs.len() as u64;
+                if counted.fetch_add(differing, Ordering::Relaxed) + differing > limit {
+                    stop.store(true, Ordering::Relaxed);
                 }
             }
+            row
+        })
+        .collect();
+
+    let diff_pixels: u64 = rows.iter().map(|row| row.differs.len() as u64).sum();
+    result.early_exit = stop.into_inner();
+    result.diff_percent = (diff_pixels as f64 / compared_pixels * 100.0) as f32;
+    if let Some(count) = result.antialiased_pixels.as_mut() {
+        *count = rows.iter().map(|row| row.antialiased).sum();
+    }
+    if !result.early_exit && diff_pixels > 0 {
+        let mut differs = vec![false; (width * height) as usize];
+        for (y, row) in rows.iter().enumerate() {
+            for &x in &row.differs {
+                differs[y * width as usize + x as usize] = true;
+            }
         }
+        result.regions = regions::cluster_regions(&differs, width, height);
     }
+    Ok(result)
+}
 
-    let diff_percent = (diff_pixels as f64 / total_pixels * 100.0) as f32;
+/// Differing columns and anti-aliased pixel count of one row
+#[derive(Default)]
+struct RowDiff {
+    differs: Vec<u32>,
+    antialiased: u64,
+}
 
-    Ok(DiffResult {
-        diff_percent,
-        dimensions_match,
-        early_exit: false,
-    })
+fn diff_row(
+    options: &DiffOptions,
+    image1: &image::RgbaImage,
+    image2: &image::RgbaImage,
+    y: u32,
+    width: u32,
+) -> RowDiff {
+    let row1 = row_at(image1, y, width);
+    let row2 = row_at(image2, y, width);
+    let mut row = RowDiff::default();
+    if row1 == row2 {
+        return row;
+    }
+    for (x, (p1, p2)) in row1.chunks_exact(4).zip(row2.chunks_exact(4)).enumerate() {
+        if p1 == p2 {
+            continue;
+        }
+        let (p1, p2) = (Rgba::from_slice(p1), Rgba::from_slice(p2));
+        match classify(options, image1, image2, x as u32, y, p1, p2) {
+            PixelClass::Same => {}
+            PixelClass::AntiAliased => row.antialiased += 1,
+            PixelClass::Different => row.differs.push(x as u32),
+        }
+    }
+    row
 }
 
+/// Borrow RGBA8 pixels, converting only images stored in another layout
+fn rgba8(image: &image::DynamicImage) -> Cow<'_, image::RgbaImage> {
+    match image.as_rgba8() {
+        Some(rgba) => Cow::Borrowed(rgba),
+        None => Cow::Owned(image.to_rgba8()),
+    }
+}
+
+/// The first `width` pixels of row `y` as raw RGBA bytes
+fn row_at(image: &image::RgbaImage, y: u32, width: u32) -> &[u8] {
+    let start = y as usize * image.width() as usize * 4;
+    &image.as_raw()[start..start + width as usize * 4]
+}
+
+fn pixel_at(image: &image::RgbaImage, x: u32, y: u32) -> &Rgba<u8> {
+    let start = (y as usize * image.width() as usize + x as usize) * 4;
+    Rgba::from_slice(&image.as_raw()[start..start + 4])
+}
+
+/// Copy the first image into the second wherever the mask ignores pixels, so they always match
+///
+/// Returns the ignored flags of the `width` x `height` common area.
+fn apply_ignore_mask(
+    options: &DiffOptions,
+    image1: &image::RgbaImage,
+    image2: &mut Cow<'_, image::RgbaImage>,
+    width: u32,
+    height: u32,
+) -> Option<Vec<bool>> {
+    let ignored = options.ignore.resolve(width, height)?;
+    let image2 = image2.to_mut();
+    for (index, _) in ignored.iter().enumerate().filter(|(_, &flag)| flag) {
+        let (x, y) = (index as u32 % width, index as u32 / width);
+        image2.put_pixel(x, y, *pixel_at(image1, x, y));
+    }
+    Some(ignored)
+}
+
+/// Judge one differing pixel of the common area with a per-pixel method
+fn classify(
+    options: &DiffOptions,
+    image1: &image::RgbaImage,
+    image2: &image::RgbaImage,
+    x: u32,
+    y: u32,
+    p1: &Rgba<u8>,
+    p2: &Rgba<u8>,
+) -> PixelClass {
+    if p1 == p2 {
+        return PixelClass::Same;
+    }
+
+    let same = match options.method {
+        DiffMethod::Pixel | DiffMethod::Ssim => pixels_similar(p1, p2, options.tolerance),
+        DiffMethod::Antialias => {
+            let delta =
+                perceptual::yiq_delta(perceptual::blend_white(p1), perceptual::blend_white(p2));
+            if delta.abs() <= perceptual::max_yiq_delta(options.aa_threshold) {
+                true
+            } else if perceptual::is_antialiased(image1, image2, x, y)
+                || perceptual::is_antialiased(image2, image1, x, y)
+            {
+                return PixelClass::AntiAliased;
+            } else {
+                false
+            }
+        }
+        DiffMethod::DeltaE => {
+            let lab1 = perceptual::srgb_to_lab(perceptual::blend_white(p1));
+            let lab2 = perceptual::srgb_to_lab(perceptual::blend_white(p2));
+            perceptual::ciede2000(lab1, lab2) <= options.max_delta_e as f64
+        }
+    };
+    if same {
+        PixelClass::Same
+    } else {
+        PixelClass::Different
+    }
+}
+
+/// Per-channel comparison including alpha; fully transparent pixels always match
 fn pixels_similar(p1: &Rgba<u8>, p2: &Rgba<u8>, tolerance: u8) -> bool {
-    let diff_r = (p1[0] as i16 - p2[0] as i16).unsigned_abs() as u8;
-    let diff_g = (p1[1] as i16 - p2[1] as i16).unsigned_abs() as u8;
-    let diff_b = (p1[2] as i16 - p2[2] as i16).unsigned_abs() as u8;
+    if p1[3] == 0 && p2[3] == 0 {
+        return true;
+    }
+    (0..4).all(|c| p1[c].abs_diff(p2[c]) <= tolerance)
+}
 
-    diff_r <= tolerance && diff_g <= tolerance && diff_b <= tolerance
+/// Print the extra scores of the perceptual methods and the ignore mask
+pub(crate) fn print_method_details(result: &DiffResult) {
+    if let Some(count) = result.ignored_pixels {
+        output::print_status(&format!("  Masked pixels ignored: {}", count));
+    }
+    if let Some(count) = result.antialiased_pixels {
+        output::print_status(&format!("  Anti-aliased pixels ignored: {}", count));
+    }
+    if let Some(ssim) = result.ssim {
+        output::print_status(&format!("  Mean SSIM: {:.4}", ssim));
+    }
+}
+
+/// Print the largest diff regions
+pub(crate) fn print_regions(regions: &[DiffRegion]) {
+    const SHOWN: usize = 5;
+    if regions.is_empty() {
+        return;
+    }
+    output::print_status(&format!("  Diff regions: {}", regions.len()));
+    for region in regions.iter().take(SHOWN) {
+        output::print_status(&format!("    {}", region.describe()));
+    }
+    if regions.len() > SHOWN {
+        output::print_status(&format!("    ... and {} more", regions.len() - SHOWN));
+    }
 }
 
 fn is_image(path: &Path) -> bool {
@@ -469,6 +939,7 @@ struct BatchReport {
     passed: usize,
     failed: usize,
     threshold: f32,
+    method: DiffMethod,
     results: Vec<CompareResult>,
 }
 
@@ -479,6 +950,10 @@ struct CompareResult {
     passed: bool,
     dimensions_match: bool,
     early_exit: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ssim: Option<f32>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    regions: Vec<DiffRegion>,
     message: Option<String>,
 }
 
@@ -492,6 +967,17 @@ struct SingleCompareOutput {
     dimensions_match: bool,
     early_exit: bool,
     diff_image: Option<String>,
+    method: DiffMethod,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    antialiased_pixels: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ssim: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ssim_map: Option<SsimMap>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ignored_pixels: Option<u64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    regions: Vec<DiffRegion>,
 }
 
 #[cfg(test)]
@@ -560,6 +1046,192 @@ mod tests {
         assert!(result.diff_percent > 10.0);
     }
 
+    #[test]
+    fn pixel_method_compares_alpha() {
+        let img1 = image_from_pixels(&[[255, 0, 0, 0], [0, 0, 0, 255]], 2, 1);
+        let img2 = image_from_pixels(&[[0, 0, 255, 0], [0, 0, 0, 128]], 2, 1);
+        let result = calculate_diff_internal(&img1, &img2, 10, None, false).unwrap();
+        assert!((result.diff_percent - 50.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn antialias_method_ignores_shifted_edges() {
+        let edge = |gray: u8| {
+            let mut img = RgbaImage::new(5, 5);
+            for (x, _, pixel) in img.enumerate_pixels_mut() {
+                let value = match x {
+                    0 | 1 => 0,
+                    2 => gray,
+                    _ => 255,
+                };
+                *pixel = Rgba([value, value, value, 255]);
+            }
+            DynamicImage::ImageRgba8(img)
+        };
+        let (img1, img2) = (edge(128), edge(60));
+
+        let pixel = compare_images(&img1, &img2, &DiffOptions::pixel(10), None, false).unwrap();
+        assert!((pixel.diff_percent - 20.0).abs() < 0.01);
+        assert_eq!(pixel.antialiased_pixels, None);
+
+        let options = DiffOptions {
+            method: DiffMethod::Antialias,
+            ..DiffOptions::default()
+        };
+        let result = compare_images(&img1, &img2, &options, None, false).unwrap();
+        assert_eq!(result.diff_percent, 0.0);
+        assert_eq!(result.antialiased_pixels, Some(5));
+        assert_eq!(
+            *render_diff_image(&img1, &img2, &options).get_pixel(2, 2),
+            Rgba([255, 255, 0, 200])
+        );
+    }
+
+    #[test]
+    fn delta_e_method_tolerates_imperceptible_shifts() {
+        let img1 = image_from_pixels(&[[200, 100, 100, 255], [0, 0, 0, 255]], 2, 1);
+        let img2 = image_from_pixels(&[[201, 101, 100, 255], [0, 0, 255, 255]], 2, 1);
+        assert_eq!(calculate_diff(&img1, &img2, 0), 100.0);
+
+        let options = DiffOptions::from_flags(DiffMethod::DeltaE, 0, 0.1, 2.3, 0.95).unwrap();
+        let result = compare_images(&img1, &img2, &options, None, false).unwrap();
+        assert!((result.diff_percent - 50.0).abs() < 0.01);
+        assert!(DiffOptions::from_flags(DiffMethod::DeltaE, 0, 0.1, -1.0, 0.95).is_err());
+    }
+
+    #[test]
+    fn ssim_method_scores_blocks() {
+        let mut pattern = RgbaImage::new(16, 8);
+        for (x, y, pixel) in pattern.enumerate_pixels_mut() {
+            let value = if (x + y) % 2 == 0 { 40 } else { 220 };
+            *pixel = Rgba([value, value, value, 255]);
+        }
+        let mut changed = pattern.clone();
+        for y in 0..8 {
+            for x in 8..16 {
+                let value = 255 - changed.get_pixel(x, y)[0];
+                changed.put_pixel(x, y, Rgba([value, value, value, 255]));
+            }
+        }
+        let (img1, img2) = (
+            DynamicImage::ImageRgba8(pattern),
+            DynamicImage::ImageRgba8(changed),
+        );
+        let options = DiffOptions {
+            method: DiffMethod::Ssim,
+            ..DiffOptions::default()
+        };
+
+        let same = compare_images(&img1, &img1, &options, None, false).unwrap();
+        assert_eq!(same.diff_percent, 0.0);
+        assert_eq!(same.ssim, Some(1.0));
+
+        let result = compare_images(&img1, &img2, &options, None, false).unwrap();
+        assert!((result.diff_percent - 50.0).abs() < 0.01);
+        let map = result.ssim_map.expect("ssim map");
+        assert_eq!((map.columns, map.rows), (2, 1));
+        assert_eq!(map.scores[0], 1.0);
+        assert!(map.scores[1] < 0.0);
+    }
+
+    #[test]
+    fn ignored_pixels_are_left_out() {
+        let img1 = image_from_pixels(&[[0, 0, 0, 255]; 4], 2, 2);
+        let img2 = image_from_pixels(
+            &[
+                [255, 0, 0, 255],
+                [255, 0, 0, 255],
+                [0, 0, 0, 255],
+                [255, 0, 0, 255],
+            ],
+            2,
+            2,
+        );
+        let options = DiffOptions {
+            ignore: IgnoreMask {
+                rects: vec!["0,0,2,1".parse().unwrap()],
+                ..IgnoreMask::default()
+            },
+            ..DiffOptions::pixel(0)
+        };
+
+        let result = compare_images(&img1, &img2, &options, None, false).unwrap();
+        assert!((result.diff_percent - 50.0).abs() < 0.01);
+        assert_eq!(result.ignored_pixels, Some(2));
+        let regions: Vec<(u32, u32, u32, u32)> = result
+            .regions
+            .iter()
+            .map(|r| (r.x, r.y, r.width, r.height))
+            .collect();
+        assert_eq!(regions, vec![(1, 1, 1, 1)]);
+        let diff = render_diff_image(&img1, &img2, &options);
+        assert_eq!(*diff.get_pixel(0, 0), Rgba([0, 0, 0, 0]));
+        assert_eq!(*diff.get_pixel(1, 1), Rgba([255, 0, 0, 200]));
+
+        let everything = DiffOptions {
+            ignore: IgnoreMask {
+                rects: vec!["0,0,2,2".parse().unwrap()],
+                ..IgnoreMask::default()
+            },
+            ..DiffOptions::pixel(0)
+        };
+        assert!(compare_images(&img1, &img2, &everything, None, false).is_err());
+    }
+
+    #[test]
+    fn rgba_and_converted_images_agree() {
+        let pixels1 = [[0, 0, 0, 255], [10, 200, 30, 255], [0, 0, 0, 255]];
+        let pixels2 = [[0, 0, 0, 255], [10, 20, 30, 255], [1, 0, 0, 255]];
+        let img1 = image_from_pixels(&pixels1, 3, 1);
+        let img2 = image_from_pixels(&pixels2, 3, 1);
+        let rgb = DynamicImage::ImageRgb8(img2.to_rgb8());
+
+        let options = DiffOptions::pixel(0);
+        let raw = compare_rgba(&img1.to_rgba8(), &img2.to_rgba8(), &options, None, false).unwrap();
+        let converted = compare_images(&img1, &rgb, &options, None, false).unwrap();
+        assert!((raw.diff_percent - 200.0 / 3.0).abs() < 0.01);
+        assert_eq!(raw.diff_percent, converted.diff_percent);
+        assert_eq!(raw.regions, converted.regions);
+    }
+
+    #[tokio::test]
+    async fn batch_results_keep_name_order_across_workers() {
+        let base = tempdir().expect("tempdir");
+        let design_dir = base.path().join("design");
+        let screenshot_dir = base.path().join("screenshots");
+        std::fs::create_dir_all(&design_dir).expect("design dir");
+        std::fs::create_dir_all(&screenshot_dir).expect("screenshot dir");
+        for name in ["c.png", "a.png", "b.png"] {
+            let image = RgbaImage::from_pixel(4, 4, Rgba([0, 0, 0, 255]));
+            image.save(design_dir.join(name)).expect("design image");
+            image
+                .save(screenshot_dir.join(name))
+                .expect("screenshot image");
+        }
+
+        let report_path = base.path().join("compare.json");
+        batch_compare(
+            &design_dir,
+            &screenshot_dir,
+            None,
+            Some(&report_path),
+            crate::reporting::ReportFormat::Json,
+            0.0,
+            &DiffOptions::pixel(0),
+            false,
+            3,
+        )
+        .await
+        .expect("identical pairs should pass");
+
+        let report = std::fs::read_to_string(&report_path).expect("report");
+        let positions: Vec<usize> = ["a.png", "b.png", "c.png"]
+            .iter()
+            .map(|name| report.find(name).expect("file in report"))
+            .collect();
+        assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
     #[tokio::test]
     async fn batch_report_includes_missing_counterpart_files() {
         let base = tempdir().expect("tempdir");
@@ -581,8 +1253,9 @@ mod tests {
             Some(&report_path),
             crate::reporting::ReportFormat::Json,
             0.0,
-            0,
+            &DiffOptions::pixel(0),
             false,
+            2,
         )
         .await;
 
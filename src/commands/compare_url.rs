@@ -1,7 +1,12 @@
+use crate::api::types::Node;
 use crate::api::{FigmaClient, FigmaUrl};
 use crate::auth::get_token;
-use crate::cli::CompareUrlArgs;
+use crate::cli::{CompareUrlArgs, DiffMethod};
 use crate::commands::compare;
+use crate::commands::mask::{IgnoreMask, IgnoreRect};
+use crate::commands::normalize::{self, Normalization};
+use crate::commands::perceptual::SsimMap;
+use crate::commands::regions::{self, DiffRegion};
 use crate::config::Config;
 use crate::output;
 use crate::reporting::{write_report, ReportItem, ReportStatus, ReportSummary};
@@ -56,6 +61,30 @@ async fn run_once(args: &CompareUrlArgs) -> Result<()> {
     if !(0.0..=100.0).contains(&args.threshold) {
         anyhow::bail!("Threshold must be between 0 and 100");
     }
+    let mut diff = compare::DiffOptions {
+        ignore: IgnoreMask::load(&args.ignore, args.ignore_mask.as_deref())?,
+        ..compare::DiffOptions::from_flags(
+            args.method,
+            args.tolerance,
+            args.aa_threshold,
+            args.max_delta_e,
+            args.min_ssim,
+        )?
+    };
+    // The compared frame's layer tree, fetched once for ignore nodes and diff regions
+    let mut frame = None;
+    if !args.ignore_node.is_empty() {
+        let (node, rects) = ignore_node_rects(
+            &client,
+            &parsed.file_key,
+            &node_id,
+            &args.ignore_node,
+            scale,
+        )
+        .await?;
+        diff.ignore.rects.extend(rects);
+        frame = Some(node);
+    }
     output::print_status(&"Exporting Figma design...".bold().to_string());
     output::print_status(&format!("  File: {}", parsed.file_key));
     output::print_status(&format!("  Node: {}", node_id));
@@ -63,7 +92,12 @@ async fn run_once(args: &CompareUrlArgs) -> Result<()> {
 
     // Export the Figma node
     let images = client
-        .export_images(&parsed.file_key, &[node_id.clone()], "png", scale)
+        .export_images(
+            &parsed.file_key,
+            std::slice::from_ref(&node_id),
+            "png",
+            scale,
+        )
         .await?;
 
     if let Some(err) = &images.err {
@@ -100,14 +134,32 @@ async fn run_once(args: &CompareUrlArgs) -> Result<()> {
     output::print_status(&format!("  Figma:      {}x{}", fw, fh));
     output::print_status(&format!("  Screenshot: {}x{}", sw, sh));
 
+    let (figma_img, screenshot_img, normalization) = if args.no_normalize {
+        (figma_img, screenshot_img, None)
+    } else {
+        let options = normalize::NormalizeOptions {
+            export_scale: scale,
+            device: args.device,
+            align_search: args.align_search,
+        };
+        let (figma_img, screenshot_img, normalization) =
+            normalize::normalize(&figma_img, &screenshot_img, &options)?;
+        print_normalization(&normalization);
+        diff.ignore.origin = normalization.export_origin();
+        (figma_img, screenshot_img, Some(normalization))
+    };
+    let (fw, fh) = figma_img.dimensions();
+    let (sw, sh) = screenshot_img.dimensions();
+
     // Check dimensions
     if fw != sw || fh != sh {
         output::print_status("");
         output::print_warning("Dimension mismatch!");
-        output::print_status("  Consider adjusting --scale or resizing the screenshot");
+        output::print_status("  Drop --no-normalize, or adjust --scale or resize the screenshot");
 
         // Still calculate diff for reference
-        let diff_percent = compare::calculate_diff(&figma_img, &screenshot_img, args.tolerance);
+        let diff_result = compare::compare_images(&figma_img, &screenshot_img, &diff, None, false)?;
+        let diff_percent = diff_result.diff_percent;
         output::print_status(&format!("  Pixel diff: {:.2}%", diff_percent));
 
         if output::format() == crate::output::OutputFormat::Json {
@@ -121,6 +173,13 @@ async fn run_once(args: &CompareUrlArgs) -> Result<()> {
                 dimensions_match: false,
                 early_exit: false,
                 diff_image: args.output.as_ref().map(|p| p.display().to_string()),
+                method: diff_result.method,
+                antialiased_pixels: diff_result.antialiased_pixels,
+                ssim: diff_result.ssim,
+                ssim_map: diff_result.ssim_map,
+                ignored_pixels: diff_result.ignored_pixels,
+                regions: diff_result.regions,
+                normalization,
             };
             output::print_json(&out)?;
         }
@@ -146,10 +205,10 @@ async fn run_once(args: &CompareUrlArgs) -> Result<()> {
     }
 
     // Calculate difference
-    let diff_result = compare::calculate_diff_internal(
+    let mut diff_result = compare::compare_images(
         &figma_img,
         &screenshot_img,
-        args.tolerance,
+        &diff,
         Some(args.threshold),
         args.fast && args.output.is_none(),
     )?;
@@ -177,10 +236,30 @@ async fn run_once(args: &CompareUrlArgs) -> Result<()> {
             suffix
         ));
     }
+    compare::print_method_details(&diff_result);
+    if !diff_result.regions.is_empty() {
+        let origin = normalization
+            .as_ref()
+            .map(Normalization::export_origin)
+            .unwrap_or((0, 0));
+        let frame = match frame {
+            Some(frame) => Ok(frame),
+            None => client.get_node(&parsed.file_key, &node_id).await,
+        };
+        match frame {
+            Ok(frame) => {
+                regions::attribute_regions(&mut diff_result.regions, &frame, scale, origin)
+            }
+            Err(err) => {
+                output::print_warning(&format!("Could not map diff regions to layers: {}", err))
+            }
+        }
+    }
+    compare::print_regions(&diff_result.regions);
 
     // Generate diff image if output specified
     if let Some(output_path) = &args.output {
-        let diff_img = compare::generate_diff_image(&figma_img, &screenshot_img, args.tolerance);
+        let diff_img = compare::render_diff_image(&figma_img, &screenshot_img, &diff);
         diff_img.save(output_path)?;
         output::print_status(&format!("  Diff image saved to: {}", output_path.display()));
     }
@@ -196,12 +275,19 @@ async fn run_once(args: &CompareUrlArgs) -> Result<()> {
             dimensions_match: fw == sw && fh == sh,
             early_exit: diff_result.early_exit,
             diff_image: args.output.as_ref().map(|p| p.display().to_string()),
+            method: diff_result.method,
+            antialiased_pixels: diff_result.antialiased_pixels,
+            ssim: diff_result.ssim,
+            ssim_map: diff_result.ssim_map,
+            ignored_pixels: diff_result.ignored_pixels,
+            regions: diff_result.regions.clone(),
+            normalization,
         };
         output::print_json(&out)?;
     }
 
     if let Some(report_path) = args.report.as_deref() {
-        let summary = ReportSummary {
+        let mut summary = ReportSummary {
             title: "fgm compare-url".to_string(),
             items: vec![ReportItem::new(
                 node_id.clone(),
@@ -217,6 +303,11 @@ async fn run_once(args: &CompareUrlArgs) -> Result<()> {
                 ),
             )],
         };
+        if diff_percent > args.threshold {
+            summary
+                .items
+                .extend(regions::report_items(&node_id, &diff_result.regions));
+        }
         write_report(report_path, args.report_format, &summary)?;
         output::print_status(&format!("  Report: {}", report_path.display()));
     }
@@ -243,4 +334,88 @@ struct CompareUrlOutput {
     dimensions_match: bool,
     early_exit: bool,
     diff_image: Option<String>,
+    method: DiffMethod,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    antialiased_pixels: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ssim: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ssim_map: Option<SsimMap>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ignored_pixels: Option<u64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    regions: Vec<DiffRegion>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    normalization: Option<Normalization>,
+}
+
+/// The frame `frame_id` and the bounds of `ignore_nodes` inside it, in export pixels
+async fn ignore_node_rects(
+    client: &FigmaClient,
+    file_key: &str,
+    frame_id: &str,
+    ignore_nodes: &[String],
+    scale: f32,
+) -> Result<(Node, Vec<IgnoreRect>)> {
+    let ignore_nodes: Vec<String> = ignore_nodes.iter().map(|id| id.replace('-', ":")).collect();
+    let mut ids = vec![frame_id.to_string()];
+    ids.extend(ignore_nodes.iter().cloned());
+    let mut nodes = client.get_node_documents(file_key, &ids).await?;
+    let frame = nodes
+        .remove(frame_id)
+        .ok_or_else(|| anyhow::anyhow!("Node {} not found", frame_id))?;
+    let frame_bounds = frame
+        .absolute_bounding_box
+        .clone()
+        .ok_or_else(|| anyhow::anyhow!("Node {} has no bounding box", frame_id))?;
+
+    let mut rects = Vec::new();
+    for id in &ignore_nodes {
+        let node = nodes
+            .get(id)
+            .ok_or_else(|| anyhow::anyhow!("Node {} not found", id))?;
+        let rect = node
+            .absolute_bounding_box
+            .as_ref()
+            .and_then(|bounds| IgnoreRect::from_node_bounds(bounds, &frame_bounds, scale));
+        match rect {
+            Some(rect) => {
+                output::print_status(&format!(
+                    "  Ignoring node {}: {}x{} at ({}, {})",
+                    id, rect.width, rect.height, rect.x, rect.y
+                ));
+                rects.push(rect);
+            }
+            None => output::print_warning(&format!(
+                "Node {} is outside the compared frame; not ignored",
+                id
+            )),
+        }
+    }
+    Ok((frame, rects))
+}
+
+fn print_normalization(normalization: &Normalization) {
+    if normalization.resampled {
+        output::print_status(&format!(
+            "  Resampled screenshot from {:.2}x to the export's density",
+            normalization.device_ratio
+        ));
+    }
+    if normalization.crop_top + normalization.crop_bottom > 0 {
+        output::print_status(&format!(
+            "  Cropped device insets: {}px top, {}px bottom",
+            normalization.crop_top, normalization.crop_bottom
+        ));
+    }
+    if normalization.offset_x != 0 || normalization.offset_y != 0 {
+        output::print_warning(&format!(
+            "Screenshot shifted by ({}, {}) px to align with the export; offsets up to --align-search are not counted as differences",
+            normalization.offset_x, normalization.offset_y
+        ));
+    }
+    output::print_status(&format!(
+        "  Compared area: {}x{}",
+        normalization.width, normalization.height
+    ));
 }
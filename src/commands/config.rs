@@ -1,4 +1,4 @@
-use crate::cli::ConfigCommands;
+use crate::cli::{ColorSpace, ConfigCommands};
 use crate::config::Config;
 use crate::output;
 use anyhow::{anyhow, Result};
@@ -57,6 +57,9 @@ fn get_value(config: &Config, key: &str) -> Result<String> {
         "export.output_dir" => Ok(config.export.output_dir.clone().unwrap_or_default()),
         "tokens.css_prefix" => Ok(config.tokens.css_prefix.clone()),
         "tokens.swift_prefix" => Ok(config.tokens.swift_prefix.clone()),
+        "tokens.color_space" => Ok(config.tokens.color_space.clone()),
+        "api.base_url" => Ok(config.api.base_url.clone().unwrap_or_default()),
+        "api.image_host" => Ok(config.api.image_host.clone().unwrap_or_default()),
         _ => Err(anyhow!("Unknown config key: {}", key)),
     }
 }
@@ -121,12 +124,40 @@ fn set_value(config: &mut Config, key: &str, value: Option<&str>, unset: bool) -
             let v = value.ok_or_else(|| anyhow!("Value is required"))?;
             config.tokens.swift_prefix = v.to_string();
         }
+        "tokens.color_space" => {
+            let v = value.ok_or_else(|| anyhow!("Value is required"))?;
+            config.tokens.color_space = ColorSpace::parse(v)?.name();
+        }
+        "api.base_url" => {
+            if unset {
+                config.api.base_url = None;
+            } else {
+                let v = value.ok_or_else(|| anyhow!("Value is required"))?;
+                config.api.base_url = Some(validate_http_url(v)?);
+            }
+        }
+        "api.image_host" => {
+            if unset {
+                config.api.image_host = None;
+            } else {
+                let v = value.ok_or_else(|| anyhow!("Value is required"))?;
+                config.api.image_host = Some(validate_http_url(v)?);
+            }
+        }
         _ => return Err(anyhow!("Unknown config key: {}", key)),
     }
 
     Ok(())
 }
 
+fn validate_http_url(value: &str) -> Result<String> {
+    let parsed = url::Url::parse(value).map_err(|_| anyhow!("Invalid URL: {}", value))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(anyhow!("URL must use http or https: {}", value));
+    }
+    Ok(value.trim_end_matches('/').to_string())
+}
+
 fn normalize_key(key: &str) -> String {
     key.trim().to_lowercase().replace('-', "_").replace(' ', "")
 }
@@ -1,4 +1,4 @@
-use crate::api::create_shared_cache;
+use crate::api::{create_shared_cache, FigmaEndpoints};
 use crate::auth::get_token_with_source;
 use crate::cli::DoctorArgs;
 use crate::config::Config;
@@ -53,6 +53,23 @@ async fn build_summary(output_dir: Option<&Path>) -> ReportSummary {
         )),
     }
 
+    let endpoints = FigmaEndpoints::resolve();
+    if endpoints.is_custom() {
+        items.push(ReportItem::warn(
+            "api-endpoint",
+            format!(
+                "Using custom API base {} (image host: {})",
+                endpoints.api_base,
+                endpoints.image_host.as_deref().unwrap_or("unchanged")
+            ),
+        ));
+    } else {
+        items.push(ReportItem::ok(
+            "api-endpoint",
+            format!("Using {}", endpoints.api_base),
+        ));
+    }
+
     match get_token_with_source() {
         Ok(token) => {
             items.push(ReportItem::ok(
@@ -1,7 +1,16 @@
 use crate::api::rate_limit::RateLimitTelemetry;
 use crate::api::{CacheKey, CacheTTL, FigmaClient, FigmaUrl};
 use crate::auth::get_token;
-use crate::cli::{ExportCommands, ExportFormat, ExportProfile, Platform};
+use crate::cli::{
+    ExportCommands, ExportFormat, ExportProfile, IconManifest, Platform, SvgOptimizeArgs,
+};
+use crate::commands::export_settings;
+use crate::commands::icons;
+use crate::commands::svg_optimize::{optimize_svg_bytes, SvgOptimizeOptions};
+use crate::commands::tokens::extract_design_tokens;
+use crate::commands::transcode;
+use crate::commands::vector_drawable::{android_resource_name, svg_to_vector_drawable};
+use crate::commands::xcassets;
 use crate::config::Config;
 use crate::output;
 use crate::select;
@@ -12,7 +21,7 @@ use colored::Colorize;
 use image::GenericImageView;
 use indicatif::{ProgressBar, ProgressStyle};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
@@ -25,10 +34,15 @@ const INITIAL_BATCH_SIZE: usize = 20;
 const MIN_BATCH_SIZE: usize = 5;
 const MAX_BATCH_SIZE: usize = 40;
 const DOWNLOAD_STATUS_INTERVAL: usize = 5;
+/// Resume/delta index written into the export directory
+pub(crate) const RESUME_INDEX_FILE: &str = ".fgm-export-index.json";
 
 #[derive(Clone)]
 struct ResolvedFileOptions {
     format: String,
+    quality: Option<u8>,
+    optimize_svg: Option<SvgOptimizeOptions>,
+    use_export_settings: bool,
     scale: f32,
     output: PathBuf,
     llm_pack: bool,
@@ -48,9 +62,16 @@ struct QuickExportArgs {
     /// Output directory
     #[arg(short, long, help = "Where to save exported files")]
     output: Option<PathBuf>,
-    /// Image format: png, svg, pdf, jpg
+    /// Image format: png, svg, pdf, jpg, webp, avif
     #[arg(short, long, help = "Output format")]
     format: Option<ExportFormat>,
+    /// Encoder quality for webp, avif and jpg (1-100)
+    #[arg(
+        long,
+        value_parser = clap::value_parser!(u8).range(1..=100),
+        help = "Encoder quality for webp, avif and jpg (1-100)"
+    )]
+    quality: Option<u8>,
     /// Scale factor (1-4)
     #[arg(short, long, value_parser = clap::value_parser!(f32), help = "Scale multiplier (1-4)")]
     scale: Option<f32>,
@@ -79,35 +100,43 @@ struct QuickExportArgs {
     /// Apply a preset export profile
     #[arg(long, value_enum, help = "Export profile preset")]
     profile: Option<ExportProfile>,
+    #[command(flatten)]
+    svg: SvgOptimizeArgs,
 }
 
 #[derive(Debug, Clone, Serialize, Default)]
-struct ExportTelemetry {
-    api_calls: u64,
-    export_batches: u64,
-    cache_hits: u64,
-    cache_misses: u64,
-    download_requests: u64,
-    skipped_writes: u64,
-    elapsed_ms: u64,
-    rate_limits: Option<RateLimitTelemetry>,
+pub(crate) struct ExportTelemetry {
+    pub(crate) api_calls: u64,
+    pub(crate) export_batches: u64,
+    pub(crate) cache_hits: u64,
+    pub(crate) cache_misses: u64,
+    pub(crate) download_requests: u64,
+    pub(crate) skipped_writes: u64,
+    pub(crate) svg_optimized: u64,
+    pub(crate) svg_bytes_saved: u64,
+    pub(crate) elapsed_ms: u64,
+    pub(crate) rate_limits: Option<RateLimitTelemetry>,
 }
 
 #[derive(Debug, Clone)]
-struct PlannedAsset {
-    order: usize,
-    node_id: String,
-    image_url: String,
-    filename: String,
+pub(crate) struct PlannedAsset {
+    pub(crate) order: usize,
+    pub(crate) node_id: String,
+    pub(crate) image_url: String,
+    pub(crate) filename: String,
+    /// Format requested from the images API
+    pub(crate) source_format: String,
 }
 
 #[derive(Debug)]
-struct DownloadedAsset {
-    order: usize,
-    node_id: String,
-    image_url: String,
-    filename: String,
-    bytes: Vec<u8>,
+pub(crate) struct DownloadedAsset {
+    pub(crate) order: usize,
+    pub(crate) node_id: String,
+    pub(crate) image_url: String,
+    pub(crate) filename: String,
+    pub(crate) source_format: String,
+    pub(crate) bytes: Vec<u8>,
+    pub(crate) source_bytes: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Default)]
@@ -134,6 +163,8 @@ struct ExportedAssetRecord {
     source_height: Option<u32>,
     exported_width: Option<u32>,
     exported_height: Option<u32>,
+    source_format: String,
+    source_bytes: usize,
     bytes: usize,
     content_hash: String,
     skipped_write: bool,
@@ -149,6 +180,7 @@ struct LlmPackManifest {
     quick_mode: bool,
     profile: Option<String>,
     format: String,
+    quality: Option<u8>,
     scale: f32,
     output_dir: String,
     telemetry: ExportTelemetry,
@@ -161,6 +193,7 @@ struct ExportJsonSummary {
     source_input: String,
     output_dir: String,
     format: String,
+    quality: Option<u8>,
     scale: f32,
     asset_count: usize,
     quick_mode: bool,
@@ -171,20 +204,23 @@ struct ExportJsonSummary {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
-struct ResumeIndex {
+pub(crate) struct ResumeIndex {
     #[serde(default = "default_resume_index_version")]
-    version: u8,
+    pub(crate) version: u8,
     #[serde(default)]
-    file_version: Option<String>,
+    pub(crate) file_version: Option<String>,
     #[serde(default)]
-    files: HashMap<String, ResumeIndexEntry>,
+    pub(crate) files: HashMap<String, ResumeIndexEntry>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-struct ResumeIndexEntry {
-    node_id: String,
-    content_hash: String,
-    updated_at: String,
+pub(crate) struct ResumeIndexEntry {
+    pub(crate) node_id: String,
+    pub(crate) content_hash: String,
+    pub(crate) updated_at: String,
+    /// Hash of the source node, for exports that skip unchanged nodes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) source_hash: Option<String>,
 }
 
 fn default_resume_index_version() -> u8 {
@@ -203,15 +239,19 @@ pub async fn run(command: ExportCommands) -> Result<()> {
             all_frames,
             pick,
             format,
+            quality,
             scale,
             output,
             name,
             platform,
+            colorsets,
             llm_pack,
             manifest_name,
             resume,
             delta,
             profile,
+            svg,
+            use_export_settings,
             watch: should_watch,
             watch_interval,
         } => {
@@ -233,6 +273,7 @@ pub async fn run(command: ExportCommands) -> Result<()> {
             let options = resolve_file_options(
                 &config,
                 format,
+                quality,
                 scale,
                 output,
                 llm_pack,
@@ -240,6 +281,8 @@ pub async fn run(command: ExportCommands) -> Result<()> {
                 resume,
                 delta,
                 profile,
+                svg.options(),
+                use_export_settings,
                 file_key_or_url,
                 false,
             )?;
@@ -249,6 +292,9 @@ pub async fn run(command: ExportCommands) -> Result<()> {
                 if options.llm_pack {
                     output::print_warning("--llm-pack is ignored for --platform exports");
                 }
+                if options.optimize_svg.is_some() {
+                    output::print_warning("--optimize-svg is ignored for --platform exports");
+                }
                 export_platform(
                     &client,
                     &file_key,
@@ -257,6 +303,8 @@ pub async fn run(command: ExportCommands) -> Result<()> {
                     &options.output,
                     name.as_deref(),
                     platform,
+                    &options.format,
+                    colorsets,
                 )
                 .await?;
             } else {
@@ -298,6 +346,8 @@ pub async fn run(command: ExportCommands) -> Result<()> {
                                 &options.output,
                                 name.as_deref(),
                                 platform,
+                                &options.format,
+                                colorsets,
                             )
                             .await
                         } else {
@@ -318,7 +368,51 @@ pub async fn run(command: ExportCommands) -> Result<()> {
 
             Ok(())
         }
-        ExportCommands::Batch { manifest } => batch_export(&client, &manifest, &config).await,
+        ExportCommands::Batch { manifest, svg } => {
+            batch_export(&client, &manifest, &config, svg.options()).await
+        }
+        ExportCommands::Icons {
+            file_key_or_url,
+            page,
+            node,
+            output,
+            sprite_name,
+            manifest,
+            resume,
+            delta,
+            svg,
+        } => {
+            let parsed = FigmaUrl::parse(&file_key_or_url)?;
+            let node_id = node.or(parsed.node_id);
+            let manifests = if manifest.is_empty() {
+                vec![IconManifest::Ts, IconManifest::Swift, IconManifest::Kotlin]
+            } else {
+                manifest
+            };
+            let options = icons::IconExportOptions {
+                output: output.unwrap_or_else(|| {
+                    config
+                        .export
+                        .output_dir
+                        .clone()
+                        .map(PathBuf::from)
+                        .unwrap_or_else(|| PathBuf::from("."))
+                }),
+                sprite_name,
+                manifests,
+                resume,
+                delta,
+                optimize_svg: svg.options(),
+            };
+            icons::export_icons(
+                &client,
+                &parsed.file_key,
+                page.as_deref(),
+                node_id.as_deref(),
+                &options,
+            )
+            .await
+        }
     }
 }
 
@@ -347,6 +441,7 @@ pub async fn run_quick(args: Vec<String>) -> Result<()> {
     let options = resolve_file_options(
         &config,
         quick.format,
+        quick.quality,
         quick.scale,
         quick.output,
         quick.llm_pack,
@@ -354,6 +449,8 @@ pub async fn run_quick(args: Vec<String>) -> Result<()> {
         quick.resume,
         quick.delta,
         quick.profile,
+        quick.svg.options(),
+        false,
         quick.input,
         true,
     )?;
@@ -361,9 +458,11 @@ pub async fn run_quick(args: Vec<String>) -> Result<()> {
     export_file(&client, &parsed.file_key, &[], true, None, &options).await
 }
 
+#[allow(clippy::too_many_arguments)]
 fn resolve_file_options(
     config: &Config,
     format: Option<ExportFormat>,
+    quality: Option<u8>,
     scale: Option<f32>,
     output: Option<PathBuf>,
     llm_pack: bool,
@@ -371,6 +470,8 @@ fn resolve_file_options(
     resume: bool,
     delta: bool,
     profile: Option<ExportProfile>,
+    optimize_svg: Option<SvgOptimizeOptions>,
+    use_export_settings: bool,
     source_input: String,
     quick_mode: bool,
 ) -> Result<ResolvedFileOptions> {
@@ -415,11 +516,34 @@ fn resolve_file_options(
             resolved_delta = true;
         }
     }
+    if let Some(ExportProfile::Web) = &profile {
+        if !format_was_set {
+            resolved_format = ExportFormat::Webp;
+        }
+        if !scale_was_set {
+            resolved_scale = 2.0;
+        }
+        if !resume {
+            resolved_resume = true;
+        }
+    }
 
     if !(1.0..=4.0).contains(&resolved_scale) {
         anyhow::bail!("Scale must be between 1 and 4");
     }
 
+    let format = resolved_format.to_string();
+    let resolved_quality = resolve_quality(&format, quality);
+    if quality.is_some() && resolved_quality.is_none() {
+        output::print_warning(&format!("--quality is not used for {} exports", format));
+    }
+    if optimize_svg.is_some() && format != "svg" {
+        output::print_warning(&format!(
+            "--optimize-svg is not used for {} exports",
+            format
+        ));
+    }
+
     let output = output.unwrap_or_else(|| {
         config
             .export
@@ -436,7 +560,10 @@ fn resolve_file_options(
     });
 
     Ok(ResolvedFileOptions {
-        format: resolved_format.to_string(),
+        format,
+        quality: resolved_quality,
+        optimize_svg,
+        use_export_settings,
         scale: resolved_scale,
         output,
         llm_pack: resolved_llm_pack,
@@ -483,13 +610,32 @@ async fn export_file(
         anyhow::bail!("No frames found to export");
     }
 
-    let resume_index_path = options.output.join(".fgm-export-index.json");
+    let resume_index_path = options.output.join(RESUME_INDEX_FILE);
     let mut resume_index = if options.resume || options.delta {
         load_resume_index(&resume_index_path)?
     } else {
         ResumeIndex::default()
     };
 
+    let export_jobs = if options.use_export_settings {
+        telemetry.api_calls = telemetry.api_calls.saturating_add(1);
+        let file = client.get_file(file_key).await?;
+        let jobs = export_settings::collect_export_jobs(&file.document, &ids_to_export);
+        if jobs.is_empty() {
+            anyhow::bail!("None of the selected nodes have export settings in Figma");
+        }
+        Some(jobs)
+    } else {
+        None
+    };
+    let expected_files = match &export_jobs {
+        Some(jobs) => jobs
+            .iter()
+            .map(|job| (job.node_id.clone(), job.filename.clone()))
+            .collect(),
+        None => expected_filenames(&ids_to_export, custom_name, &options.format),
+    };
+
     let current_file_version = if options.delta {
         telemetry.api_calls = telemetry.api_calls.saturating_add(1);
         client
@@ -505,11 +651,10 @@ async fn export_file(
         options,
         &resume_index,
         current_file_version.as_deref(),
-        &ids_to_export,
-        custom_name,
+        &expected_files,
     ) {
         telemetry.elapsed_ms = start.elapsed().as_millis() as u64;
-        telemetry.skipped_writes = ids_to_export.len() as u64;
+        telemetry.skipped_writes = expected_files.len() as u64;
         telemetry.rate_limits = Some(client.rate_limit_telemetry().await);
 
         if output::format() == crate::output::OutputFormat::Json {
@@ -518,8 +663,9 @@ async fn export_file(
                 source_input: options.source_input.clone(),
                 output_dir: options.output.display().to_string(),
                 format: options.format.clone(),
+                quality: options.quality,
                 scale: options.scale,
-                asset_count: ids_to_export.len(),
+                asset_count: expected_files.len(),
                 quick_mode: options.quick_mode,
                 llm_pack: options.llm_pack,
                 delta: options.delta,
@@ -538,176 +684,94 @@ async fn export_file(
         return Ok(());
     }
 
-    output::print_status(
-        &format!(
-            "Exporting {} node(s) as {} at {}x to {}...",
-            ids_to_export.len(),
-            options.format,
-            options.scale,
-            options.output.display()
-        )
-        .bold()
-        .to_string(),
-    );
-    output::print_status("Resolving image URLs with adaptive batching...");
-
-    let mut all_images: HashMap<String, Option<String>> = HashMap::new();
-    let mut batch_size = initial_batch_size(options.low_rate, ids_to_export.len());
-    let mut cursor = 0usize;
-
-    while cursor < ids_to_export.len() {
-        let mut retry_count = 0u8;
-        let chunk_end = (cursor + batch_size).min(ids_to_export.len());
-        let current_chunk: Vec<String> = ids_to_export[cursor..chunk_end].to_vec();
-        let used_full_batch = current_chunk.len() == batch_size;
-
-        let mut saw_rate_limit = false;
-
-        loop {
-            let cache_key = CacheKey::Images(
-                file_key.to_string(),
-                CacheKey::hash_export_params(&current_chunk, &options.format, options.scale),
+    let planned_assets = match &export_jobs {
+        Some(jobs) => {
+            output::print_status(
+                &format!(
+                    "Exporting {} asset(s) from the export settings of {} node(s) to {}...",
+                    jobs.len(),
+                    ids_to_export.len(),
+                    options.output.display()
+                )
+                .bold()
+                .to_string(),
             );
-            if client.cache().contains(&cache_key) {
-                telemetry.cache_hits = telemetry.cache_hits.saturating_add(1);
-            } else {
-                telemetry.cache_misses = telemetry.cache_misses.saturating_add(1);
+            plan_export_setting_assets(client, file_key, jobs, options.low_rate, &mut telemetry)
+                .await?
+        }
+        None => {
+            output::print_status(
+                &format!(
+                    "Exporting {} node(s) as {} at {}x to {}...",
+                    ids_to_export.len(),
+                    options.format,
+                    options.scale,
+                    options.output.display()
+                )
+                .bold()
+                .to_string(),
+            );
+            let api_format = transcode::source_format(&options.format, options.quality);
+            if let Some(quality) = options.quality {
+                output::print_status(&format!(
+                    "Rendering as {} and transcoding at quality {}",
+                    api_format, quality
+                ));
             }
+            let all_images = resolve_export_urls(
+                client,
+                file_key,
+                &ids_to_export,
+                api_format,
+                options.scale,
+                options.low_rate,
+                &mut telemetry,
+            )
+            .await?;
 
-            telemetry.export_batches = telemetry.export_batches.saturating_add(1);
-            telemetry.api_calls = telemetry.api_calls.saturating_add(1);
-
-            let before = client.rate_limit_telemetry().await;
-            let result = client
-                .export_images(file_key, &current_chunk, &options.format, options.scale)
-                .await;
-            let after = client.rate_limit_telemetry().await;
-
-            if after.total_retries > before.total_retries
-                || after.total_rate_limited_responses > before.total_rate_limited_responses
-            {
-                saw_rate_limit = true;
+            if all_images.is_empty() {
+                anyhow::bail!("No images were exported");
             }
 
-            match result {
-                Ok(images) => {
-                    if let Some(err) = &images.err {
-                        if is_rate_limit_message(err) && retry_count < 3 {
-                            retry_count += 1;
-                            batch_size = next_batch_size_for_mode(batch_size, true, false, options);
-                            let delay_ms = retry_after_delay_ms(client, options).await;
-                            tokio::time::sleep(tokio::time::Duration::from_millis(delay_ms)).await;
-                            continue;
-                        }
-
-                        output::print_warning(&format!("API Error: {}", err));
-                        if images.status == Some(400) || images.status == Some(404) {
-                            output::print_warning("Some node IDs may be invalid or inaccessible");
-                        }
-                    } else {
-                        all_images.extend(images.images);
-                    }
-                    break;
-                }
-                Err(err) => {
-                    if is_rate_limit_message(&err.to_string()) && retry_count < 3 {
-                        retry_count += 1;
-                        saw_rate_limit = true;
-                        batch_size = next_batch_size_for_mode(batch_size, true, false, options);
-                        let delay_ms = retry_after_delay_ms(client, options).await;
-                        tokio::time::sleep(tokio::time::Duration::from_millis(delay_ms)).await;
-                        continue;
-                    }
-                    return Err(err);
-                }
+            let mut planned_assets: Vec<PlannedAsset> = all_images
+                .into_iter()
+                .filter_map(|(node_id, url)| {
+                    url.map(|image_url| PlannedAsset {
+                        order: 0,
+                        filename: String::new(),
+                        node_id,
+                        image_url,
+                        source_format: api_format.to_string(),
+                    })
+                })
+                .collect();
+
+            planned_assets.sort_by(|a, b| a.node_id.cmp(&b.node_id));
+            let use_custom_name = custom_name.is_some() && planned_assets.len() == 1;
+            for (i, asset) in planned_assets.iter_mut().enumerate() {
+                asset.order = i;
+                asset.filename = build_filename(
+                    &asset.node_id,
+                    i,
+                    custom_name,
+                    use_custom_name,
+                    &options.format,
+                );
             }
+            planned_assets
         }
-
-        batch_size = next_batch_size_for_mode(batch_size, saw_rate_limit, used_full_batch, options);
-        cursor = chunk_end;
-        output::print_status(&format_resolution_progress(
-            cursor,
-            ids_to_export.len(),
-            batch_size,
-        ));
-
-        if cursor < ids_to_export.len() {
-            tokio::time::sleep(tokio::time::Duration::from_millis(150)).await;
-        }
-    }
-
-    if all_images.is_empty() {
+    };
+    if planned_assets.is_empty() {
         anyhow::bail!("No images were exported");
     }
-
-    let mut planned_assets: Vec<PlannedAsset> = all_images
-        .into_iter()
-        .filter_map(|(node_id, url)| {
-            url.map(|image_url| PlannedAsset {
-                order: 0,
-                filename: String::new(),
-                node_id,
-                image_url,
-            })
-        })
-        .collect();
-
-    planned_assets.sort_by(|a, b| a.node_id.cmp(&b.node_id));
-    let use_custom_name = custom_name.is_some() && planned_assets.len() == 1;
-    for (i, asset) in planned_assets.iter_mut().enumerate() {
-        asset.order = i;
-        asset.filename = build_filename(
-            &asset.node_id,
-            i,
-            custom_name,
-            use_custom_name,
-            &options.format,
-        );
-    }
-    let total_assets = planned_assets.len();
-    let mut download_concurrency = client.download_parallelism().max(1);
-    if options.low_rate {
-        download_concurrency = download_concurrency.min(4).max(1);
-    }
-    output::print_status(&format!(
-        "Downloading {} image(s) with up to {} concurrent requests...",
-        total_assets, download_concurrency
-    ));
-
-    let semaphore = Arc::new(Semaphore::new(download_concurrency));
-    let mut joins = JoinSet::new();
-
-    for asset in planned_assets {
-        let sem = semaphore.clone();
-        let client = client.clone();
-        joins.spawn(async move {
-            let _permit = sem
-                .acquire_owned()
-                .await
-                .map_err(|_| anyhow!("download semaphore closed"))?;
-            let bytes = client.download_image(&asset.image_url).await?;
-            Ok::<DownloadedAsset, anyhow::Error>(DownloadedAsset {
-                order: asset.order,
-                node_id: asset.node_id,
-                image_url: asset.image_url,
-                filename: asset.filename,
-                bytes,
-            })
-        });
-    }
-
-    let mut downloaded = Vec::new();
-    let mut downloaded_count = 0usize;
-    while let Some(join_result) = joins.join_next().await {
-        let asset = join_result.map_err(|err| anyhow!("download task failed: {}", err))??;
-        downloaded.push(asset);
-        downloaded_count = downloaded_count.saturating_add(1);
-        if should_emit_download_status(downloaded_count, total_assets) {
-            output::print_status(&format_download_progress(downloaded_count, total_assets));
-        }
-    }
-
-    downloaded.sort_by_key(|asset| asset.order);
+    let downloaded = download_assets(
+        client,
+        planned_assets,
+        options.low_rate,
+        &options.format,
+        options.quality.filter(|_| export_jobs.is_none()),
+    )
+    .await?;
 
     telemetry.download_requests = downloaded.len() as u64;
     telemetry.api_calls = telemetry
@@ -728,7 +792,26 @@ async fn export_file(
     output::print_status("Writing files to disk...");
 
     let mut asset_records = Vec::new();
-    for asset in downloaded {
+    for mut asset in downloaded {
+        if let Some(svg_options) = options
+            .optimize_svg
+            .as_ref()
+            .filter(|_| asset.source_format == "svg")
+        {
+            match optimize_svg_bytes(&asset.bytes, svg_options) {
+                Ok(optimized) => {
+                    telemetry.svg_optimized = telemetry.svg_optimized.saturating_add(1);
+                    telemetry.svg_bytes_saved = telemetry
+                        .svg_bytes_saved
+                        .saturating_add(asset.bytes.len().saturating_sub(optimized.len()) as u64);
+                    asset.bytes = optimized;
+                }
+                Err(err) => output::print_warning(&format!(
+                    "Could not optimize {}: {}",
+                    asset.filename, err
+                )),
+            }
+        }
         let filepath = options.output.join(&asset.filename);
         let content_hash = hash_bytes(&asset.bytes);
         let existing_hash = if options.resume {
@@ -740,6 +823,9 @@ async fn export_file(
         let skipped_write =
             options.resume && existing_hash.as_deref() == Some(content_hash.as_str());
         if !skipped_write {
+            if let Some(parent) = filepath.parent() {
+                fs::create_dir_all(parent)?;
+            }
             fs::write(&filepath, &asset.bytes)?;
         } else {
             telemetry.skipped_writes = telemetry.skipped_writes.saturating_add(1);
@@ -751,6 +837,7 @@ async fn export_file(
                 node_id: asset.node_id.clone(),
                 content_hash: content_hash.clone(),
                 updated_at: chrono::Utc::now().to_rfc3339(),
+                source_hash: None,
             },
         );
 
@@ -773,6 +860,8 @@ async fn export_file(
             source_height: None,
             exported_width,
             exported_height,
+            source_format: asset.source_format.clone(),
+            source_bytes: asset.source_bytes,
             bytes: asset.bytes.len(),
             content_hash,
             skipped_write,
@@ -799,6 +888,12 @@ async fn export_file(
         "Saved {} file(s), skipped {} unchanged file(s)",
         written_count, telemetry.skipped_writes
     ));
+    if telemetry.svg_optimized > 0 {
+        output::print_status(&format!(
+            "Optimized {} SVG(s), saved {} bytes",
+            telemetry.svg_optimized, telemetry.svg_bytes_saved
+        ));
+    }
 
     let mut node_context = HashMap::new();
     if options.llm_pack {
@@ -827,7 +922,7 @@ async fn export_file(
     if options.llm_pack {
         let manifest_path = options.output.join(&options.manifest_name);
         let manifest = LlmPackManifest {
-            schema_version: 2,
+            schema_version: 3,
             generated_at: chrono::Utc::now().to_rfc3339(),
             file_key: file_key.to_string(),
             source_input: options.source_input.clone(),
@@ -839,6 +934,7 @@ async fn export_file(
             quick_mode: options.quick_mode,
             profile: options.profile.as_ref().map(profile_name),
             format: options.format.clone(),
+            quality: options.quality,
             scale: options.scale,
             output_dir: options.output.display().to_string(),
             telemetry: telemetry.clone(),
@@ -858,6 +954,7 @@ async fn export_file(
             source_input: options.source_input.clone(),
             output_dir: options.output.display().to_string(),
             format: options.format.clone(),
+            quality: options.quality,
             scale: options.scale,
             asset_count: asset_records.len(),
             quick_mode: options.quick_mode,
@@ -873,6 +970,216 @@ async fn export_file(
     Ok(())
 }
 
+/// Resolve export URLs in adaptive batches, shrinking them on rate limits
+pub(crate) async fn resolve_export_urls(
+    client: &FigmaClient,
+    file_key: &str,
+    node_ids: &[String],
+    format: &str,
+    scale: f32,
+    low_rate: bool,
+    telemetry: &mut ExportTelemetry,
+) -> Result<HashMap<String, Option<String>>> {
+    output::print_status("Resolving image URLs with adaptive batching...");
+
+    let mut all_images: HashMap<String, Option<String>> = HashMap::new();
+    let mut batch_size = initial_batch_size(low_rate, node_ids.len());
+    let mut cursor = 0usize;
+
+    while cursor < node_ids.len() {
+        let mut retry_count = 0u8;
+        let chunk_end = (cursor + batch_size).min(node_ids.len());
+        let current_chunk: Vec<String> = node_ids[cursor..chunk_end].to_vec();
+        let used_full_batch = current_chunk.len() == batch_size;
+
+        let mut saw_rate_limit = false;
+
+        loop {
+            let cache_key = CacheKey::Images(
+                file_key.to_string(),
+                CacheKey::hash_export_params(&current_chunk, format, scale),
+            );
+            if client.cache().contains(&cache_key) {
+                telemetry.cache_hits = telemetry.cache_hits.saturating_add(1);
+            } else {
+                telemetry.cache_misses = telemetry.cache_misses.saturating_add(1);
+            }
+
+            telemetry.export_batches = telemetry.export_batches.saturating_add(1);
+            telemetry.api_calls = telemetry.api_calls.saturating_add(1);
+
+            let before = client.rate_limit_telemetry().await;
+            let result = client
+                .export_images(file_key, &current_chunk, format, scale)
+                .await;
+            let after = client.rate_limit_telemetry().await;
+
+            if after.total_retries > before.total_retries
+                || after.total_rate_limited_responses > before.total_rate_limited_responses
+            {
+                saw_rate_limit = true;
+            }
+
+            match result {
+                Ok(images) => {
+                    if let Some(err) = &images.err {
+                        if is_rate_limit_message(err) && retry_count < 3 {
+                            retry_count += 1;
+                            batch_size =
+                                next_batch_size_for_mode(batch_size, true, false, low_rate);
+                            let delay_ms = retry_after_delay_ms(client, low_rate).await;
+                            tokio::time::sleep(tokio::time::Duration::from_millis(delay_ms)).await;
+                            continue;
+                        }
+
+                        output::print_warning(&format!("API Error: {}", err));
+                        if images.status == Some(400) || images.status == Some(404) {
+                            output::print_warning("Some node IDs may be invalid or inaccessible");
+                        }
+                    } else {
+                        all_images.extend(images.images);
+                    }
+                    break;
+                }
+                Err(err) => {
+                    if is_rate_limit_message(&err.to_string()) && retry_count < 3 {
+                        retry_count += 1;
+                        saw_rate_limit = true;
+                        batch_size = next_batch_size_for_mode(batch_size, true, false, low_rate);
+                        let delay_ms = retry_after_delay_ms(client, low_rate).await;
+                        tokio::time::sleep(tokio::time::Duration::from_millis(delay_ms)).await;
+                        continue;
+                    }
+                    return Err(err);
+                }
+            }
+        }
+
+        batch_size =
+            next_batch_size_for_mode(batch_size, saw_rate_limit, used_full_batch, low_rate);
+        cursor = chunk_end;
+        output::print_status(&format_resolution_progress(
+            cursor,
+            node_ids.len(),
+            batch_size,
+        ));
+
+        if cursor < node_ids.len() {
+            tokio::time::sleep(tokio::time::Duration::from_millis(150)).await;
+        }
+    }
+
+    Ok(all_images)
+}
+
+/// Download planned assets concurrently, transcoding them when `quality` is set
+pub(crate) async fn download_assets(
+    client: &FigmaClient,
+    planned_assets: Vec<PlannedAsset>,
+    low_rate: bool,
+    format: &str,
+    quality: Option<u8>,
+) -> Result<Vec<DownloadedAsset>> {
+    let total_assets = planned_assets.len();
+    let mut download_concurrency = client.download_parallelism().max(1);
+    if low_rate {
+        download_concurrency = download_concurrency.clamp(1, 4);
+    }
+    output::print_status(&format!(
+        "Downloading {} image(s) with up to {} concurrent requests...",
+        total_assets, download_concurrency
+    ));
+
+    let semaphore = Arc::new(Semaphore::new(download_concurrency));
+    let mut joins = JoinSet::new();
+
+    for asset in planned_assets {
+        let sem = semaphore.clone();
+        let client = client.clone();
+        let format = format.to_string();
+        joins.spawn(async move {
+            let _permit = sem
+                .acquire_owned()
+                .await
+                .map_err(|_| anyhow!("download semaphore closed"))?;
+            let mut bytes = client.download_image(&asset.image_url).await?;
+            let source_bytes = bytes.len();
+            if let Some(quality) = quality {
+                bytes = tokio::task::spawn_blocking(move || {
+                    transcode::transcode(&bytes, &format, quality)
+                })
+                .await
+                .map_err(|err| anyhow!("transcode task failed: {}", err))??;
+            }
+            Ok::<DownloadedAsset, anyhow::Error>(DownloadedAsset {
+                order: asset.order,
+                node_id: asset.node_id,
+                image_url: asset.image_url,
+                filename: asset.filename,
+                source_format: asset.source_format,
+                bytes,
+                source_bytes,
+            })
+        });
+    }
+
+    let mut downloaded = Vec::new();
+    let mut downloaded_count = 0usize;
+    while let Some(join_result) = joins.join_next().await {
+        let asset = join_result.map_err(|err| anyhow!("download task failed: {}", err))??;
+        downloaded.push(asset);
+        downloaded_count = downloaded_count.saturating_add(1);
+        if should_emit_download_status(downloaded_count, total_assets) {
+            output::print_status(&format_download_progress(downloaded_count, total_assets));
+        }
+    }
+
+    downloaded.sort_by_key(|asset| asset.order);
+    Ok(downloaded)
+}
+
+/// Resolve one URL batch per distinct format and scale of the designers' export settings
+pub(crate) async fn plan_export_setting_assets(
+    client: &FigmaClient,
+    file_key: &str,
+    jobs: &[export_settings::ExportJob],
+    low_rate: bool,
+    telemetry: &mut ExportTelemetry,
+) -> Result<Vec<PlannedAsset>> {
+    let mut planned = Vec::new();
+    for batch in export_settings::batches(jobs) {
+        output::print_status(&format!(
+            "  {} at {}x: {} file(s)",
+            batch.format,
+            batch.scale,
+            batch.jobs.len()
+        ));
+        let urls = resolve_export_urls(
+            client,
+            file_key,
+            &batch.node_ids(),
+            &batch.format,
+            batch.scale,
+            low_rate,
+            telemetry,
+        )
+        .await?;
+        for job in batch.jobs {
+            match urls.get(&job.node_id).cloned().flatten() {
+                Some(image_url) => planned.push(PlannedAsset {
+                    order: planned.len(),
+                    node_id: job.node_id.clone(),
+                    image_url,
+                    filename: job.filename.clone(),
+                    source_format: job.format.clone(),
+                }),
+                None => output::print_warning(&format!("{}: no image returned", job.filename)),
+            }
+        }
+    }
+    Ok(planned)
+}
+
 fn build_filename(
     node_id: &str,
     index: usize,
@@ -889,13 +1196,13 @@ fn build_filename(
     }
 }
 
-fn hash_bytes(bytes: &[u8]) -> String {
+pub(crate) fn hash_bytes(bytes: &[u8]) -> String {
     let mut hasher = std::collections::hash_map::DefaultHasher::new();
     bytes.hash(&mut hasher);
     format!("{:x}", hasher.finish())
 }
 
-fn hash_existing_file(path: &Path) -> Result<Option<String>> {
+pub(crate) fn hash_existing_file(path: &Path) -> Result<Option<String>> {
     if !path.exists() {
         return Ok(None);
     }
@@ -903,7 +1210,7 @@ fn hash_existing_file(path: &Path) -> Result<Option<String>> {
     Ok(Some(hash_bytes(&bytes)))
 }
 
-fn load_resume_index(path: &Path) -> Result<ResumeIndex> {
+pub(crate) fn load_resume_index(path: &Path) -> Result<ResumeIndex> {
     if !path.exists() {
         return Ok(ResumeIndex::default());
     }
@@ -912,7 +1219,7 @@ fn load_resume_index(path: &Path) -> Result<ResumeIndex> {
     Ok(index)
 }
 
-fn save_resume_index(path: &Path, index: &ResumeIndex) -> Result<()> {
+pub(crate) fn save_resume_index(path: &Path, index: &ResumeIndex) -> Result<()> {
     fs::write(path, serde_json::to_string_pretty(index)?)?;
     Ok(())
 }
@@ -921,6 +1228,17 @@ fn profile_name(profile: &ExportProfile) -> String {
     match profile {
         ExportProfile::PixelPerfect => "pixel-perfect".to_string(),
         ExportProfile::LowRate => "low-rate".to_string(),
+        ExportProfile::Web => "web".to_string(),
+    }
+}
+
+/// Encoder quality for a format: transcoded web formats always get one, JPEG
+/// only when asked for, and formats rendered by Figma never do
+fn resolve_quality(format: &str, quality: Option<u8>) -> Option<u8> {
+    match format {
+        "webp" | "avif" => Some(quality.unwrap_or(transcode::DEFAULT_QUALITY)),
+        "jpg" => quality,
+        _ => None,
     }
 }
 
@@ -943,9 +1261,9 @@ fn next_batch_size_for_mode(
     current: usize,
     saw_rate_limit: bool,
     filled_batch: bool,
-    options: &ResolvedFileOptions,
+    low_rate: bool,
 ) -> usize {
-    if !options.low_rate {
+    if !low_rate {
         return next_batch_size(current, saw_rate_limit, filled_batch);
     }
 
@@ -958,8 +1276,8 @@ fn next_batch_size_for_mode(
     current
 }
 
-async fn retry_after_delay_ms(client: &FigmaClient, options: &ResolvedFileOptions) -> u64 {
-    let fallback = if options.low_rate { 700 } else { 400 };
+async fn retry_after_delay_ms(client: &FigmaClient, low_rate: bool) -> u64 {
+    let fallback = if low_rate { 700 } else { 400 };
     let telemetry = client.rate_limit_telemetry().await;
     telemetry
         .retry_after
@@ -968,12 +1286,28 @@ async fn retry_after_delay_ms(client: &FigmaClient, options: &ResolvedFileOption
         .max(fallback)
 }
 
+/// `(node_id, filename)` pairs an export of these nodes writes
+fn expected_filenames(
+    node_ids: &[String],
+    custom_name: Option<&str>,
+    format: &str,
+) -> Vec<(String, String)> {
+    let use_custom_name = custom_name.is_some() && node_ids.len() == 1;
+    node_ids
+        .iter()
+        .enumerate()
+        .map(|(index, node_id)| {
+            let filename = build_filename(node_id, index, custom_name, use_custom_name, format);
+            (node_id.clone(), filename)
+        })
+        .collect()
+}
+
 fn should_skip_delta_export(
     options: &ResolvedFileOptions,
     resume_index: &ResumeIndex,
     current_file_version: Option<&str>,
-    node_ids: &[String],
-    custom_name: Option<&str>,
+    expected_files: &[(String, String)],
 ) -> bool {
     if !(options.delta && options.resume) {
         return false;
@@ -987,20 +1321,12 @@ fn should_skip_delta_export(
         return false;
     }
 
-    let use_custom_name = custom_name.is_some() && node_ids.len() == 1;
-    for (index, node_id) in node_ids.iter().enumerate() {
-        let filename = build_filename(
-            node_id,
-            index,
-            custom_name,
-            use_custom_name,
-            &options.format,
-        );
-        let output_path = options.output.join(&filename);
+    for (node_id, filename) in expected_files {
+        let output_path = options.output.join(filename);
         if !output_path.exists() {
             return false;
         }
-        let Some(entry) = resume_index.files.get(&filename) else {
+        let Some(entry) = resume_index.files.get(filename) else {
             return false;
         };
         if entry.node_id != *node_id {
@@ -1023,7 +1349,7 @@ fn format_download_progress(downloaded: usize, total: usize) -> String {
 }
 
 fn should_emit_download_status(downloaded: usize, total: usize) -> bool {
-    downloaded == total || downloaded % DOWNLOAD_STATUS_INTERVAL == 0
+    downloaded == total || downloaded.is_multiple_of(DOWNLOAD_STATUS_INTERVAL)
 }
 
 fn is_rate_limit_message(msg: &str) -> bool {
@@ -1192,7 +1518,12 @@ fn collect_node_context(
     }
 }
 
-async fn batch_export(client: &FigmaClient, manifest_path: &Path, config: &Config) -> Result<()> {
+async fn batch_export(
+    client: &FigmaClient,
+    manifest_path: &Path,
+    config: &Config,
+    optimize_svg: Option<SvgOptimizeOptions>,
+) -> Result<()> {
     let content = fs::read_to_string(manifest_path)?;
     let manifest: BatchManifest = toml::from_str(&content)?;
 
@@ -1228,8 +1559,12 @@ async fn batch_export(client: &FigmaClient, manifest_path: &Path, config: &Confi
             anyhow::bail!("Scale must be between 1 and 4");
         }
 
+        let quality = resolve_quality(&format, export.quality);
         let options = ResolvedFileOptions {
+            optimize_svg: optimize_svg.clone().filter(|_| format == "svg"),
+            use_export_settings: false,
             format,
+            quality,
             scale,
             output,
             llm_pack: false,
@@ -1257,6 +1592,11 @@ async fn batch_export(client: &FigmaClient, manifest_path: &Path, config: &Confi
 }
 
 /// Export for specific platform with all required sizes
+///
+/// iOS exports are written as an `Assets.xcassets` catalog; SVG and PDF
+/// formats become single-scale vector imagesets there, and `colorsets` adds the
+/// file's color styles. Other platforms and formats export PNGs.
+#[allow(clippy::too_many_arguments)]
 async fn export_platform(
     client: &FigmaClient,
     file_key: &str,
@@ -1265,6 +1605,8 @@ async fn export_platform(
     output: &Path,
     custom_name: Option<&str>,
     platform: Platform,
+    format: &str,
+    colorsets: bool,
 ) -> Result<()> {
     let ids_to_export: Vec<String> = if all_frames {
         list_top_level_frame_ids(client, file_key).await?
@@ -1278,7 +1620,18 @@ async fn export_platform(
         anyhow::bail!("No frames found to export");
     }
 
-    let scales: Vec<(f32, &str)> = match platform {
+    let vector = matches!(platform, Platform::Ios) && matches!(format, "svg" | "pdf");
+    let vector_drawables = matches!(platform, Platform::Android) && format == "svg";
+    let export_format = if vector { format } else { "png" };
+    if format != export_format && !vector_drawables {
+        output::print_warning(&format!(
+            "--format {} is not used for this platform; exporting PNG",
+            format
+        ));
+    }
+
+    let mut scales: Vec<(f32, &str)> = match platform {
+        Platform::Ios if vector => vec![(1.0, "")],
         Platform::Ios => vec![(1.0, ""), (2.0, "@2x"), (3.0, "@3x")],
         Platform::Android => vec![
             (1.0, "mdpi"),
@@ -1296,27 +1649,48 @@ async fn export_platform(
         Platform::Web => "Web",
     };
 
+    let sizes = if vector_drawables {
+        "vector drawables".to_string()
+    } else {
+        format!("{} sizes", scales.len())
+    };
     output::print_status(
         &format!(
-            "Exporting {} node(s) for {} ({} sizes)...",
+            "Exporting {} node(s) for {} ({})...",
             ids_to_export.len(),
             platform_name,
-            scales.len()
+            sizes
         )
         .bold()
         .to_string(),
     );
 
+    // Nodes exported as raster images; vector drawables that fail to convert fall back
+    let mut raster_ids = ids_to_export.clone();
+    if vector_drawables {
+        raster_ids =
+            export_vector_drawables(client, file_key, &ids_to_export, output, custom_name).await?;
+        if raster_ids.is_empty() {
+            scales.clear();
+        }
+    }
+
+    let mut catalog = output.join(xcassets::CATALOG_NAME);
     match platform {
+        Platform::Ios => {
+            catalog = xcassets::create_catalog(output)?;
+        }
         Platform::Android => {
             for (_, suffix) in &scales {
                 fs::create_dir_all(output.join(format!("drawable-{}", suffix)))?;
             }
         }
-        _ => {
+        Platform::Web => {
             fs::create_dir_all(output)?;
         }
     }
+    // Images written per imageset, as (scale, filename)
+    let mut image_sets: BTreeMap<String, Vec<(u32, String)>> = BTreeMap::new();
 
     for (scale, suffix) in &scales {
         output::print_status(&format!(
@@ -1326,7 +1700,7 @@ async fn export_platform(
         ));
 
         let images = client
-            .export_images(file_key, &ids_to_export, "png", *scale)
+            .export_images(file_key, &raster_ids, export_format, *scale)
             .await?;
 
         if let Some(err) = &images.err {
@@ -1342,16 +1716,18 @@ async fn export_platform(
 
                 let filepath = match platform {
                     Platform::Ios => {
-                        let filename = if suffix.is_empty() {
-                            format!("{}.png", base_name)
-                        } else {
-                            format!("{}{}.png", base_name, suffix)
-                        };
-                        output.join(filename)
+                        let filename = format!("{}{}.{}", base_name, suffix, export_format);
+                        let dir = xcassets::image_set_dir(&catalog, base_name);
+                        fs::create_dir_all(&dir)?;
+                        image_sets
+                            .entry(base_name.to_string())
+                            .or_default()
+                            .push((*scale as u32, filename.clone()));
+                        dir.join(filename)
                     }
                     Platform::Android => {
                         let dir = output.join(format!("drawable-{}", suffix));
-                        dir.join(format!("{}.png", base_name))
+                        dir.join(format!("{}.png", android_resource_name(base_name)))
                     }
                     Platform::Web => {
                         let filename = if suffix.is_empty() {
@@ -1370,19 +1746,51 @@ async fn export_platform(
         tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
     }
 
-    output::print_success(&format!("Exported to {}", output.display()));
+    if matches!(platform, Platform::Ios) {
+        for (name, images) in &image_sets {
+            let contents = if vector {
+                xcassets::vector_image_set_contents(&images[0].1)
+            } else {
+                xcassets::image_set_contents(images)
+            };
+            xcassets::write_image_set(&xcassets::image_set_dir(&catalog, name), &contents)?;
+        }
+        output::print_status(&format!("  {} imageset(s)", image_sets.len()));
+        // Colorsets are extra: a failure here must not fail the image export
+        if colorsets {
+            let written = match client.get_file(file_key).await {
+                Ok(file) => {
+                    let colors = extract_design_tokens(&file).colors;
+                    xcassets::write_color_sets(&catalog, &colors)
+                }
+                Err(err) => Err(err),
+            };
+            match written {
+                Ok(written) => output::print_status(&format!("  {} colorset(s)", written)),
+                Err(err) => output::print_warning(&format!("Could not write colorsets: {}", err)),
+            }
+        }
+    }
+
+    let exported_to = match platform {
+        Platform::Ios => catalog.as_path(),
+        _ => output,
+    };
+    output::print_success(&format!("Exported to {}", exported_to.display()));
 
     match platform {
         Platform::Ios => {
             output::print_status("");
             output::print_status(&"iOS Usage:".bold().to_string());
-            output::print_status("  Add images to Assets.xcassets in Xcode");
-            output::print_status("  Use: Image(\"name\") or UIImage(named: \"name\")");
+            output::print_status("  Add Assets.xcassets to your Xcode project");
+            output::print_status(
+                "  Use: Image(\"name\"), Color(\"name\") or UIImage(named: \"name\")",
+            );
         }
         Platform::Android => {
             output::print_status("");
             output::print_status(&"Android Usage:".bold().to_string());
-            output::print_status("  Copy drawable-* folders to app/src/main/res/");
+            output::print_status("  Copy drawable* folders to app/src/main/res/");
             output::print_status("  Use: @drawable/name or R.drawable.name");
         }
         Platform::Web => {
@@ -1396,6 +1804,48 @@ async fn export_platform(
     Ok(())
 }
 
+/// Export nodes as SVG and write them as VectorDrawables under `drawable/`
+///
+/// Returns the nodes that could not be converted, to be exported as PNG densities.
+async fn export_vector_drawables(
+    client: &FigmaClient,
+    file_key: &str,
+    node_ids: &[String],
+    output: &Path,
+    custom_name: Option<&str>,
+) -> Result<Vec<String>> {
+    let images = client.export_images(file_key, node_ids, "svg", 1.0).await?;
+    if let Some(err) = &images.err {
+        output::print_warning(&format!("API Error: {}", err));
+        return Ok(node_ids.to_vec());
+    }
+
+    let dir = output.join("drawable");
+    fs::create_dir_all(&dir)?;
+    let mut fallback = Vec::new();
+    for (node_id, url) in images.images {
+        let default_name = node_id.replace(':', "-");
+        let name = android_resource_name(custom_name.unwrap_or(&default_name));
+        let Some(url) = url else {
+            output::print_warning(&format!(
+                "{}: no SVG export; falling back to PNG densities",
+                name
+            ));
+            fallback.push(node_id);
+            continue;
+        };
+        let bytes = client.download_image(&url).await?;
+        match svg_to_vector_drawable(&String::from_utf8_lossy(&bytes)) {
+            Ok(xml) => fs::write(dir.join(format!("{}.xml", name)), xml)?,
+            Err(err) => {
+                output::print_warning(&format!("{}: {}; falling back to PNG densities", name, err));
+                fallback.push(node_id);
+            }
+        }
+    }
+    Ok(fallback)
+}
+
 #[derive(serde::Deserialize)]
 struct BatchManifest {
     exports: Vec<ExportItem>,
@@ -1411,6 +1861,8 @@ struct ExportItem {
     #[serde(default)]
     format: Option<String>,
     #[serde(default)]
+    quality: Option<u8>,
+    #[serde(default)]
     scale: Option<f32>,
     #[serde(default)]
     output: Option<String>,
@@ -1477,11 +1929,14 @@ mod tests {
             None,
             None,
             None,
+            None,
             false,
             "manifest.json".to_string(),
             false,
             false,
             Some(ExportProfile::PixelPerfect),
+            None,
+            false,
             "abc123".to_string(),
             false,
         )
@@ -1501,11 +1956,14 @@ mod tests {
             None,
             None,
             None,
+            None,
             false,
             "manifest.json".to_string(),
             false,
             false,
             Some(ExportProfile::LowRate),
+            None,
+            false,
             "abc123".to_string(),
             true,
         )
@@ -1518,21 +1976,58 @@ mod tests {
         assert!(options.low_rate);
     }
 
+    #[test]
+    fn web_profile_transcodes_to_webp_with_default_quality() {
+        let config = Config::default();
+        let options = resolve_file_options(
+            &config,
+            None,
+            None,
+            None,
+            None,
+            false,
+            "manifest.json".to_string(),
+            false,
+            false,
+            Some(ExportProfile::Web),
+            None,
+            false,
+            "abc123".to_string(),
+            false,
+        )
+        .expect("options should resolve");
+
+        assert_eq!(options.format, "webp");
+        assert_eq!(options.quality, Some(transcode::DEFAULT_QUALITY));
+        assert_eq!(options.scale, 2.0);
+        assert!(options.resume);
+
+        assert_eq!(resolve_quality("jpg", None), None);
+        assert_eq!(resolve_quality("jpg", Some(60)), Some(60));
+        assert_eq!(resolve_quality("png", Some(60)), None);
+    }
+
     #[test]
     fn delta_skip_requires_matching_version_and_outputs() {
-        let mut index = ResumeIndex::default();
-        index.file_version = Some("v1".to_string());
+        let mut index = ResumeIndex {
+            file_version: Some("v1".to_string()),
+            ..Default::default()
+        };
         index.files.insert(
             "1-2.png".to_string(),
             ResumeIndexEntry {
                 node_id: "1:2".to_string(),
                 content_hash: "abc".to_string(),
                 updated_at: "2026-03-02T00:00:00Z".to_string(),
+                source_hash: None,
             },
         );
 
         let options = ResolvedFileOptions {
             format: "png".to_string(),
+            quality: None,
+            optimize_svg: None,
+            use_export_settings: false,
             scale: 1.0,
             output: PathBuf::from("."),
             llm_pack: false,
@@ -1549,8 +2044,7 @@ mod tests {
             &options,
             &index,
             Some("v1"),
-            &[String::from("1:2")],
-            None
+            &expected_filenames(&[String::from("1:2")], None, "png"),
         ));
     }
 
@@ -0,0 +1,324 @@
+//! Designer-defined export settings for `--use-export-settings`
+//!
+//! Nodes carry the exports configured in Figma's Export panel: a format, a
+//! suffix and a SCALE, WIDTH or HEIGHT constraint. Each setting becomes an
+//! export job named `<node name><suffix>.<format>`, the way Figma names its own
+//! exports (slashes in node names become folders). Jobs are grouped into one
+//! images API batch per distinct format and scale; WIDTH and HEIGHT
+//! constraints become the scale that renders the node's bounding box at that
+//! size.
+
+use crate::api::types::{Document, ExportSetting, Node};
+use crate::output;
+use std::collections::HashSet;
+
+/// Scale range accepted by the images API
+const MIN_SCALE: f32 = 0.01;
+const MAX_SCALE: f32 = 4.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportJob {
+    pub node_id: String,
+    /// Lowercase API format: png, jpg, svg or pdf
+    pub format: String,
+    pub scale: f32,
+    /// Designer suffix, e.g. `@2x`
+    pub suffix: String,
+    /// Path relative to the output directory
+    pub filename: String,
+}
+
+/// Jobs sharing one images API request
+#[derive(Debug)]
+pub struct ExportBatch<'a> {
+    pub format: String,
+    pub scale: f32,
+    pub jobs: Vec<&'a ExportJob>,
+}
+
+impl ExportBatch<'_> {
+    /// Node IDs to request, without duplicates
+    pub fn node_ids(&self) -> Vec<String> {
+        let mut seen = HashSet::new();
+        self.jobs
+            .iter()
+            .filter(|job| seen.insert(job.node_id.as_str()))
+            .map(|job| job.node_id.clone())
+            .collect()
+    }
+}
+
+/// Export jobs for the selected nodes and everything inside them, in document order
+///
+/// Filenames that collide get a numeric suffix.
+pub fn collect_export_jobs(document: &Document, node_ids: &[String]) -> Vec<ExportJob> {
+    let selected: HashSet<&str> = node_ids.iter().map(String::as_str).collect();
+    let mut jobs = Vec::new();
+    for page in document.children.as_deref().unwrap_or_default() {
+        collect(page, &selected, false, &mut jobs);
+    }
+    unique_filenames(&mut jobs);
+    jobs
+}
+
+/// Export jobs for `node` and everything inside it, with the node's own files named after `stem`
+///
+/// Used by `sync`, where the manifest output path names the selected node.
+pub fn subtree_export_jobs(node: &Node, stem: &str) -> Vec<ExportJob> {
+    let mut jobs = Vec::new();
+    collect(node, &HashSet::new(), true, &mut jobs);
+    for job in jobs.iter_mut().filter(|job| job.node_id == node.id) {
+        job.filename = export_filename(stem, &job.suffix, &job.format);
+    }
+    unique_filenames(&mut jobs);
+    jobs
+}
+
+fn unique_filenames(jobs: &mut [ExportJob]) {
+    let mut used = HashSet::new();
+    for job in jobs {
+        let (stem, extension) = job
+            .filename
+            .rsplit_once('.')
+            .map(|(stem, ext)| (stem.to_string(), ext.to_string()))
+            .unwrap_or_else(|| (job.filename.clone(), job.format.clone()));
+        let mut suffix = 2;
+        while !used.insert(job.filename.clone()) {
+            job.filename = format!("{}-{}.{}", stem, suffix, extension);
+            suffix += 1;
+        }
+    }
+}
+
+fn collect(node: &Node, selected: &HashSet<&str>, inside: bool, jobs: &mut Vec<ExportJob>) {
+    if node.visible == Some(false) {
+        return;
+    }
+    let inside = inside || selected.contains(node.id.as_str());
+    if inside {
+        jobs.extend(node_export_jobs(node));
+    }
+    for child in node.children.as_deref().unwrap_or_default() {
+        collect(child, selected, inside, jobs);
+    }
+}
+
+/// Export jobs for one node's own export settings
+pub fn node_export_jobs(node: &Node) -> Vec<ExportJob> {
+    node.export_settings
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .map(|setting| {
+            let format = api_format(&setting.format);
+            ExportJob {
+                node_id: node.id.clone(),
+                filename: export_filename(&node.name, &setting.suffix, &format),
+                scale: setting_scale(setting, node),
+                suffix: setting.suffix.clone(),
+                format,
+            }
+        })
+        .collect()
+}
+
+/// Group jobs into one batch per distinct format and scale, in first-seen order
+pub fn batches(jobs: &[ExportJob]) -> Vec<ExportBatch<'_>> {
+    let mut batches: Vec<ExportBatch> = Vec::new();
+    for job in jobs {
+        match batches
+            .iter_mut()
+            .find(|batch| batch.format == job.format && batch.scale == job.scale)
+        {
+            Some(batch) => batch.jobs.push(job),
+            None => batches.push(ExportBatch {
+                format: job.format.clone(),
+                scale: job.scale,
+                jobs: vec![job],
+            }),
+        }
+    }
+    batches
+}
+
+fn api_format(format: &str) -> String {
+    match format.to_ascii_lowercase().as_str() {
+        "jpeg" => "jpg".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Scale for a setting; WIDTH and HEIGHT are relative to the node's bounding box
+///
+/// Vector formats ignore the constraint and export at 1x, as Figma does. Scales
+/// outside the images API range are clamped with a warning, since the file then
+/// comes out at a different size than the designer asked for.
+fn setting_scale(setting: &ExportSetting, node: &Node) -> f32 {
+    if matches!(api_format(&setting.format).as_str(), "svg" | "pdf") {
+        return 1.0;
+    }
+    let value = setting.constraint.value;
+    let size = node
+        .absolute_bounding_box
+        .as_ref()
+        .map(|bb| (bb.width, bb.height));
+    let scale = match (setting.constraint.constraint_type.as_str(), size) {
+        ("WIDTH", Some((width, _))) if width > 0.0 => value / width,
+        ("HEIGHT", Some((_, height))) if height > 0.0 => value / height,
+        ("SCALE", _) if value > 0.0 => value,
+        _ => 1.0,
+    };
+    // Rounded so settings that differ only by float noise share a batch
+    let scale = (scale * 1000.0).round() / 1000.0;
+    let clamped = scale.clamp(MIN_SCALE as f64, MAX_SCALE as f64);
+    if clamped != scale {
+        output::print_warning(&format!(
+            "{}: {} {} needs a {}x export, outside the {}x-{}x the API allows; exporting at {}x",
+            node.name,
+            setting.constraint.constraint_type,
+            setting.constraint.value,
+            scale,
+            MIN_SCALE,
+            MAX_SCALE,
+            clamped
+        ));
+    }
+    clamped as f32
+}
+
+/// `<node name><suffix>.<format>`, with each `/`-separated part of the name as a folder
+pub fn export_filename(node_name: &str, suffix: &str, format: &str) -> String {
+    let parts: Vec<String> = node_name
+        .split('/')
+        .map(|part| sanitize_path_part(part.trim()))
+        .filter(|part| !part.is_empty() && part != "." && part != "..")
+        .collect();
+    let stem = if parts.is_empty() {
+        "export".to_string()
+    } else {
+        parts.join("/")
+    };
+    format!("{}{}.{}", stem, sanitize_path_part(suffix), format)
+}
+
+fn sanitize_path_part(part: &str) -> String {
+    part.chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '-',
+            c if c.is_control() => '-',
+            c => c,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document() -> Document {
+        serde_json::from_value(serde_json::json!({
+            "id": "0:0",
+            "name": "Document",
+            "type": "DOCUMENT",
+            "children": [{
+                "id": "0:1",
+                "name": "Page",
+                "type": "CANVAS",
+                "children": [{
+                    "id": "1:1",
+                    "name": "Icons/Close",
+                    "type": "FRAME",
+                    "absoluteBoundingBox": { "x": 0, "y": 0, "width": 24, "height": 12 },
+                    "exportSettings": [
+                        { "suffix": "", "format": "PNG", "constraint": { "type": "SCALE", "value": 1 } },
+                        { "suffix": "@2x", "format": "PNG", "constraint": { "type": "SCALE", "value": 2 } },
+                        { "suffix": "", "format": "SVG", "constraint": { "type": "SCALE", "value": 3 } }
+                    ],
+                    "children": [{
+                        "id": "1:2",
+                        "name": "Glyph",
+                        "type": "VECTOR",
+                        "absoluteBoundingBox": { "x": 0, "y": 0, "width": 24, "height": 12 },
+                        "exportSettings": [
+                            { "suffix": "", "format": "PNG", "constraint": { "type": "WIDTH", "value": 48 } }
+                        ]
+                    }]
+                }, {
+                    "id": "1:3",
+                    "name": "Unselected",
+                    "type": "FRAME",
+                    "exportSettings": [
+                        { "suffix": "", "format": "PNG", "constraint": { "type": "SCALE", "value": 1 } }
+                    ]
+                }]
+            }]
+        }))
+        .expect("document should deserialize")
+    }
+
+    #[test]
+    fn collects_settings_from_selected_subtrees() {
+        let jobs = collect_export_jobs(&document(), &["1:1".to_string()]);
+        let files: Vec<(&str, &str, f32)> = jobs
+            .iter()
+            .map(|job| (job.filename.as_str(), job.format.as_str(), job.scale))
+            .collect();
+        assert_eq!(
+            files,
+            vec![
+                ("Icons/Close.png", "png", 1.0),
+                ("Icons/Close@2x.png", "png", 2.0),
+                ("Icons/Close.svg", "svg", 1.0),
+                ("Glyph.png", "png", 2.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn batches_group_by_format_and_scale() {
+        let jobs = collect_export_jobs(&document(), &["1:1".to_string()]);
+        let batches = batches(&jobs);
+        let keys: Vec<(&str, f32, Vec<String>)> = batches
+            .iter()
+            .map(|batch| (batch.format.as_str(), batch.scale, batch.node_ids()))
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                ("png", 1.0, vec!["1:1".to_string()]),
+                ("png", 2.0, vec!["1:1".to_string(), "1:2".to_string()]),
+                ("svg", 1.0, vec!["1:1".to_string()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn filenames_are_safe_and_unique() {
+        assert_eq!(export_filename("a/../b", "", "png"), "a/b.png");
+        assert_eq!(export_filename("  ", "-dark", "svg"), "export-dark.svg");
+        assert_eq!(export_filename("Home: v2", "@3x", "jpg"), "Home- v2@3x.jpg");
+
+        let mut document = document();
+        let page = &mut document.children.as_mut().unwrap()[0];
+        page.children.as_mut().unwrap()[1].name = "Glyph".to_string();
+        let jobs = collect_export_jobs(&document, &["1:2".to_string(), "1:3".to_string()]);
+        let names: Vec<&str> = jobs.iter().map(|job| job.filename.as_str()).collect();
+        assert_eq!(names, vec!["Glyph.png", "Glyph-2.png"]);
+    }
+
+    #[test]
+    fn subtree_jobs_name_the_root_after_the_stem() {
+        let document = document();
+        let frame = &document.children.as_ref().unwrap()[0]
+            .children
+            .as_ref()
+            .unwrap()[0];
+        let jobs = subtree_export_jobs(frame, "close");
+        let names: Vec<&str> = jobs.iter().map(|job| job.filename.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["close.png", "close@2x.png", "close.svg", "Glyph.png"]
+        );
+        assert_eq!(batches(&jobs).len(), 3);
+    }
+}
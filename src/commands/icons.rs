@@ -0,0 +1,803 @@
+//! Icon sprite and typed manifest generation for `export icons`
+//!
+//! Every COMPONENT under a page or frame is exported as SVG and written to
+//! `svg/<name>.svg`. The icons are then combined into one SVG sprite of
+//! `<symbol>` elements (ids inside each symbol are prefixed with the icon
+//! name so clip paths and gradients cannot collide), plus TypeScript, Swift
+//! and Kotlin manifests of the icon names.
+//!
+//! Icons whose node subtree is unchanged since the last run are read back
+//! from disk instead of being exported and downloaded again.
+
+use crate::api::types::{File, Node};
+use crate::api::FigmaClient;
+use crate::cli::IconManifest;
+use crate::commands::export::{
+    download_assets, hash_bytes, hash_existing_file, load_resume_index, resolve_export_urls,
+    save_resume_index, ExportTelemetry, PlannedAsset, ResumeIndexEntry, RESUME_INDEX_FILE,
+};
+use crate::commands::svg_optimize::{optimize_svg_bytes, SvgOptimizeOptions};
+use crate::output;
+use anyhow::{anyhow, bail, Result};
+use colored::Colorize;
+use roxmltree::Document;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+use std::time::Instant;
+
+/// Folder inside the output directory holding one SVG per icon
+const SVG_DIR: &str = "svg";
+
+/// Root attributes of an exported SVG that do not carry over to a `<symbol>`
+const DROPPED_ROOT_ATTRIBUTES: &[&str] = &["width", "height", "viewBox", "x", "y", "version"];
+
+/// Swift keywords that must be backtick-escaped to name an enum case
+const SWIFT_KEYWORDS: &[&str] = &[
+    "Any",
+    "Protocol",
+    "Self",
+    "Type",
+    "as",
+    "associatedtype",
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "continue",
+    "default",
+    "defer",
+    "deinit",
+    "do",
+    "else",
+    "enum",
+    "extension",
+    "fallthrough",
+    "false",
+    "fileprivate",
+    "for",
+    "func",
+    "guard",
+    "if",
+    "import",
+    "in",
+    "init",
+    "inout",
+    "internal",
+    "is",
+    "let",
+    "nil",
+    "open",
+    "operator",
+    "private",
+    "precedencegroup",
+    "protocol",
+    "public",
+    "repeat",
+    "rethrows",
+    "return",
+    "self",
+    "static",
+    "struct",
+    "subscript",
+    "super",
+    "switch",
+    "throw",
+    "throws",
+    "true",
+    "try",
+    "typealias",
+    "var",
+    "where",
+    "while",
+];
+
+#[derive(Debug, Clone)]
+pub struct IconExportOptions {
+    pub output: PathBuf,
+    pub sprite_name: String,
+    pub manifests: Vec<IconManifest>,
+    pub resume: bool,
+    pub delta: bool,
+    pub optimize_svg: Option<SvgOptimizeOptions>,
+}
+
+/// A component selected for the sprite
+#[derive(Debug, Clone, PartialEq)]
+pub struct IconComponent {
+    pub node_id: String,
+    /// Symbol id and file stem: lowercase kebab-case, unique within the set
+    pub name: String,
+    /// Hash of the component subtree, used to skip unchanged icons
+    pub source_hash: String,
+}
+
+#[derive(Debug, Serialize)]
+struct IconsJsonSummary {
+    file_key: String,
+    output_dir: String,
+    sprite: String,
+    manifests: Vec<String>,
+    icon_count: usize,
+    downloaded: usize,
+    reused: usize,
+    telemetry: ExportTelemetry,
+}
+
+/// Export every component under a page or frame as an SVG sprite and manifests
+pub async fn export_icons(
+    client: &FigmaClient,
+    file_key: &str,
+    page: Option<&str>,
+    node_id: Option<&str>,
+    options: &IconExportOptions,
+) -> Result<()> {
+    let start = Instant::now();
+    let mut telemetry = ExportTelemetry::default();
+
+    output::print_status("Finding icon components...");
+    telemetry.api_calls = telemetry.api_calls.saturating_add(1);
+    let file = client.get_file(file_key).await?;
+    let root = find_icon_root(&file, page, node_id)?;
+    let icons = collect_icons(root, options.optimize_svg.as_ref());
+    if icons.is_empty() {
+        bail!("No components found under '{}'", root.name);
+    }
+
+    let svg_dir = options.output.join(SVG_DIR);
+    fs::create_dir_all(&svg_dir)?;
+    let resume_index_path = options.output.join(RESUME_INDEX_FILE);
+    let mut resume_index = if options.resume || options.delta {
+        load_resume_index(&resume_index_path)?
+    } else {
+        Default::default()
+    };
+
+    // Icons unchanged since the last run are read back instead of re-exported
+    let mut svgs: HashMap<String, Vec<u8>> = HashMap::new();
+    let mut to_export = Vec::new();
+    for icon in &icons {
+        let filename = icon_filename(&icon.name);
+        let path = options.output.join(&filename);
+        let unchanged = options.delta
+            && resume_index.files.get(&filename).is_some_and(|entry| {
+                entry.node_id == icon.node_id
+                    && entry.source_hash.as_deref() == Some(icon.source_hash.as_str())
+            });
+        match unchanged.then(|| fs::read(&path).ok()).flatten() {
+            Some(bytes) => {
+                svgs.insert(icon.node_id.clone(), bytes);
+            }
+            None => to_export.push(icon),
+        }
+    }
+    let reused = svgs.len();
+
+    output::print_status(
+        &format!(
+            "Exporting {} icon(s) from '{}' to {} ({} unchanged)...",
+            icons.len(),
+            root.name,
+            options.output.display(),
+            reused
+        )
+        .bold()
+        .to_string(),
+    );
+
+    let mut downloaded = 0usize;
+    if !to_export.is_empty() {
+        let ids: Vec<String> = to_export.iter().map(|icon| icon.node_id.clone()).collect();
+        let urls =
+            resolve_export_urls(client, file_key, &ids, "svg", 1.0, false, &mut telemetry).await?;
+        let planned: Vec<PlannedAsset> = to_export
+            .iter()
+            .enumerate()
+            .filter_map(|(order, icon)| {
+                let url = urls.get(&icon.node_id).cloned().flatten();
+                if url.is_none() {
+                    output::print_warning(&format!("{}: no SVG export", icon.name));
+                }
+                url.map(|image_url| PlannedAsset {
+                    order,
+                    node_id: icon.node_id.clone(),
+                    image_url,
+                    filename: icon_filename(&icon.name),
+                    source_format: "svg".to_string(),
+                })
+            })
+            .collect();
+
+        for asset in download_assets(client, planned, false, "svg", None).await? {
+            let mut bytes = asset.bytes;
+            if let Some(svg_options) = &options.optimize_svg {
+                match optimize_svg_bytes(&bytes, svg_options) {
+                    Ok(optimized) => {
+                        telemetry.svg_optimized = telemetry.svg_optimized.saturating_add(1);
+                        telemetry.svg_bytes_saved = telemetry
+                            .svg_bytes_saved
+                            .saturating_add(bytes.len().saturating_sub(optimized.len()) as u64);
+                        bytes = optimized;
+                    }
+                    Err(err) => output::print_warning(&format!(
+                        "Could not optimize {}: {}",
+                        asset.filename, err
+                    )),
+                }
+            }
+            svgs.insert(asset.node_id, bytes);
+            downloaded = downloaded.saturating_add(1);
+        }
+        telemetry.download_requests = downloaded as u64;
+        telemetry.api_calls = telemetry.api_calls.saturating_add(downloaded as u64);
+    }
+
+    let mut symbols = Vec::new();
+    let mut names = Vec::new();
+    for icon in &icons {
+        let Some(bytes) = svgs.get(&icon.node_id) else {
+            continue;
+        };
+        let filename = icon_filename(&icon.name);
+        let path = options.output.join(&filename);
+        let content_hash = hash_bytes(bytes);
+        if options.resume && hash_existing_file(&path)?.as_deref() == Some(content_hash.as_str()) {
+            telemetry.skipped_writes = telemetry.skipped_writes.saturating_add(1);
+        } else {
+            fs::write(&path, bytes)?;
+        }
+        resume_index.files.insert(
+            filename,
+            ResumeIndexEntry {
+                node_id: icon.node_id.clone(),
+                content_hash,
+                updated_at: chrono::Utc::now().to_rfc3339(),
+                source_hash: Some(icon.source_hash.clone()),
+            },
+        );
+
+        match svg_to_symbol(&String::from_utf8_lossy(bytes), &icon.name) {
+            Ok(symbol) => {
+                symbols.push(symbol);
+                names.push(icon.name.clone());
+            }
+            Err(err) => {
+                output::print_warning(&format!("{}: {}; left out of the sprite", icon.name, err))
+            }
+        }
+    }
+
+    if options.resume || options.delta {
+        resume_index.file_version = Some(file.version.clone());
+        save_resume_index(&resume_index_path, &resume_index)?;
+    }
+
+    let sprite_path = options.output.join(&options.sprite_name);
+    fs::write(&sprite_path, build_sprite(&symbols))?;
+    let mut manifest_paths = Vec::new();
+    for manifest in &options.manifests {
+        let (filename, content) = match manifest {
+            IconManifest::Ts => ("icons.ts", typescript_manifest(&names)),
+            IconManifest::Swift => ("Icons.swift", swift_manifest(&names)),
+            IconManifest::Kotlin => ("Icons.kt", kotlin_manifest(&names)),
+        };
+        let path = options.output.join(filename);
+        fs::write(&path, content)?;
+        manifest_paths.push(path.display().to_string());
+    }
+
+    output::print_status(&format!(
+        "Sprite with {} symbol(s): downloaded {}, reused {}, skipped {} unchanged write(s)",
+        names.len(),
+        downloaded,
+        reused,
+        telemetry.skipped_writes
+    ));
+    if telemetry.svg_optimized > 0 {
+        output::print_status(&format!(
+            "Optimized {} SVG(s), saved {} bytes",
+            telemetry.svg_optimized, telemetry.svg_bytes_saved
+        ));
+    }
+
+    telemetry.elapsed_ms = start.elapsed().as_millis() as u64;
+    telemetry.rate_limits = Some(client.rate_limit_telemetry().await);
+    if output::format() == crate::output::OutputFormat::Json {
+        output::print_json(&IconsJsonSummary {
+            file_key: file_key.to_string(),
+            output_dir: options.output.display().to_string(),
+            sprite: sprite_path.display().to_string(),
+            manifests: manifest_paths,
+            icon_count: names.len(),
+            downloaded,
+            reused,
+            telemetry,
+        })?;
+    }
+
+    output::print_success(&format!("Sprite written to {}", sprite_path.display()));
+    Ok(())
+}
+
+/// The node icons are collected from: a frame by node id, or a page by name
+pub fn find_icon_root<'a>(
+    file: &'a File,
+    page: Option<&str>,
+    node_id: Option<&str>,
+) -> Result<&'a Node> {
+    let pages = file.document.children.as_deref().unwrap_or_default();
+    if let Some(node_id) = node_id {
+        return pages
+            .iter()
+            .find_map(|page| find_node(page, node_id))
+            .ok_or_else(|| anyhow!("Node {} not found in file", node_id));
+    }
+    let Some(page) = page else {
+        bail!("No icon source specified. Use --page, --node, or a URL with ?node-id=");
+    };
+    pages
+        .iter()
+        .find(|candidate| candidate.name.trim().eq_ignore_ascii_case(page.trim()))
+        .ok_or_else(|| {
+            let available: Vec<&str> = pages.iter().map(|page| page.name.as_str()).collect();
+            anyhow!(
+                "Page '{}' not found (pages: {})",
+                page,
+                available.join(", ")
+            )
+        })
+}
+
+fn find_node<'a>(node: &'a Node, node_id: &str) -> Option<&'a Node> {
+    if node.id == node_id {
+        return Some(node);
+    }
+    node.children
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .find_map(|child| find_node(child, node_id))
+}
+
+/// Components under `root` with unique names, sorted by name
+///
+/// Variants of a component set are named `<set>-<variant>`. Components nested
+/// inside another component are part of that icon and are not collected.
+/// `optimize_svg` is folded into the source hash so toggling it re-exports.
+pub fn collect_icons(root: &Node, optimize_svg: Option<&SvgOptimizeOptions>) -> Vec<IconComponent> {
+    let mut found = Vec::new();
+    collect_components(root, None, &mut found);
+
+    let settings = format!("{:?}", optimize_svg);
+    let mut used = HashSet::new();
+    let mut icons: Vec<IconComponent> = found
+        .into_iter()
+        .map(|(node, name)| {
+            let base = icon_name(&name);
+            let mut name = base.clone();
+            let mut suffix = 2;
+            while !used.insert(name.clone()) {
+                name = format!("{}-{}", base, suffix);
+                suffix += 1;
+            }
+            let tree = serde_json::to_value(node).unwrap_or_default();
+            IconComponent {
+                node_id: node.id.clone(),
+                name,
+                source_hash: hash_bytes(format!("{}{}", tree, settings).as_bytes()),
+            }
+        })
+        .collect();
+    icons.sort_by(|a, b| a.name.cmp(&b.name));
+    icons
+}
+
+fn collect_components<'a>(
+    node: &'a Node,
+    component_set: Option<&str>,
+    found: &mut Vec<(&'a Node, String)>,
+) {
+    if node.visible == Some(false) {
+        return;
+    }
+    match node.node_type.as_str() {
+        "COMPONENT" => {
+            let name = match component_set {
+                Some(set) => format!("{}-{}", set, variant_name(&node.name)),
+                None => node.name.clone(),
+            };
+            found.push((node, name));
+            return;
+        }
+        "INSTANCE" => return,
+        _ => {}
+    }
+    let set = (node.node_type == "COMPONENT_SET").then_some(node.name.as_str());
+    for child in node.children.as_deref().unwrap_or_default() {
+        collect_components(child, set.or(component_set), found);
+    }
+}
+
+/// Variant values of `Size=24, Style=Filled`, joined as `24-filled`
+fn variant_name(name: &str) -> String {
+    name.split(',')
+        .map(|part| part.split_once('=').map_or(part, |(_, value)| value).trim())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Symbol id for a component name: `Icons / Arrow Left` becomes `icons-arrow-left`
+pub fn icon_name(name: &str) -> String {
+    let mut out = String::new();
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            out.push(c.to_ascii_lowercase());
+        } else if !out.is_empty() && !out.ends_with('-') {
+            out.push('-');
+        }
+    }
+    let out = out.trim_end_matches('-');
+    match out.chars().next() {
+        None => "icon".to_string(),
+        Some(first) if first.is_ascii_digit() => format!("icon-{}", out),
+        Some(_) => out.to_string(),
+    }
+}
+
+fn icon_filename(name: &str) -> String {
+    format!("{}/{}.svg", SVG_DIR, name)
+}
+
+/// Convert an exported SVG document into a `<symbol>` with the given id
+///
+/// Root presentation attributes (Figma sets `fill="none"`) move onto the
+/// symbol, and ids inside it are prefixed with the symbol id.
+pub fn svg_to_symbol(svg: &str, id: &str) -> Result<String> {
+    let document = Document::parse(svg).map_err(|err| anyhow!("invalid SVG: {}", err))?;
+    let root = document.root_element();
+    if root.tag_name().name() != "svg" {
+        bail!("invalid SVG: root element is <{}>", root.tag_name().name());
+    }
+
+    let view_box = match root.attribute("viewBox") {
+        Some(view_box) => view_box.to_string(),
+        None => {
+            let length = |name: &str| {
+                root.attribute(name)
+                    .map(|value| value.trim_end_matches("px"))
+                    .and_then(|value| value.parse::<f64>().ok())
+                    .ok_or_else(|| anyhow!("SVG has no viewBox or {}", name))
+            };
+            format!("0 0 {} {}", length("width")?, length("height")?)
+        }
+    };
+
+    let mut symbol = format!(
+        "<symbol id=\"{}\" viewBox=\"{}\"",
+        escape_attribute(id),
+        escape_attribute(&view_box)
+    );
+    for attribute in root.attributes() {
+        if attribute.namespace().is_some() || DROPPED_ROOT_ATTRIBUTES.contains(&attribute.name()) {
+            continue;
+        }
+        symbol.push_str(&format!(
+            " {}=\"{}\"",
+            attribute.name(),
+            escape_attribute(attribute.value())
+        ));
+    }
+    symbol.push('>');
+
+    let elements: Vec<_> = root.children().filter(|node| node.is_element()).collect();
+    if let (Some(first), Some(last)) = (elements.first(), elements.last()) {
+        let body = first.range().start..last.range().end;
+        let renamed: HashMap<&str, String> = root
+            .descendants()
+            .filter_map(|node| node.attribute("id"))
+            .map(|old| (old, format!("{}-{}", id, old)))
+            .collect();
+
+        // Each id, url(#…) and href value is rewritten once, in document order
+        let mut edits = Vec::new();
+        for node in root.descendants().filter(|node| node.is_element()) {
+            for attribute in node.attributes() {
+                let value = match attribute.name() {
+                    "id" => renamed.get(attribute.value()).cloned(),
+                    "href" => attribute
+                        .value()
+                        .strip_prefix('#')
+                        .and_then(|old| renamed.get(old))
+                        .map(|new| format!("#{}", new)),
+                    _ => rename_url_references(attribute.value(), &renamed),
+                };
+                if let Some(value) = value {
+                    edits.push((attribute.range_value(), value));
+                }
+            }
+        }
+        edits.sort_by_key(|(range, _)| range.start);
+
+        let mut cursor = body.start;
+        for (range, value) in edits {
+            if range.start < body.start || range.end > body.end {
+                continue;
+            }
+            symbol.push_str(&svg[cursor..range.start]);
+            symbol.push_str(&escape_attribute(&value));
+            cursor = range.end;
+        }
+        symbol.push_str(&svg[cursor..body.end]);
+    }
+    symbol.push_str("</symbol>");
+    Ok(symbol)
+}
+
+/// `value` with every `url(#old)` pointing at a renamed id, or `None` when nothing changes
+fn rename_url_references(value: &str, renamed: &HashMap<&str, String>) -> Option<String> {
+    let mut out = String::new();
+    let mut rest = value;
+    let mut changed = false;
+    while let Some(start) = rest.find("url(#") {
+        let target = &rest[start + 5..];
+        let Some(end) = target.find(')') else {
+            break;
+        };
+        out.push_str(&rest[..start + 5]);
+        match renamed.get(&target[..end]) {
+            Some(new) => {
+                out.push_str(new);
+                changed = true;
+            }
+            None => out.push_str(&target[..end]),
+        }
+        rest = &target[end..];
+    }
+    out.push_str(rest);
+    changed.then_some(out)
+}
+
+fn escape_attribute(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('"', "&quot;")
+}
+
+/// Hidden SVG sprite; use icons with `<svg><use href="icons.svg#name"/></svg>`
+pub fn build_sprite(symbols: &[String]) -> String {
+    let mut sprite = String::from(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" xmlns:xlink=\"http://www.w3.org/1999/xlink\" style=\"display:none\">\n",
+    );
+    for symbol in symbols {
+        sprite.push_str("  ");
+        sprite.push_str(symbol);
+        sprite.push('\n');
+    }
+    sprite.push_str("</svg>\n");
+    sprite
+}
+
+/// `IconName` union type and the list of all names
+pub fn typescript_manifest(names: &[String]) -> String {
+    let mut ts = String::from("export type IconName =\n");
+    if names.is_empty() {
+        ts.push_str("  never;\n");
+    }
+    for (i, name) in names.iter().enumerate() {
+        let end = if i + 1 == names.len() { ";" } else { "" };
+        ts.push_str(&format!("  | \"{}\"{}\n", name, end));
+    }
+    ts.push_str("\nexport const iconNames: readonly IconName[] = [\n");
+    for name in names {
+        ts.push_str(&format!("  \"{}\",\n", name));
+    }
+    ts.push_str("];\n");
+    ts
+}
+
+/// `Icon` enum whose raw values are the symbol ids
+///
+/// Cases named after Swift keywords (`repeat`, `switch`, …) are backtick-escaped.
+pub fn swift_manifest(names: &[String]) -> String {
+    let mut swift = String::from("enum Icon: String, CaseIterable {\n");
+    for (case, name) in identifiers(names, camel_case) {
+        if SWIFT_KEYWORDS.contains(&case.as_str()) {
+            swift.push_str(&format!("    case `{}` = \"{}\"\n", case, name));
+        } else {
+            swift.push_str(&format!("    case {} = \"{}\"\n", case, name));
+        }
+    }
+    swift.push_str("}\n");
+    swift
+}
+
+/// `Icons` object with one constant per symbol id and the list of all names
+pub fn kotlin_manifest(names: &[String]) -> String {
+    let constants = identifiers(names, |name| name.replace('-', "_").to_ascii_uppercase());
+    let mut kotlin = String::from("object Icons {\n");
+    for (constant, name) in &constants {
+        kotlin.push_str(&format!("    const val {} = \"{}\"\n", constant, name));
+    }
+    let all: Vec<&str> = constants.keys().map(String::as_str).collect();
+    kotlin.push_str(&format!("\n    val all = listOf({})\n", all.join(", ")));
+    kotlin.push_str("}\n");
+    kotlin
+}
+
+/// Language identifiers for icon names, prefixed when they would start with a digit
+///
+/// Names that convert to an identifier already taken get a numeric suffix, so
+/// `arrow-left` and `arrowLeft` become `arrowLeft` and `arrowLeft2`.
+fn identifiers(names: &[String], convert: impl Fn(&str) -> String) -> BTreeMap<String, String> {
+    let ident = |name: &str| {
+        let ident = convert(name);
+        if ident.starts_with(|c: char| c.is_ascii_digit()) {
+            format!("_{}", ident)
+        } else {
+            ident
+        }
+    };
+    let mut identifiers = BTreeMap::new();
+    for name in names {
+        let mut candidate = ident(name);
+        let mut suffix = 2;
+        while identifiers.contains_key(&candidate) {
+            candidate = ident(&format!("{}-{}", name, suffix));
+            suffix += 1;
+        }
+        identifiers.insert(candidate, name.clone());
+    }
+    identifiers
+}
+
+fn camel_case(name: &str) -> String {
+    let mut out = String::new();
+    for (i, part) in name.split('-').filter(|part| !part.is_empty()).enumerate() {
+        if i == 0 {
+            out.push_str(part);
+        } else {
+            let mut chars = part.chars();
+            if let Some(first) = chars.next() {
+                out.push(first.to_ascii_uppercase());
+                out.push_str(chars.as_str());
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(id: &str, name: &str, node_type: &str, children: Vec<Node>) -> Node {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "name": name,
+            "type": node_type,
+            "children": children,
+        }))
+        .expect("node should deserialize")
+    }
+
+    #[test]
+    fn collects_components_and_variants_with_unique_names() {
+        let page = node(
+            "0:1",
+            "Icons",
+            "CANVAS",
+            vec![
+                node(
+                    "1:1",
+                    "Navigation",
+                    "FRAME",
+                    vec![
+                        node("1:2", "Arrow / Left", "COMPONENT", vec![]),
+                        node("1:3", "arrow-left", "COMPONENT", vec![]),
+                        node("1:4", "Instance", "INSTANCE", vec![]),
+                    ],
+                ),
+                node(
+                    "2:1",
+                    "Star",
+                    "COMPONENT_SET",
+                    vec![node("2:2", "Size=24, Style=Filled", "COMPONENT", vec![])],
+                ),
+            ],
+        );
+
+        let icons = collect_icons(&page, None);
+        let names: Vec<&str> = icons.iter().map(|icon| icon.name.as_str()).collect();
+        assert_eq!(names, vec!["arrow-left", "arrow-left-2", "star-24-filled"]);
+        assert_ne!(icons[0].source_hash, icons[1].source_hash);
+
+        let optimized = collect_icons(&page, SvgOptimizeOptions::from_flags(true, false).as_ref());
+        assert_ne!(icons[0].source_hash, optimized[0].source_hash);
+    }
+
+    #[test]
+    fn icon_names_are_kebab_case_ids() {
+        assert_eq!(icon_name("Icons / Arrow Left"), "icons-arrow-left");
+        assert_eq!(icon_name("24/Close"), "icon-24-close");
+        assert_eq!(icon_name("///"), "icon");
+    }
+
+    #[test]
+    fn svg_becomes_symbol_with_prefixed_ids() {
+        let svg = r##"<svg width="24" height="24" viewBox="0 0 24 24" fill="none" xmlns="http://www.w3.org/2000/svg">
+<g clip-path="url(#clip0)"><path d="M0 0h24v24H0z" fill="#000"/></g>
+<defs><clipPath id="clip0"><rect width="24" height="24"/></clipPath></defs>
+</svg>"##;
+
+        let symbol = svg_to_symbol(svg, "close").expect("symbol should build");
+        assert!(symbol.starts_with(r#"<symbol id="close" viewBox="0 0 24 24" fill="none">"#));
+        assert!(symbol.contains(r#"clip-path="url(#close-clip0)""#));
+        assert!(symbol.contains(r#"<clipPath id="close-clip0">"#));
+        assert!(symbol.contains(r##"fill="#000""##));
+        assert!(symbol.ends_with("</defs></symbol>"));
+        assert!(!symbol.contains("width=\"24\" height=\"24\" viewBox"));
+
+        let sprite = build_sprite(&[symbol]);
+        assert!(Document::parse(&sprite).is_ok());
+    }
+
+    #[test]
+    fn manifests_list_every_icon() {
+        let names = vec!["arrow-left".to_string(), "icon-24-close".to_string()];
+
+        let ts = typescript_manifest(&names);
+        assert!(ts.contains("  | \"arrow-left\"\n  | \"icon-24-close\";\n"));
+
+        let swift = swift_manifest(&names);
+        assert!(swift.contains("    case arrowLeft = \"arrow-left\"\n"));
+        assert!(swift.contains("    case icon24Close = \"icon-24-close\"\n"));
+
+        let kotlin = kotlin_manifest(&names);
+        assert!(kotlin.contains("    const val ARROW_LEFT = \"arrow-left\"\n"));
+        assert!(kotlin.contains("    val all = listOf(ARROW_LEFT, ICON_24_CLOSE)\n"));
+    }
+
+    #[test]
+    fn swift_keywords_are_escaped() {
+        let names = vec![
+            "repeat".to_string(),
+            "switch".to_string(),
+            "star".to_string(),
+        ];
+        let swift = swift_manifest(&names);
+        assert!(swift.contains("    case `repeat` = \"repeat\"\n"));
+        assert!(swift.contains("    case `switch` = \"switch\"\n"));
+        assert!(swift.contains("    case star = \"star\"\n"));
+    }
+
+    #[test]
+    fn colliding_identifiers_are_disambiguated() {
+        let names = vec!["arrow-left".to_string(), "arrowLeft".to_string()];
+        let swift = swift_manifest(&names);
+        assert!(swift.contains("    case arrowLeft = \"arrow-left\"\n"));
+        assert!(swift.contains("    case arrowLeft2 = \"arrowLeft\"\n"));
+
+        let names = vec!["arrow-left".to_string(), "arrow_left".to_string()];
+        let kotlin = kotlin_manifest(&names);
+        assert!(kotlin.contains("    const val ARROW_LEFT = \"arrow-left\"\n"));
+        assert!(kotlin.contains("    const val ARROW_LEFT_2 = \"arrow_left\"\n"));
+    }
+
+    #[test]
+    fn symbol_ids_are_renamed_once() {
+        let svg = r##"<svg viewBox="0 0 24 24" xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink">
+<g clip-path="url(#a)" fill="url(#icon-a)"><use xlink:href="#a"/><use href="#icon-a"/></g>
+<defs><clipPath id="a"><rect width="24" height="24"/></clipPath><linearGradient id="icon-a"/></defs>
+</svg>"##;
+
+        let symbol = svg_to_symbol(svg, "icon").expect("symbol should build");
+        assert!(symbol.contains(r#"clip-path="url(#icon-a)" fill="url(#icon-icon-a)""#));
+        assert!(symbol.contains(r##"<use xlink:href="#icon-a"/><use href="#icon-icon-a"/>"##));
+        assert!(symbol.contains(r#"<clipPath id="icon-a">"#));
+        assert!(symbol.contains(r#"<linearGradient id="icon-icon-a"/>"#));
+        assert!(!symbol.contains("icon-icon-icon-a"));
+    }
+}
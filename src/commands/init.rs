@@ -6,7 +6,7 @@ use colored::Colorize;
 use std::io::{self, Write};
 
 pub async fn run(args: InitArgs) -> Result<()> {
-    let base_dir = if args.path == std::path::PathBuf::from(".") {
+    let base_dir = if args.path == std::path::Path::new(".") {
         std::env::current_dir()?
     } else {
         args.path.clone()
@@ -7,6 +7,7 @@ use crate::select;
 use anyhow::Result;
 use colored::Colorize;
 use serde::{Deserialize, Serialize};
+use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
@@ -197,11 +198,7 @@ fn coverage(map_path: &Path) -> Result<()> {
         .collect();
 
     // Coverage bar
-    let pct = if total > 0 {
-        (implemented.len() * 100) / total
-    } else {
-        0
-    };
+    let pct = (implemented.len() * 100).checked_div(total).unwrap_or(0);
     let bar_width = 30;
     let filled = (pct * bar_width) / 100;
     let bar: String = "█".repeat(filled) + &"░".repeat(bar_width - filled);
@@ -293,8 +290,8 @@ async fn update(map_path: &Path) -> Result<()> {
     let mut removed = 0;
 
     for (key, entry) in new_components {
-        if !map.components.contains_key(&key) {
-            map.components.insert(key, entry);
+        if let Entry::Vacant(slot) = map.components.entry(key) {
+            slot.insert(entry);
             added += 1;
         }
     }
@@ -302,12 +299,10 @@ async fn update(map_path: &Path) -> Result<()> {
     // Mark components that no longer exist in Figma
     for (key, entry) in &mut map.components {
         // Check if component still exists in file.components
-        if !file.components.contains_key(key) {
-            if entry.status == ComponentStatus::Implemented {
-                entry.status = ComponentStatus::NeedsUpdate;
-                entry.notes = Some("Component may have been removed from Figma".to_string());
-                removed += 1;
-            }
+        if !file.components.contains_key(key) && entry.status == ComponentStatus::Implemented {
+            entry.status = ComponentStatus::NeedsUpdate;
+            entry.notes = Some("Component may have been removed from Figma".to_string());
+            removed += 1;
         }
     }
 
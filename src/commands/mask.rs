@@ -0,0 +1,227 @@
+//! Ignore masks for `compare` and `compare-url`
+//!
+//! Regions such as clocks, avatars or carousels can be left out of a
+//! comparison with `--ignore x,y,w,h` rectangles, a `--ignore-mask` PNG (light,
+//! opaque pixels are ignored) or, in `compare-url`, `--ignore-node` IDs whose
+//! bounding boxes are converted into export pixels. Masked pixels count neither
+//! toward `diff_percent` nor toward the diff image.
+
+use crate::api::types::BoundingBox;
+use anyhow::{Context, Result};
+use image::GrayImage;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Rectangle in image pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IgnoreRect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl IgnoreRect {
+    fn contains(&self, x: u32, y: u32) -> bool {
+        x >= self.x && y >= self.y && x - self.x < self.width && y - self.y < self.height
+    }
+
+    /// Bounds of `node` inside `frame`, in pixels of an export at `scale`
+    ///
+    /// Returns `None` when the node lies entirely outside the frame.
+    pub fn from_node_bounds(node: &BoundingBox, frame: &BoundingBox, scale: f32) -> Option<Self> {
+        let scale = scale as f64;
+        let left = ((node.x - frame.x) * scale).floor().max(0.0);
+        let top = ((node.y - frame.y) * scale).floor().max(0.0);
+        let right = ((node.x + node.width - frame.x) * scale)
+            .ceil()
+            .min(frame.width * scale);
+        let bottom = ((node.y + node.height - frame.y) * scale)
+            .ceil()
+            .min(frame.height * scale);
+        (right > left && bottom > top).then_some(Self {
+            x: left as u32,
+            y: top as u32,
+            width: (right - left) as u32,
+            height: (bottom - top) as u32,
+        })
+    }
+}
+
+impl FromStr for IgnoreRect {
+    type Err = String;
+
+    /// Parses `x,y,width,height`
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<u32> = value
+            .split(',')
+            .map(|part| part.trim().parse::<u32>())
+            .collect::<Result<_, _>>()
+            .map_err(|_| format!("expected x,y,width,height in pixels, got '{}'", value))?;
+        match parts[..] {
+            [x, y, width, height] if width > 0 && height > 0 => Ok(Self {
+                x,
+                y,
+                width,
+                height,
+            }),
+            [_, _, _, _] => Err("ignore rectangle must have a non-zero size".to_string()),
+            _ => Err(format!(
+                "expected x,y,width,height in pixels, got '{}'",
+                value
+            )),
+        }
+    }
+}
+
+/// Read from `x,y,width,height` strings, as in `fgm run` manifests
+impl<'de> serde::Deserialize<'de> for IgnoreRect {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Regions left out of a comparison
+///
+/// Coordinates are in the first image's pixels; `origin` is where the compared
+/// area starts in that space, for images cropped before diffing.
+#[derive(Debug, Clone, Default)]
+pub struct IgnoreMask {
+    pub rects: Vec<IgnoreRect>,
+    pub image: Option<GrayImage>,
+    pub origin: (u32, u32),
+}
+
+impl IgnoreMask {
+    /// Mask from `--ignore` rectangles and an optional `--ignore-mask` PNG
+    pub fn load(rects: &[IgnoreRect], mask_path: Option<&Path>) -> Result<Self> {
+        let image = mask_path
+            .map(|path| {
+                image::open(path)
+                    .with_context(|| format!("Failed to open ignore mask {}", path.display()))
+                    .map(|mask| mask_pixels(&mask))
+            })
+            .transpose()?;
+        Ok(Self {
+            rects: rects.to_vec(),
+            image,
+            origin: (0, 0),
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rects.is_empty() && self.image.is_none()
+    }
+
+    /// Whether pixel `(x, y)` of the compared area is ignored
+    pub fn is_ignored(&self, x: u32, y: u32) -> bool {
+        let (x, y) = (x + self.origin.0, y + self.origin.1);
+        self.rects.iter().any(|rect| rect.contains(x, y))
+            || self.image.as_ref().is_some_and(|mask| {
+                x < mask.width() && y < mask.height() && mask.get_pixel(x, y)[0] > 0
+            })
+    }
+
+    /// Ignored flags for a `width` x `height` area, row by row; `None` when nothing is masked
+    pub fn resolve(&self, width: u32, height: u32) -> Option<Vec<bool>> {
+        if self.is_empty() {
+            return None;
+        }
+        let mut ignored = Vec::with_capacity((width * height) as usize);
+        for y in 0..height {
+            for x in 0..width {
+                ignored.push(self.is_ignored(x, y));
+            }
+        }
+        Some(ignored)
+    }
+}
+
+/// Light, opaque mask pixels become 255 and everything else 0
+fn mask_pixels(mask: &image::DynamicImage) -> GrayImage {
+    let rgba = mask.to_rgba8();
+    GrayImage::from_fn(rgba.width(), rgba.height(), |x, y| {
+        let pixel = rgba.get_pixel(x, y);
+        let luma = 0.299 * pixel[0] as f32 + 0.587 * pixel[1] as f32 + 0.114 * pixel[2] as f32;
+        let coverage = luma * pixel[3] as f32 / 255.0;
+        image::Luma([if coverage >= 128.0 { 255 } else { 0 }])
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{DynamicImage, Rgba, RgbaImage};
+
+    #[test]
+    fn parses_rectangles() {
+        assert_eq!(
+            "10, 20,30,40".parse::<IgnoreRect>(),
+            Ok(IgnoreRect {
+                x: 10,
+                y: 20,
+                width: 30,
+                height: 40
+            })
+        );
+        assert!("10,20,30".parse::<IgnoreRect>().is_err());
+        assert!("10,20,0,5".parse::<IgnoreRect>().is_err());
+        assert!("a,b,c,d".parse::<IgnoreRect>().is_err());
+
+        let rects: Vec<IgnoreRect> = serde_json::from_str(r#"["1,2,3,4"]"#).unwrap();
+        assert_eq!(rects[0].width, 3);
+        assert!(serde_json::from_str::<Vec<IgnoreRect>>(r#"["1,2,3"]"#).is_err());
+    }
+
+    #[test]
+    fn node_bounds_are_relative_to_the_frame() {
+        let frame = BoundingBox {
+            x: 100.0,
+            y: 200.0,
+            width: 50.0,
+            height: 80.0,
+        };
+        let node = BoundingBox {
+            x: 140.5,
+            y: 190.0,
+            width: 20.0,
+            height: 20.0,
+        };
+        assert_eq!(
+            IgnoreRect::from_node_bounds(&node, &frame, 2.0),
+            Some(IgnoreRect {
+                x: 81,
+                y: 0,
+                width: 19,
+                height: 20
+            })
+        );
+        let outside = BoundingBox { x: 0.0, ..node };
+        assert_eq!(IgnoreRect::from_node_bounds(&outside, &frame, 2.0), None);
+    }
+
+    #[test]
+    fn masks_combine_rectangles_and_images() {
+        let mut mask_image = RgbaImage::from_pixel(4, 4, Rgba([0, 0, 0, 255]));
+        mask_image.put_pixel(3, 3, Rgba([255, 255, 255, 255]));
+        mask_image.put_pixel(2, 3, Rgba([255, 255, 255, 0]));
+        let mask = IgnoreMask {
+            rects: vec!["0,0,2,1".parse().unwrap()],
+            image: Some(mask_pixels(&DynamicImage::ImageRgba8(mask_image))),
+            origin: (0, 0),
+        };
+        let ignored = mask.resolve(4, 4).unwrap();
+        let positions: Vec<usize> = (0..16).filter(|&i| ignored[i]).collect();
+        assert_eq!(positions, vec![0, 1, 15]);
+
+        let shifted = IgnoreMask {
+            origin: (1, 1),
+            ..mask
+        };
+        assert!(shifted.is_ignored(2, 2));
+        assert!(!shifted.is_ignored(0, 0));
+        assert!(IgnoreMask::default().resolve(4, 4).is_none());
+    }
+}
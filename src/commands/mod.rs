@@ -1,3 +1,4 @@
+pub mod audit;
 pub mod auth;
 pub mod cache;
 pub mod compare;
@@ -6,11 +7,21 @@ pub mod components;
 pub mod config;
 pub mod doctor;
 pub mod export;
+pub mod export_settings;
 pub mod files;
+pub mod icons;
 pub mod init;
 pub mod map;
+pub mod mask;
+pub mod normalize;
+pub mod perceptual;
 pub mod preview;
+pub mod regions;
 pub mod run;
 pub mod snapshot;
+pub mod svg_optimize;
 pub mod sync;
 pub mod tokens;
+pub mod transcode;
+pub mod vector_drawable;
+pub mod xcassets;
@@ -0,0 +1,273 @@
+//! Screenshot normalization for `compare-url`
+//!
+//! Device screenshots rarely line up with a Figma export: they are captured at
+//! the device's pixel ratio, include a live status bar and may be shifted by a
+//! few pixels. Before diffing, the screenshot is resampled to the export's
+//! pixel density (detected from the image widths), the `--device` insets are
+//! cropped from both images, and the screenshot is moved to the offset where
+//! its luminance best matches the export.
+
+use crate::cli::DevicePreset;
+use anyhow::Result;
+use image::imageops::FilterType;
+use image::{DynamicImage, GenericImageView, GrayImage};
+use serde::Serialize;
+
+/// Samples per offset when scoring an alignment; larger images are strided
+const ALIGN_SAMPLES: u64 = 250_000;
+
+/// Offsets must keep at least this share of the image overlapping
+const MIN_OVERLAP: f64 = 0.5;
+
+#[derive(Debug, Clone, Copy)]
+pub struct NormalizeOptions {
+    /// Scale the Figma frame was exported at
+    pub export_scale: f32,
+    pub device: Option<DevicePreset>,
+    /// Largest shift in pixels tried in each direction
+    pub align_search: u32,
+}
+
+/// What normalization did, in export pixels
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct Normalization {
+    /// Screenshot pixels per export pixel
+    pub device_ratio: f32,
+    pub resampled: bool,
+    pub crop_top: u32,
+    pub crop_bottom: u32,
+    /// Where the export's top-left corner sits in the screenshot
+    pub offset_x: i32,
+    pub offset_y: i32,
+    /// Size of the compared area
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Normalization {
+    /// Top-left corner of the compared area in the original export
+    pub fn export_origin(&self) -> (u32, u32) {
+        (
+            (-self.offset_x).max(0) as u32,
+            self.crop_top + (-self.offset_y).max(0) as u32,
+        )
+    }
+}
+
+/// Status bar and bottom inset of a device, in points
+pub fn device_insets(device: DevicePreset) -> (u32, u32) {
+    match device {
+        DevicePreset::IphoneSe => (20, 0),
+        DevicePreset::IphoneNotch => (47, 34),
+        DevicePreset::IphoneDynamicIsland => (59, 34),
+        DevicePreset::Android => (24, 48),
+        DevicePreset::AndroidGesture => (24, 24),
+    }
+}
+
+/// Screenshot pixels per export pixel, snapped to a quarter when within 1%
+pub fn detect_device_ratio(export_width: u32, screenshot_width: u32) -> f32 {
+    let ratio = screenshot_width as f32 / export_width as f32;
+    let snapped = (ratio * 4.0).round() / 4.0;
+    if snapped > 0.0 && ((ratio - snapped) / snapped).abs() < 0.01 {
+        snapped
+    } else {
+        ratio
+    }
+}
+
+/// Bring the screenshot into the export's pixel space and crop both images to the compared area
+pub fn normalize(
+    export: &DynamicImage,
+    screenshot: &DynamicImage,
+    options: &NormalizeOptions,
+) -> Result<(DynamicImage, DynamicImage, Normalization)> {
+    let (ew, eh) = export.dimensions();
+    let (sw, sh) = screenshot.dimensions();
+    if ew == 0 || eh == 0 || sw == 0 || sh == 0 {
+        anyhow::bail!("Images have zero dimensions");
+    }
+
+    let device_ratio = detect_device_ratio(ew, sw);
+    let resampled = sw != ew;
+    let mut screenshot = if resampled {
+        let height = ((sh as f32 / device_ratio).round() as u32).max(1);
+        screenshot.resize_exact(ew, height, FilterType::Lanczos3)
+    } else {
+        screenshot.clone()
+    };
+    let mut export = export.clone();
+
+    let (crop_top, crop_bottom) = options
+        .device
+        .map(device_insets)
+        .map(|(top, bottom)| {
+            let px = |points: u32| (points as f32 * options.export_scale).round() as u32;
+            (px(top), px(bottom))
+        })
+        .unwrap_or((0, 0));
+    if crop_top + crop_bottom > 0 {
+        export = crop_insets(&export, crop_top, crop_bottom)?;
+        screenshot = crop_insets(&screenshot, crop_top, crop_bottom)?;
+    }
+
+    let (offset_x, offset_y) = best_offset(
+        &export.to_luma8(),
+        &screenshot.to_luma8(),
+        options.align_search as i32,
+    );
+
+    // Overlap of the export with the shifted screenshot
+    let (ew, eh) = export.dimensions();
+    let (sw, sh) = screenshot.dimensions();
+    let x0 = (-offset_x).max(0) as u32;
+    let y0 = (-offset_y).max(0) as u32;
+    let width = (ew as i32).min(sw as i32 - offset_x) as u32 - x0;
+    let height = (eh as i32).min(sh as i32 - offset_y) as u32 - y0;
+
+    let export = export.crop_imm(x0, y0, width, height);
+    let screenshot = screenshot.crop_imm(
+        (x0 as i32 + offset_x) as u32,
+        (y0 as i32 + offset_y) as u32,
+        width,
+        height,
+    );
+
+    Ok((
+        export,
+        screenshot,
+        Normalization {
+            device_ratio,
+            resampled,
+            crop_top,
+            crop_bottom,
+            offset_x,
+            offset_y,
+            width,
+            height,
+        },
+    ))
+}
+
+fn crop_insets(image: &DynamicImage, top: u32, bottom: u32) -> Result<DynamicImage> {
+    let (width, height) = image.dimensions();
+    if top + bottom >= height {
+        anyhow::bail!(
+            "Device insets ({}px top, {}px bottom) leave nothing of a {}px tall image",
+            top,
+            bottom,
+            height
+        );
+    }
+    Ok(image.crop_imm(0, top, width, height - top - bottom))
+}
+
+/// Offset `(dx, dy)` within `±search` where `screenshot(x + dx, y + dy)` best matches `export(x, y)`
+///
+/// Scores are the mean absolute luminance difference over the overlap; ties go
+/// to the smaller shift, so identical images stay at `(0, 0)`.
+pub fn best_offset(export: &GrayImage, screenshot: &GrayImage, search: i32) -> (i32, i32) {
+    let (ew, eh) = (export.width() as i32, export.height() as i32);
+    let (sw, sh) = (screenshot.width() as i32, screenshot.height() as i32);
+    let area = (ew.min(sw) as f64) * (eh.min(sh) as f64);
+    let stride = ((area / ALIGN_SAMPLES as f64).sqrt().ceil() as i32).max(1);
+
+    let mut offsets: Vec<(i32, i32)> = (-search..=search)
+        .flat_map(|dy| (-search..=search).map(move |dx| (dx, dy)))
+        .collect();
+    offsets.sort_by_key(|(dx, dy)| dx.abs() + dy.abs());
+
+    let mut best = ((0, 0), f64::MAX);
+    for (dx, dy) in offsets {
+        let (x0, y0) = ((-dx).max(0), (-dy).max(0));
+        let (x1, y1) = (ew.min(sw - dx), eh.min(sh - dy));
+        if x1 <= x0 || y1 <= y0 || ((x1 - x0) as f64 * (y1 - y0) as f64) < area * MIN_OVERLAP {
+            continue;
+        }
+
+        let (mut total, mut samples) = (0u64, 0u64);
+        for y in (y0..y1).step_by(stride as usize) {
+            for x in (x0..x1).step_by(stride as usize) {
+                let a = export.get_pixel(x as u32, y as u32)[0];
+                let b = screenshot.get_pixel((x + dx) as u32, (y + dy) as u32)[0];
+                total += a.abs_diff(b) as u64;
+                samples += 1;
+            }
+        }
+        let score = total as f64 / samples as f64;
+        if score < best.1 {
+            best = ((dx, dy), score);
+        }
+    }
+    best.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Luma, Rgba, RgbaImage};
+
+    fn pattern(width: u32, height: u32) -> RgbaImage {
+        RgbaImage::from_fn(width, height, |x, y| {
+            let value = ((x * 37 + y * 91) % 251) as u8;
+            Rgba([value, value / 2, 255 - value, 255])
+        })
+    }
+
+    #[test]
+    fn device_ratio_snaps_to_common_densities() {
+        assert_eq!(detect_device_ratio(786, 1179), 1.5);
+        assert_eq!(detect_device_ratio(390, 1170), 3.0);
+        assert_eq!(detect_device_ratio(393, 1178), 3.0);
+        assert!((detect_device_ratio(400, 1000) - 2.5).abs() < f32::EPSILON);
+        assert!((detect_device_ratio(300, 1000) - 3.333).abs() < 0.001);
+    }
+
+    #[test]
+    fn finds_shifted_content() {
+        let export = GrayImage::from_fn(40, 30, |x, y| Luma([((x * 13 + y * 29) % 256) as u8]));
+        let screenshot = GrayImage::from_fn(40, 30, |x, y| {
+            let (sx, sy) = (x.saturating_sub(3), y.saturating_sub(2));
+            Luma([((sx * 13 + sy * 29) % 256) as u8])
+        });
+        assert_eq!(best_offset(&export, &screenshot, 5), (3, 2));
+        assert_eq!(best_offset(&export, &export, 5), (0, 0));
+        assert_eq!(best_offset(&export, &screenshot, 0), (0, 0));
+    }
+
+    #[test]
+    fn normalizes_retina_screenshot_with_device_insets() {
+        // 1x export of a 20x60pt screen and a 3x screenshot of the same pixels
+        let export = DynamicImage::ImageRgba8(pattern(20, 60));
+        let screenshot = export.resize_exact(60, 180, FilterType::Nearest);
+        let options = NormalizeOptions {
+            export_scale: 1.0,
+            device: Some(DevicePreset::IphoneSe),
+            align_search: 2,
+        };
+
+        let (a, b, normalization) = normalize(&export, &screenshot, &options).unwrap();
+        assert_eq!(a.dimensions(), (20, 40));
+        assert_eq!(b.dimensions(), (20, 40));
+        assert_eq!(
+            normalization,
+            Normalization {
+                device_ratio: 3.0,
+                resampled: true,
+                crop_top: 20,
+                crop_bottom: 0,
+                offset_x: 0,
+                offset_y: 0,
+                width: 20,
+                height: 40,
+            }
+        );
+
+        let options = NormalizeOptions {
+            export_scale: 1.0,
+            device: Some(DevicePreset::IphoneNotch),
+            align_search: 0,
+        };
+        assert!(normalize(&export, &export, &options).is_err());
+    }
+}
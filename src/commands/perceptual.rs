@@ -0,0 +1,365 @@
+//! Perceptual pixel metrics for `compare --method`
+//!
+//! - YIQ color distance and anti-aliasing detection as in pixelmatch: a pixel
+//!   whose neighbourhood is a brightness ramp, with flat siblings in both
+//!   images, is an anti-aliased edge rather than a real difference.
+//! - CIEDE2000 color distance in CIELAB (sRGB, D65).
+//! - SSIM over fixed luminance blocks, kept as a per-block score map.
+//!
+//! Translucent pixels are blended onto white first, so alpha differences
+//! show up as color differences.
+
+use image::{Pixel, Rgba, RgbaImage};
+use rayon::prelude::*;
+use serde::Serialize;
+
+/// Side of the square blocks SSIM is computed over
+pub const SSIM_BLOCK: u32 = 8;
+
+/// Largest possible YIQ delta between two colors
+const MAX_YIQ_DELTA: f64 = 35215.0;
+
+/// Color of a pixel blended onto white, as RGB in 0-255
+pub fn blend_white(pixel: &Rgba<u8>) -> [f64; 3] {
+    let alpha = pixel[3] as f64 / 255.0;
+    [0, 1, 2].map(|c| 255.0 + (pixel[c] as f64 - 255.0) * alpha)
+}
+
+fn yiq_y(rgb: [f64; 3]) -> f64 {
+    rgb[0] * 0.29889531 + rgb[1] * 0.58662247 + rgb[2] * 0.11448223
+}
+
+/// Squared YIQ distance; negative when `b` is brighter than `a`
+pub fn yiq_delta(a: [f64; 3], b: [f64; 3]) -> f64 {
+    let i = |c: [f64; 3]| c[0] * 0.59597799 - c[1] * 0.27417610 - c[2] * 0.32180189;
+    let q = |c: [f64; 3]| c[0] * 0.21147017 - c[1] * 0.52261711 + c[2] * 0.31114694;
+    let y = yiq_y(a) - yiq_y(b);
+    let i = i(a) - i(b);
+    let q = q(a) - q(b);
+    let delta = 0.5053 * y * y + 0.299 * i * i + 0.1957 * q * q;
+    if yiq_y(a) > yiq_y(b) {
+        delta
+    } else {
+        -delta
+    }
+}
+
+/// Largest YIQ delta still treated as the same color for a 0-1 threshold
+pub fn max_yiq_delta(threshold: f32) -> f64 {
+    MAX_YIQ_DELTA * (threshold as f64) * (threshold as f64)
+}
+
+/// Whether the pixel at `(x, y)` of `image` looks like an anti-aliased edge
+///
+/// `other` is the image it is compared against; both must be at least `x + 1`
+/// by `y + 1` pixels.
+pub fn is_antialiased(image: &RgbaImage, other: &RgbaImage, x: u32, y: u32) -> bool {
+    let (width, height) = (
+        image.width().min(other.width()),
+        image.height().min(other.height()),
+    );
+    let (x0, y0) = (x.saturating_sub(1), y.saturating_sub(1));
+    let (x2, y2) = ((x + 1).min(width - 1), (y + 1).min(height - 1));
+    let center = yiq_y(blend_white(image.get_pixel(x, y)));
+
+    let mut zeroes = u32::from(x == x0 || x == x2 || y == y0 || y == y2);
+    let (mut min, mut max) = (0.0f64, 0.0f64);
+    let (mut min_at, mut max_at) = ((x, y), (x, y));
+    for ny in y0..=y2 {
+        for nx in x0..=x2 {
+            if (nx, ny) == (x, y) {
+                continue;
+            }
+            let delta = center - yiq_y(blend_white(image.get_pixel(nx, ny)));
+            if delta == 0.0 {
+                zeroes += 1;
+                if zeroes > 2 {
+                    return false;
+                }
+            } else if delta < min {
+                min = delta;
+                min_at = (nx, ny);
+            } else if delta > max {
+                max = delta;
+                max_at = (nx, ny);
+            }
+        }
+    }
+    if min == 0.0 || max == 0.0 {
+        return false;
+    }
+
+    let siblings = |(sx, sy): (u32, u32)| {
+        has_many_siblings(image, sx, sy, width, height)
+            && has_many_siblings(other, sx, sy, width, height)
+    };
+    siblings(min_at) || siblings(max_at)
+}
+
+/// Whether at least three neighbours have exactly the same color
+fn has_many_siblings(image: &RgbaImage, x: u32, y: u32, width: u32, height: u32) -> bool {
+    let (x0, y0) = (x.saturating_sub(1), y.saturating_sub(1));
+    let (x2, y2) = ((x + 1).min(width - 1), (y + 1).min(height - 1));
+    let center = image.get_pixel(x, y);
+    let mut zeroes = u32::from(x == x0 || x == x2 || y == y0 || y == y2);
+    for ny in y0..=y2 {
+        for nx in x0..=x2 {
+            if (nx, ny) != (x, y) && image.get_pixel(nx, ny) == center {
+                zeroes += 1;
+                if zeroes > 2 {
+                    return true;
+                }
+            }
+        }
+    }
+    false
+}
+
+/// CIELAB (D65) of an sRGB color in 0-255
+pub fn srgb_to_lab(rgb: [f64; 3]) -> [f64; 3] {
+    let [r, g, b] = rgb.map(|c| {
+        let c = c / 255.0;
+        if c <= 0.04045 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    });
+    let x = (0.4124564 * r + 0.3575761 * g + 0.1804375 * b) / 0.95047;
+    let y = 0.2126729 * r + 0.7151522 * g + 0.0721750 * b;
+    let z = (0.0193339 * r + 0.1191920 * g + 0.9503041 * b) / 1.08883;
+    let f = |t: f64| {
+        const EPSILON: f64 = 216.0 / 24389.0;
+        if t > EPSILON {
+            t.cbrt()
+        } else {
+            t * 841.0 / 108.0 + 4.0 / 29.0
+        }
+    };
+    let (fx, fy, fz) = (f(x), f(y), f(z));
+    [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+}
+
+/// CIEDE2000 color difference between two CIELAB colors
+pub fn ciede2000(lab1: [f64; 3], lab2: [f64; 3]) -> f64 {
+    let [l1, a1, b1] = lab1;
+    let [l2, a2, b2] = lab2;
+    let pow7 = |v: f64| v.powi(7);
+    let twenty_five_7 = pow7(25.0);
+
+    let c_bar = ((a1 * a1 + b1 * b1).sqrt() + (a2 * a2 + b2 * b2).sqrt()) / 2.0;
+    let g = 0.5 * (1.0 - (pow7(c_bar) / (pow7(c_bar) + twenty_five_7)).sqrt());
+    let (a1p, a2p) = ((1.0 + g) * a1, (1.0 + g) * a2);
+    let (c1p, c2p) = ((a1p * a1p + b1 * b1).sqrt(), (a2p * a2p + b2 * b2).sqrt());
+    let hue = |b: f64, a: f64| {
+        if a == 0.0 && b == 0.0 {
+            0.0
+        } else {
+            b.atan2(a).to_degrees().rem_euclid(360.0)
+        }
+    };
+    let (h1p, h2p) = (hue(b1, a1p), hue(b2, a2p));
+
+    let delta_l = l2 - l1;
+    let delta_c = c2p - c1p;
+    let delta_h = if c1p * c2p == 0.0 {
+        0.0
+    } else {
+        let diff = h2p - h1p;
+        if diff.abs() <= 180.0 {
+            diff
+        } else if diff > 180.0 {
+            diff - 360.0
+        } else {
+            diff + 360.0
+        }
+    };
+    let delta_big_h = 2.0 * (c1p * c2p).sqrt() * (delta_h / 2.0).to_radians().sin();
+
+    let l_bar = (l1 + l2) / 2.0;
+    let c_bar_p = (c1p + c2p) / 2.0;
+    let h_bar = if c1p * c2p == 0.0 {
+        h1p + h2p
+    } else if (h1p - h2p).abs() <= 180.0 {
+        (h1p + h2p) / 2.0
+    } else if h1p + h2p < 360.0 {
+        (h1p + h2p + 360.0) / 2.0
+    } else {
+        (h1p + h2p - 360.0) / 2.0
+    };
+
+    let cos = |degrees: f64| degrees.to_radians().cos();
+    let t =
+        1.0 - 0.17 * cos(h_bar - 30.0) + 0.24 * cos(2.0 * h_bar) + 0.32 * cos(3.0 * h_bar + 6.0)
+            - 0.20 * cos(4.0 * h_bar - 63.0);
+    let delta_theta = 30.0 * (-((h_bar - 275.0) / 25.0).powi(2)).exp();
+    let r_c = 2.0 * (pow7(c_bar_p) / (pow7(c_bar_p) + twenty_five_7)).sqrt();
+    let s_l = 1.0 + 0.015 * (l_bar - 50.0).powi(2) / (20.0 + (l_bar - 50.0).powi(2)).sqrt();
+    let s_c = 1.0 + 0.045 * c_bar_p;
+    let s_h = 1.0 + 0.015 * c_bar_p * t;
+    let r_t = -(2.0 * delta_theta).to_radians().sin() * r_c;
+
+    let (l, c, h) = (delta_l / s_l, delta_c / s_c, delta_big_h / s_h);
+    (l * l + c * c + h * h + r_t * c * h).sqrt()
+}
+
+/// SSIM scores of the `SSIM_BLOCK` blocks of two images, row by row
+#[derive(Debug, Clone, Serialize)]
+pub struct SsimMap {
+    pub block_size: u32,
+    pub columns: u32,
+    pub rows: u32,
+    pub scores: Vec<f32>,
+}
+
+impl SsimMap {
+    /// Score of the block containing pixel `(x, y)`
+    pub fn score_at(&self, x: u32, y: u32) -> f32 {
+        let index = (y / self.block_size) * self.columns + x / self.block_size;
+        self.scores.get(index as usize).copied().unwrap_or(1.0)
+    }
+}
+
+/// SSIM of the luminance of two images over their common area
+///
+/// Luminance planes and block rows are computed in parallel.
+pub fn ssim_map(image1: &RgbaImage, image2: &RgbaImage) -> SsimMap {
+    const C1: f64 = (0.01 * 255.0) * (0.01 * 255.0);
+    const C2: f64 = (0.03 * 255.0) * (0.03 * 255.0);
+
+    let width = image1.width().min(image2.width());
+    let height = image1.height().min(image2.height());
+    let columns = width.div_ceil(SSIM_BLOCK);
+    let rows = height.div_ceil(SSIM_BLOCK);
+    let (luma1, luma2) = rayon::join(
+        || luma_plane(image1, width, height),
+        || luma_plane(image2, width, height),
+    );
+
+    let scores = (0..rows)
+        .into_par_iter()
+        .flat_map_iter(|row| {
+            let (luma1, luma2) = (&luma1, &luma2);
+            (0..columns).map(move |column| {
+                let (x0, y0) = (column * SSIM_BLOCK, row * SSIM_BLOCK);
+                let (x1, y1) = ((x0 + SSIM_BLOCK).min(width), (y0 + SSIM_BLOCK).min(height));
+                let n = ((x1 - x0) * (y1 - y0)) as f64;
+                let (mut sum1, mut sum2, mut sq1, mut sq2, mut cross) = (0.0, 0.0, 0.0, 0.0, 0.0);
+                for y in y0..y1 {
+                    let start = (y * width) as usize;
+                    let range = start + x0 as usize..start + x1 as usize;
+                    for (&a, &b) in luma1[range.clone()].iter().zip(&luma2[range]) {
+                        sum1 += a;
+                        sum2 += b;
+                        sq1 += a * a;
+                        sq2 += b * b;
+                        cross += a * b;
+                    }
+                }
+                let (mean1, mean2) = (sum1 / n, sum2 / n);
+                let var1 = (sq1 / n - mean1 * mean1).max(0.0);
+                let var2 = (sq2 / n - mean2 * mean2).max(0.0);
+                let covariance = cross / n - mean1 * mean2;
+                let score = ((2.0 * mean1 * mean2 + C1) * (2.0 * covariance + C2))
+                    / ((mean1 * mean1 + mean2 * mean2 + C1) * (var1 + var2 + C2));
+                ((score * 1000.0).round() / 1000.0) as f32
+            })
+        })
+        .collect();
+
+    SsimMap {
+        block_size: SSIM_BLOCK,
+        columns,
+        rows,
+        scores,
+    }
+}
+
+/// Row-major luminance of the top-left `width` x `height` pixels, blended onto white
+fn luma_plane(image: &RgbaImage, width: u32, height: u32) -> Vec<f64> {
+    if width == 0 {
+        return Vec::new();
+    }
+    let stride = image.width() as usize * 4;
+    let mut plane = vec![0.0; (width * height) as usize];
+    plane
+        .par_chunks_mut(width as usize)
+        .enumerate()
+        .for_each(|(y, row)| {
+            let pixels = &image.as_raw()[y * stride..y * stride + row.len() * 4];
+            for (luma, pixel) in row.iter_mut().zip(pixels.chunks_exact(4)) {
+                *luma = yiq_y(blend_white(Rgba::from_slice(pixel)));
+            }
+        });
+    plane
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ciede2000_matches_reference_pairs() {
+        // Sharma, Wu and Dalal (2005), pairs 1 and 17
+        let delta = ciede2000([50.0, 2.6772, -79.7751], [50.0, 0.0, -82.7485]);
+        assert!((delta - 2.0425).abs() < 1e-4);
+        let delta = ciede2000([50.0, 2.5, 0.0], [73.0, 25.0, -18.0]);
+        assert!((delta - 27.1492).abs() < 1e-4);
+        assert_eq!(ciede2000([50.0, 10.0, 10.0], [50.0, 10.0, 10.0]), 0.0);
+
+        let white = srgb_to_lab([255.0, 255.0, 255.0]);
+        assert!((white[0] - 100.0).abs() < 0.01 && white[1].abs() < 0.01);
+    }
+
+    #[test]
+    fn translucent_pixels_blend_onto_white() {
+        assert_eq!(blend_white(&Rgba([0, 0, 0, 0])), [255.0; 3]);
+        assert_eq!(blend_white(&Rgba([0, 0, 0, 255])), [0.0; 3]);
+        assert!(yiq_delta([255.0; 3], [0.0; 3]) > max_yiq_delta(0.9));
+    }
+
+    #[test]
+    fn detects_antialiased_edge_pixels() {
+        // Black and white halves with a gray column between them; the
+        // screenshot moves the edge by one pixel
+        let edge = |gray_at: u32| {
+            RgbaImage::from_fn(7, 5, |x, _| match x {
+                x if x == gray_at => Rgba([128, 128, 128, 255]),
+                x if x < gray_at => Rgba([0, 0, 0, 255]),
+                _ => Rgba([255, 255, 255, 255]),
+            })
+        };
+        let (design, screenshot) = (edge(3), edge(4));
+        assert!(is_antialiased(&design, &screenshot, 3, 2));
+
+        let dot = RgbaImage::from_fn(5, 5, |x, y| {
+            if (x, y) == (2, 2) {
+                Rgba([255, 0, 0, 255])
+            } else {
+                Rgba([255, 255, 255, 255])
+            }
+        });
+        assert!(!is_antialiased(&dot, &dot, 2, 2));
+    }
+
+    #[test]
+    fn ssim_is_one_for_identical_blocks() {
+        let noise = RgbaImage::from_fn(16, 8, |x, y| {
+            let v = ((x * 37 + y * 91) % 255) as u8;
+            Rgba([v, v, v, 255])
+        });
+        let map = ssim_map(&noise, &noise);
+        assert_eq!((map.columns, map.rows), (2, 1));
+        assert!(map.scores.iter().all(|score| *score == 1.0));
+
+        let mut changed = noise.clone();
+        for y in 0..8 {
+            for x in 8..16 {
+                changed.put_pixel(x, y, Rgba([255, 255, 255, 255]));
+            }
+        }
+        let map = ssim_map(&noise, &changed);
+        assert_eq!(map.score_at(0, 0), 1.0);
+        assert!(map.score_at(12, 4) < 0.5);
+    }
+}
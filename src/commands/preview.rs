@@ -56,7 +56,12 @@ pub async fn run(args: PreviewArgs) -> Result<()> {
         anyhow::bail!("Scale must be between 1 and 4");
     }
     let images = client
-        .export_images(&parsed.file_key, &[target_node.clone()], "png", scale)
+        .export_images(
+            &parsed.file_key,
+            std::slice::from_ref(&target_node),
+            "png",
+            scale,
+        )
         .await?;
 
     if let Some(err) = &images.err {
@@ -0,0 +1,286 @@
+//! Diff regions for `compare` and `compare-url`
+//!
+//! Differing pixels are bucketed into `CELL` x `CELL` cells and touching cells
+//! are joined, so pixels less than a cell apart always land in the same region
+//! and a changed label becomes one box rather than one per glyph. In
+//! `compare-url` each region is then attributed to the most specific layer of
+//! the compared frame whose bounding box covers it.
+
+use crate::api::types::Node;
+use crate::commands::mask::IgnoreRect;
+use crate::reporting::{ReportItem, ReportStatus};
+use serde::Serialize;
+
+/// Cell size in pixels used to join nearby differences
+const CELL: u32 = 8;
+
+/// Largest number of regions reported, biggest first
+pub const MAX_REGIONS: usize = 50;
+
+/// Bounding box of a cluster of differing pixels, in compared-area pixels
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DiffRegion {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+    /// Differing pixels inside the box
+    pub pixels: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub node_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub node_name: Option<String>,
+}
+
+impl DiffRegion {
+    /// `Button/Primary (1:234) differs in a 120x44 area at (16, 300)`
+    pub fn describe(&self) -> String {
+        let subject = match (&self.node_name, &self.node_id) {
+            (Some(name), Some(id)) => format!("{} ({})", name, id),
+            _ => "Region".to_string(),
+        };
+        format!(
+            "{} differs in a {}x{} area at ({}, {})",
+            subject, self.width, self.height, self.x, self.y
+        )
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Bounds {
+    min_x: u32,
+    min_y: u32,
+    max_x: u32,
+    max_y: u32,
+    pixels: u64,
+}
+
+impl Bounds {
+    fn point(x: u32, y: u32) -> Self {
+        Self {
+            min_x: x,
+            min_y: y,
+            max_x: x,
+            max_y: y,
+            pixels: 1,
+        }
+    }
+
+    fn merge(&mut self, other: &Bounds) {
+        self.min_x = self.min_x.min(other.min_x);
+        self.min_y = self.min_y.min(other.min_y);
+        self.max_x = self.max_x.max(other.max_x);
+        self.max_y = self.max_y.max(other.max_y);
+        self.pixels += other.pixels;
+    }
+}
+
+/// Cluster the `true` entries of a row-major `width` x `height` grid into regions, largest first
+pub fn cluster_regions(differs: &[bool], width: u32, height: u32) -> Vec<DiffRegion> {
+    let columns = width.div_ceil(CELL) as usize;
+    let rows = height.div_ceil(CELL) as usize;
+    let mut cells: Vec<Option<Bounds>> = vec![None; columns * rows];
+    for (index, _) in differs.iter().enumerate().filter(|(_, &differs)| differs) {
+        let (x, y) = (index as u32 % width, index as u32 / width);
+        let cell = (y / CELL) as usize * columns + (x / CELL) as usize;
+        match &mut cells[cell] {
+            Some(bounds) => bounds.merge(&Bounds::point(x, y)),
+            empty => *empty = Some(Bounds::point(x, y)),
+        }
+    }
+
+    let mut regions = Vec::new();
+    let mut stack = Vec::new();
+    for start in 0..cells.len() {
+        let Some(mut bounds) = cells[start].take() else {
+            continue;
+        };
+        stack.push(start);
+        while let Some(cell) = stack.pop() {
+            let (column, row) = (cell % columns, cell / columns);
+            for ny in row.saturating_sub(1)..=(row + 1).min(rows - 1) {
+                for nx in column.saturating_sub(1)..=(column + 1).min(columns - 1) {
+                    let neighbour = ny * columns + nx;
+                    if let Some(other) = cells[neighbour].take() {
+                        bounds.merge(&other);
+                        stack.push(neighbour);
+                    }
+                }
+            }
+        }
+        regions.push(DiffRegion {
+            x: bounds.min_x,
+            y: bounds.min_y,
+            width: bounds.max_x - bounds.min_x + 1,
+            height: bounds.max_y - bounds.min_y + 1,
+            pixels: bounds.pixels,
+            node_id: None,
+            node_name: None,
+        });
+    }
+
+    regions.sort_by(|a, b| b.pixels.cmp(&a.pixels).then((a.y, a.x).cmp(&(b.y, b.x))));
+    regions.truncate(MAX_REGIONS);
+    regions
+}
+
+/// Name each region after the smallest layer of `frame` that overlaps it the most
+///
+/// `scale` is the export scale and `origin` the compared area's top-left corner
+/// in export pixels. Hidden layers are skipped; the frame itself is the fallback.
+pub fn attribute_regions(regions: &mut [DiffRegion], frame: &Node, scale: f32, origin: (u32, u32)) {
+    let Some(frame_bounds) = frame.absolute_bounding_box.as_ref() else {
+        return;
+    };
+    let mut layers = Vec::new();
+    collect_layers(frame, &mut |node| {
+        let rect = node
+            .absolute_bounding_box
+            .as_ref()
+            .and_then(|bounds| IgnoreRect::from_node_bounds(bounds, frame_bounds, scale));
+        if let Some(rect) = rect {
+            layers.push((node, rect));
+        }
+    });
+
+    for region in regions {
+        let area = IgnoreRect {
+            x: region.x + origin.0,
+            y: region.y + origin.1,
+            width: region.width,
+            height: region.height,
+        };
+        let best = layers
+            .iter()
+            .map(|(node, rect)| {
+                (
+                    node,
+                    overlap(&area, rect),
+                    rect.width as u64 * rect.height as u64,
+                )
+            })
+            .filter(|(_, overlap, _)| *overlap > 0)
+            .min_by(|a, b| b.1.cmp(&a.1).then(a.2.cmp(&b.2)));
+        if let Some((node, _, _)) = best {
+            region.node_id = Some(node.id.clone());
+            region.node_name = Some(node.name.clone());
+        }
+    }
+}
+
+/// Visible nodes, children after their parents
+fn collect_layers<'a>(node: &'a Node, visit: &mut impl FnMut(&'a Node)) {
+    if node.visible == Some(false) {
+        return;
+    }
+    visit(node);
+    for child in node.children.as_deref().unwrap_or_default() {
+        collect_layers(child, visit);
+    }
+}
+
+fn overlap(a: &IgnoreRect, b: &IgnoreRect) -> u64 {
+    let width = (a.x + a.width)
+        .min(b.x + b.width)
+        .saturating_sub(a.x.max(b.x));
+    let height = (a.y + a.height)
+        .min(b.y + b.height)
+        .saturating_sub(a.y.max(b.y));
+    width as u64 * height as u64
+}
+
+/// One report item per region of a failed comparison, named after its layer when known
+pub fn report_items(subject: &str, regions: &[DiffRegion]) -> Vec<ReportItem> {
+    regions
+        .iter()
+        .enumerate()
+        .map(|(index, region)| {
+            let name = match &region.node_name {
+                Some(name) => format!("{}: {}", subject, name),
+                None => format!("{}: region {}", subject, index + 1),
+            };
+            ReportItem::new(name, ReportStatus::Fail, region.describe())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(width: u32, height: u32, points: &[(u32, u32)]) -> Vec<bool> {
+        let mut differs = vec![false; (width * height) as usize];
+        for &(x, y) in points {
+            differs[(y * width + x) as usize] = true;
+        }
+        differs
+    }
+
+    #[test]
+    fn nearby_pixels_form_one_region() {
+        let differs = grid(64, 32, &[(2, 2), (9, 3), (12, 10), (60, 30), (61, 31)]);
+        let regions = cluster_regions(&differs, 64, 32);
+        let boxes: Vec<(u32, u32, u32, u32, u64)> = regions
+            .iter()
+            .map(|r| (r.x, r.y, r.width, r.height, r.pixels))
+            .collect();
+        assert_eq!(boxes, vec![(2, 2, 11, 9, 3), (60, 30, 2, 2, 2)]);
+        assert!(cluster_regions(&grid(4, 4, &[]), 4, 4).is_empty());
+    }
+
+    #[test]
+    fn regions_are_attributed_to_the_smallest_covering_layer() {
+        let frame: Node = serde_json::from_value(serde_json::json!({
+            "id": "1:1",
+            "name": "Screen",
+            "type": "FRAME",
+            "absoluteBoundingBox": { "x": 100, "y": 100, "width": 200, "height": 400 },
+            "children": [{
+                "id": "1:2",
+                "name": "Card",
+                "type": "FRAME",
+                "absoluteBoundingBox": { "x": 100, "y": 150, "width": 200, "height": 100 },
+                "children": [{
+                    "id": "1:234",
+                    "name": "Button/Primary",
+                    "type": "INSTANCE",
+                    "absoluteBoundingBox": { "x": 120, "y": 200, "width": 60, "height": 22 }
+                }]
+            }, {
+                "id": "1:3",
+                "name": "Hidden",
+                "type": "RECTANGLE",
+                "visible": false,
+                "absoluteBoundingBox": { "x": 120, "y": 200, "width": 10, "height": 10 }
+            }]
+        }))
+        .expect("node should deserialize");
+
+        let region = |x, y, width, height| DiffRegion {
+            x,
+            y,
+            width,
+            height,
+            pixels: 1,
+            node_id: None,
+            node_name: None,
+        };
+        // 2x export whose compared area starts 10px down after cropping
+        let mut regions = vec![
+            region(40, 190, 120, 44),
+            region(0, 600, 20, 20),
+            region(0, 0, 10, 10),
+        ];
+        attribute_regions(&mut regions, &frame, 2.0, (0, 10));
+        assert_eq!(
+            regions[0].describe(),
+            "Button/Primary (1:234) differs in a 120x44 area at (40, 190)"
+        );
+        assert_eq!(regions[1].node_name.as_deref(), Some("Screen"));
+        assert_eq!(regions[2].node_name.as_deref(), Some("Screen"));
+
+        let items = report_items("frame", &regions);
+        assert_eq!(items[0].name, "frame: Button/Primary");
+        assert!(matches!(items[0].status, ReportStatus::Fail));
+    }
+}
@@ -1,5 +1,10 @@
-use crate::cli::{CompareUrlArgs, ExportCommands, RunArgs, SnapshotCommands, SyncArgs};
+use crate::cli::{
+    CompareUrlArgs, DevicePreset, DiffMethod, ExportCommands, RunArgs, SnapshotCommands,
+    SvgOptimizeArgs, SyncArgs,
+};
 use crate::commands;
+use crate::commands::compare::DiffOptions;
+use crate::commands::mask::IgnoreRect;
 use crate::output;
 use crate::reporting::{write_report, ReportItem, ReportStatus, ReportSummary};
 use anyhow::Result;
@@ -15,16 +20,38 @@ pub async fn run(args: RunArgs) -> Result<()> {
         let name = job.name();
         output::print_status(&format!("Running job: {}", name));
         let result = match job {
-            RunJob::ExportBatch { manifest, .. } => {
-                commands::export::run(ExportCommands::Batch { manifest }).await
+            RunJob::ExportBatch {
+                manifest,
+                optimize_svg,
+                svg_current_color,
+                ..
+            } => {
+                commands::export::run(ExportCommands::Batch {
+                    manifest,
+                    svg: SvgOptimizeArgs {
+                        optimize_svg,
+                        svg_current_color,
+                    },
+                })
+                .await
             }
             RunJob::Sync {
-                manifest, force, ..
+                manifest,
+                force,
+                optimize_svg,
+                svg_current_color,
+                use_export_settings,
+                ..
             } => {
                 commands::sync::run(SyncArgs {
                     manifest,
                     dry_run: false,
                     force,
+                    svg: SvgOptimizeArgs {
+                        optimize_svg,
+                        svg_current_color,
+                    },
+                    use_export_settings,
                     report: None,
                     report_format: crate::reporting::ReportFormat::Json,
                 })
@@ -37,8 +64,19 @@ pub async fn run(args: RunArgs) -> Result<()> {
                 scale,
                 tolerance,
                 fast,
+                method,
+                aa_threshold,
+                max_delta_e,
+                min_ssim,
+                ignore,
+                ignore_mask,
+                ignore_node,
+                device,
+                align_search,
+                no_normalize,
                 ..
             } => {
+                let defaults = DiffOptions::default();
                 commands::compare_url::run(CompareUrlArgs {
                     figma_url,
                     screenshot,
@@ -46,6 +84,16 @@ pub async fn run(args: RunArgs) -> Result<()> {
                     threshold: threshold.unwrap_or(5.0),
                     scale,
                     tolerance: tolerance.unwrap_or(10),
+                    method,
+                    aa_threshold: aa_threshold.unwrap_or(defaults.aa_threshold),
+                    max_delta_e: max_delta_e.unwrap_or(defaults.max_delta_e),
+                    min_ssim: min_ssim.unwrap_or(defaults.min_ssim),
+                    ignore,
+                    ignore_mask,
+                    ignore_node,
+                    device,
+                    align_search,
+                    no_normalize,
                     fast,
                     report: None,
                     report_format: crate::reporting::ReportFormat::Json,
@@ -120,12 +168,22 @@ enum RunJob {
     ExportBatch {
         name: Option<String>,
         manifest: PathBuf,
+        #[serde(default)]
+        optimize_svg: bool,
+        #[serde(default)]
+        svg_current_color: bool,
     },
     Sync {
         name: Option<String>,
         manifest: PathBuf,
         #[serde(default)]
         force: bool,
+        #[serde(default)]
+        optimize_svg: bool,
+        #[serde(default)]
+        svg_current_color: bool,
+        #[serde(default)]
+        use_export_settings: bool,
     },
     CompareUrl {
         name: Option<String>,
@@ -136,6 +194,21 @@ enum RunJob {
         tolerance: Option<u8>,
         #[serde(default)]
         fast: bool,
+        #[serde(default)]
+        method: DiffMethod,
+        aa_threshold: Option<f32>,
+        max_delta_e: Option<f32>,
+        min_ssim: Option<f32>,
+        #[serde(default)]
+        ignore: Vec<IgnoreRect>,
+        ignore_mask: Option<PathBuf>,
+        #[serde(default)]
+        ignore_node: Vec<String>,
+        device: Option<DevicePreset>,
+        #[serde(default)]
+        align_search: u32,
+        #[serde(default)]
+        no_normalize: bool,
     },
     SnapshotCreate {
         name: String,
@@ -150,7 +223,7 @@ enum RunJob {
 impl RunJob {
     fn name(&self) -> String {
         match self {
-            RunJob::ExportBatch { name, manifest } => name
+            RunJob::ExportBatch { name, manifest, .. } => name
                 .clone()
                 .unwrap_or_else(|| format!("export-batch {}", manifest.display())),
             RunJob::Sync { name, manifest, .. } => name
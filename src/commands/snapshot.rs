@@ -259,20 +259,6 @@ fn list(dir: &Path) -> Result<()> {
     Ok(())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::watch_rerun_selection;
-
-    #[test]
-    fn watch_rerun_reuses_initial_pick_results() {
-        let picked = vec!["1:2".to_string(), "1:3".to_string()];
-        let (nodes, pick) = watch_rerun_selection(&[], &picked, true);
-
-        assert_eq!(nodes, picked);
-        assert!(!pick);
-    }
-}
-
 async fn diff(
     from: &str,
     to: &str,
@@ -433,3 +419,17 @@ fn extract_frame_info(document: &crate::api::types::Document) -> Vec<(String, St
     }
     frames
 }
+
+#[cfg(test)]
+mod tests {
+    use super::watch_rerun_selection;
+
+    #[test]
+    fn watch_rerun_reuses_initial_pick_results() {
+        let picked = vec!["1:2".to_string(), "1:3".to_string()];
+        let (nodes, pick) = watch_rerun_selection(&[], &picked, true);
+
+        assert_eq!(nodes, picked);
+        assert!(!pick);
+    }
+}
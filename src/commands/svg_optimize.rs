@@ -0,0 +1,502 @@
+//! Built-in optimizer for exported SVG assets
+//!
+//! Figma's SVG export carries ids on every clip path and gradient, wrapper
+//! groups and long decimal coordinates. The optimizer rounds numbers, drops
+//! ids nothing references, unwraps redundant groups, removes empty `<defs>`,
+//! comments and non-SVG namespaces, and can replace solid fill and stroke
+//! colors with `currentColor` so icons follow the surrounding text color.
+
+use anyhow::{anyhow, bail, Result};
+use roxmltree::{Document, Node, NodeType};
+use std::collections::HashSet;
+
+const SVG_NS: &str = "http://www.w3.org/2000/svg";
+const XLINK_NS: &str = "http://www.w3.org/1999/xlink";
+const XML_NS: &str = "http://www.w3.org/XML/1998/namespace";
+
+/// Attributes holding numbers or number lists that are rounded
+const NUMERIC_ATTRIBUTES: &[&str] = &[
+    "d",
+    "points",
+    "x",
+    "y",
+    "x1",
+    "y1",
+    "x2",
+    "y2",
+    "cx",
+    "cy",
+    "r",
+    "rx",
+    "ry",
+    "fx",
+    "fy",
+    "width",
+    "height",
+    "viewBox",
+    "stroke-width",
+    "stroke-dasharray",
+    "stroke-dashoffset",
+    "stroke-miterlimit",
+    "opacity",
+    "fill-opacity",
+    "stroke-opacity",
+    "stop-opacity",
+    "offset",
+];
+
+/// Transforms keep two extra decimals since they scale everything below them
+const TRANSFORM_ATTRIBUTES: &[&str] = &["transform", "gradientTransform", "patternTransform"];
+
+/// Inherited presentation attributes a group can hand to its only child
+const INHERITED_ATTRIBUTES: &[&str] = &[
+    "fill",
+    "fill-opacity",
+    "fill-rule",
+    "clip-rule",
+    "stroke",
+    "stroke-width",
+    "stroke-opacity",
+    "stroke-linecap",
+    "stroke-linejoin",
+    "stroke-miterlimit",
+    "stroke-dasharray",
+    "stroke-dashoffset",
+];
+
+#[derive(Debug, Clone)]
+pub struct SvgOptimizeOptions {
+    /// Decimal places kept in coordinates and lengths
+    pub precision: usize,
+    /// Replace solid fill and stroke colors with `currentColor`
+    pub current_color: bool,
+}
+
+impl SvgOptimizeOptions {
+    /// Options for the `--optimize-svg` and `--svg-current-color` flags
+    pub fn from_flags(optimize_svg: bool, current_color: bool) -> Option<Self> {
+        optimize_svg.then(|| Self {
+            current_color,
+            ..Self::default()
+        })
+    }
+}
+
+impl Default for SvgOptimizeOptions {
+    fn default() -> Self {
+        Self {
+            precision: 3,
+            current_color: false,
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Item {
+    Element(Element),
+    Text(String),
+}
+
+#[derive(Debug)]
+struct Element {
+    name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<Item>,
+}
+
+impl Element {
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Optimize an SVG document, returning the rewritten markup
+pub fn optimize_svg(svg: &str, options: &SvgOptimizeOptions) -> Result<String> {
+    let document = Document::parse(svg).map_err(|err| anyhow!("invalid SVG: {}", err))?;
+    let root = document.root_element();
+    if root.tag_name().name() != "svg" {
+        bail!("invalid SVG: root element is <{}>", root.tag_name().name());
+    }
+
+    let mut root = convert(root).ok_or_else(|| anyhow!("invalid SVG: unknown root namespace"))?;
+
+    let mut referenced = HashSet::new();
+    let keep_all_ids = collect_references(&root, &mut referenced);
+    rewrite(&mut root, &referenced, keep_all_ids, options);
+    root.attributes
+        .insert(0, ("xmlns".to_string(), SVG_NS.to_string()));
+    if uses_xlink(&root) {
+        root.attributes
+            .insert(1, ("xmlns:xlink".to_string(), XLINK_NS.to_string()));
+    }
+
+    let mut out = String::with_capacity(svg.len());
+    write_element(&mut out, &root);
+    Ok(out)
+}
+
+/// Optimize downloaded SVG bytes, keeping whichever version is smaller
+pub fn optimize_svg_bytes(bytes: &[u8], options: &SvgOptimizeOptions) -> Result<Vec<u8>> {
+    let svg = std::str::from_utf8(bytes).map_err(|_| anyhow!("SVG is not valid UTF-8"))?;
+    let optimized = optimize_svg(svg, options)?.into_bytes();
+    if optimized.len() < bytes.len() || options.current_color {
+        Ok(optimized)
+    } else {
+        Ok(bytes.to_vec())
+    }
+}
+
+/// Owned copy of an element without comments, editor metadata or foreign namespaces
+fn convert(node: Node) -> Option<Element> {
+    if !matches!(node.tag_name().namespace(), None | Some(SVG_NS)) {
+        return None;
+    }
+    let name = node.tag_name().name().to_string();
+    if name == "metadata" {
+        return None;
+    }
+
+    let attributes = node
+        .attributes()
+        .filter_map(|attr| {
+            let name = match attr.namespace() {
+                None => attr.name().to_string(),
+                Some(XLINK_NS) => format!("xlink:{}", attr.name()),
+                Some(XML_NS) => format!("xml:{}", attr.name()),
+                Some(_) => return None,
+            };
+            Some((name, attr.value().to_string()))
+        })
+        .collect();
+
+    let keeps_whitespace = matches!(name.as_str(), "text" | "tspan" | "textPath");
+    let children = node
+        .children()
+        .filter_map(|child| match child.node_type() {
+            NodeType::Element => convert(child).map(Item::Element),
+            NodeType::Text => {
+                let text = child.text().unwrap_or_default();
+                (keeps_whitespace || !text.trim().is_empty()).then(|| Item::Text(text.to_string()))
+            }
+            _ => None,
+        })
+        .collect();
+
+    Some(Element {
+        name,
+        attributes,
+        children,
+    })
+}
+
+/// Collect ids referenced through `url(#id)` or `href="#id"`
+///
+/// Returns true when a `<style>` element is present, since its selectors may
+/// target any id.
+fn collect_references(element: &Element, out: &mut HashSet<String>) -> bool {
+    let mut has_style = element.name == "style";
+    for (name, value) in &element.attributes {
+        if name == "href" || name == "xlink:href" {
+            if let Some(id) = value.strip_prefix('#') {
+                out.insert(id.to_string());
+            }
+        }
+        let mut rest = value.as_str();
+        while let Some(start) = rest.find("url(") {
+            rest = &rest[start + 4..];
+            let end = rest.find(')').unwrap_or(rest.len());
+            let target = rest[..end]
+                .trim()
+                .trim_matches(|c| c == '\'' || c == '"')
+                .trim_start_matches('#');
+            out.insert(target.to_string());
+            rest = &rest[end..];
+        }
+    }
+    for child in &element.children {
+        if let Item::Element(child) = child {
+            has_style |= collect_references(child, out);
+        }
+    }
+    has_style
+}
+
+fn rewrite(
+    element: &mut Element,
+    referenced: &HashSet<String>,
+    keep_all_ids: bool,
+    options: &SvgOptimizeOptions,
+) {
+    let is_root = element.name == "svg";
+    element.attributes.retain(|(name, value)| {
+        name != "id" || keep_all_ids || referenced.contains(value.as_str())
+    });
+    for (name, value) in &mut element.attributes {
+        if NUMERIC_ATTRIBUTES.contains(&name.as_str()) {
+            *value = round_numbers(value, options.precision);
+        } else if TRANSFORM_ATTRIBUTES.contains(&name.as_str()) {
+            *value = round_numbers(value, options.precision + 2);
+        } else if options.current_color
+            && !is_root
+            && (name == "fill" || name == "stroke")
+            && is_solid_color(value)
+        {
+            *value = "currentColor".to_string();
+        }
+    }
+
+    for child in &mut element.children {
+        if let Item::Element(child) = child {
+            rewrite(child, referenced, keep_all_ids, options);
+        }
+    }
+
+    let children = std::mem::take(&mut element.children);
+    for child in children {
+        let Item::Element(mut child) = child else {
+            element.children.push(child);
+            continue;
+        };
+        let has_elements = child
+            .children
+            .iter()
+            .any(|item| matches!(item, Item::Element(_)));
+        match child.name.as_str() {
+            // Nothing can reference a definition without an id
+            _ if element.name == "defs"
+                && child.name != "style"
+                && child.attribute("id").is_none() => {}
+            "defs" | "g" if !has_elements && child.attribute("id").is_none() => {}
+            "g" if child.attributes.is_empty() => element.children.append(&mut child.children),
+            "g" if can_merge_into_child(&child) => {
+                let Some(Item::Element(mut only)) = child.children.pop() else {
+                    unreachable!("checked by can_merge_into_child");
+                };
+                only.attributes.append(&mut child.attributes);
+                element.children.push(Item::Element(only));
+            }
+            _ => element.children.push(Item::Element(child)),
+        }
+    }
+}
+
+/// A group with only inherited attributes and a single child that sets none of them
+fn can_merge_into_child(group: &Element) -> bool {
+    let [Item::Element(only)] = group.children.as_slice() else {
+        return false;
+    };
+    group.attributes.iter().all(|(name, _)| {
+        INHERITED_ATTRIBUTES.contains(&name.as_str()) && only.attribute(name).is_none()
+    })
+}
+
+fn is_solid_color(value: &str) -> bool {
+    let value = value.trim();
+    !(value.is_empty()
+        || value.starts_with("url(")
+        || matches!(value, "none" | "currentColor" | "transparent" | "inherit"))
+}
+
+fn uses_xlink(element: &Element) -> bool {
+    element
+        .attributes
+        .iter()
+        .any(|(name, _)| name.starts_with("xlink:"))
+        || element.children.iter().any(|child| match child {
+            Item::Element(child) => uses_xlink(child),
+            Item::Text(_) => false,
+        })
+}
+
+/// Round every number in an attribute value, keeping separators and commands
+fn round_numbers(value: &str, precision: usize) -> String {
+    let bytes = value.as_bytes();
+    let mut out = String::with_capacity(value.len());
+    // Whether the previous token was a number written without a decimal point
+    let mut after_integer = false;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let c = bytes[i];
+        let starts_number = c.is_ascii_digit()
+            || c == b'.'
+            || ((c == b'-' || c == b'+')
+                && bytes
+                    .get(i + 1)
+                    .is_some_and(|next| next.is_ascii_digit() || *next == b'.'));
+        if !starts_number {
+            let ch = value[i..].chars().next().unwrap_or_default();
+            out.push(ch);
+            after_integer = false;
+            i += ch.len_utf8();
+            continue;
+        }
+
+        let start = i;
+        if c == b'-' || c == b'+' {
+            i += 1;
+        }
+        let digits = |mut i: usize| {
+            while i < bytes.len() && bytes[i].is_ascii_digit() {
+                i += 1;
+            }
+            i
+        };
+        i = digits(i);
+        if i < bytes.len() && bytes[i] == b'.' {
+            i = digits(i + 1);
+        }
+        if i < bytes.len() && (bytes[i] == b'e' || bytes[i] == b'E') {
+            let mut j = i + 1;
+            if j < bytes.len() && (bytes[j] == b'-' || bytes[j] == b'+') {
+                j += 1;
+            }
+            if j < bytes.len() && bytes[j].is_ascii_digit() {
+                i = digits(j);
+            }
+        }
+
+        let token = &value[start..i];
+        // Run-together arc flags like `011` are not a number
+        let formatted = if token.len() > 1 && token.starts_with('0') && !token.contains('.') {
+            token.to_string()
+        } else {
+            match token.parse::<f64>() {
+                Ok(number) => format_number(number, precision),
+                Err(_) => token.to_string(),
+            }
+        };
+        if after_integer && formatted.starts_with('.') {
+            out.push(' ');
+        }
+        after_integer = !formatted.contains('.');
+        out.push_str(&formatted);
+    }
+
+    out
+}
+
+/// Shortest form of a number at `precision` decimals: `0.50` becomes `.5`
+fn format_number(value: f64, precision: usize) -> String {
+    let formatted = format!("{:.*}", precision, value);
+    let trimmed = if formatted.contains('.') {
+        formatted.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        formatted.as_str()
+    };
+    match trimmed {
+        "-0" => "0".to_string(),
+        _ if trimmed.starts_with("0.") => trimmed[1..].to_string(),
+        _ if trimmed.starts_with("-0.") => format!("-{}", &trimmed[2..]),
+        _ => trimmed.to_string(),
+    }
+}
+
+fn write_element(out: &mut String, element: &Element) {
+    out.push('<');
+    out.push_str(&element.name);
+    for (name, value) in &element.attributes {
+        out.push_str(&format!(
+            " {}=\"{}\"",
+            name,
+            value
+                .replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('"', "&quot;")
+        ));
+    }
+    if element.children.is_empty() {
+        out.push_str("/>");
+        return;
+    }
+    out.push('>');
+    for child in &element.children {
+        match child {
+            Item::Element(child) => write_element(out, child),
+            Item::Text(text) => out.push_str(
+                &text
+                    .replace('&', "&amp;")
+                    .replace('<', "&lt;")
+                    .replace('>', "&gt;"),
+            ),
+        }
+    }
+    out.push_str(&format!("</{}>", element.name));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIGMA_ICON: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<!-- Exported from Figma -->
+<svg width="24" height="24" viewBox="0 0 24 24" fill="none" xmlns="http://www.w3.org/2000/svg" xmlns:sketch="http://www.bohemiancoding.com/sketch/ns">
+<g id="Icon / Check" sketch:type="MSArtboardGroup">
+<g clip-path="url(#clip0_12_34)">
+<g>
+<path id="Vector" d="M20.000001 6.4999999L9.0000003 17.500001L4.0000000 12.4999" stroke="#1E1E1E" stroke-width="2.0000001"/>
+</g>
+<g fill="#FF0000"><rect x="0.50000012" y="0.5" width="3" height="3"/></g>
+<g fill="#00FF00"></g>
+</g>
+</g>
+<defs>
+<clipPath id="clip0_12_34">
+<rect width="24" height="24" fill="white"/>
+</clipPath>
+<linearGradient id="paint0_linear_unused"></linearGradient>
+</defs>
+<defs></defs>
+</svg>"##;
+
+    #[test]
+    fn strips_cruft_and_rounds_numbers() {
+        let optimized = optimize_svg(FIGMA_ICON, &SvgOptimizeOptions::default()).expect("optimize");
+
+        assert!(optimized.len() < FIGMA_ICON.len());
+        assert!(optimized.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="24""#));
+        assert!(!optimized.contains("sketch"));
+        assert!(!optimized.contains("Exported from Figma"));
+        assert!(!optimized.contains(r#"id="Vector""#));
+        assert!(!optimized.contains(r#"id="Icon / Check""#));
+        assert!(optimized.contains(r#"<clipPath id="clip0_12_34">"#));
+        assert!(optimized.contains(r#"d="M20 6.5L9 17.5L4 12.5""#));
+        assert!(optimized.contains(r#"stroke-width="2""#));
+        assert!(
+            optimized.contains(r##"<rect x=".5" y=".5" width="3" height="3" fill="#FF0000"/>"##)
+        );
+        assert!(!optimized.contains("#00FF00"));
+        assert_eq!(optimized.matches("<defs>").count(), 1);
+        assert!(!optimized.contains("linearGradient"));
+        assert!(optimized.contains(r#"<g clip-path="url(#clip0_12_34)"><path"#));
+        assert!(Document::parse(&optimized).is_ok());
+    }
+
+    #[test]
+    fn replaces_solid_colors_with_current_color() {
+        let options = SvgOptimizeOptions {
+            current_color: true,
+            ..Default::default()
+        };
+        let optimized = optimize_svg(FIGMA_ICON, &options).expect("optimize");
+
+        assert!(optimized.contains(r#"stroke="currentColor""#));
+        assert!(optimized.contains(r#"fill="currentColor""#));
+        assert!(optimized.contains(r#"viewBox="0 0 24 24" fill="none""#));
+        assert!(!optimized.contains("#1E1E1E"));
+    }
+
+    #[test]
+    fn keeps_path_data_parseable_after_rounding() {
+        assert_eq!(round_numbers("M1.0001.5L-0.25-0.75", 3), "M1 .5L-.25-.75");
+        assert_eq!(round_numbers("a1 1 0 011 1", 3), "a1 1 0 011 1");
+        assert_eq!(
+            round_numbers("matrix(0.7071068 0.7071068 -1e-7 1 0 0)", 5),
+            "matrix(.70711 .70711 0 1 0 0)"
+        );
+        assert_eq!(round_numbers("50%", 3), "50%");
+    }
+}
@@ -1,6 +1,9 @@
 use crate::api::{FigmaClient, FigmaUrl};
 use crate::auth::get_token;
 use crate::cli::SyncArgs;
+use crate::commands::export::{download_assets, plan_export_setting_assets, ExportTelemetry};
+use crate::commands::export_settings::{subtree_export_jobs, ExportJob};
+use crate::commands::svg_optimize::{optimize_svg_bytes, SvgOptimizeOptions};
 use crate::output;
 use crate::reporting::{write_report, ReportItem, ReportSummary};
 use anyhow::Result;
@@ -26,11 +29,13 @@ pub async fn run(args: SyncArgs) -> Result<()> {
 
     let token = get_token()?;
     let client = FigmaClient::new(token)?;
+    let svg_options = args.svg.options();
 
     // Process each asset definition
     let mut synced = 0;
     let mut skipped = 0;
     let mut errors = 0;
+    let mut svg_bytes_saved = 0usize;
     let mut report_items = Vec::new();
 
     for (name, asset) in &manifest.assets {
@@ -57,35 +62,99 @@ pub async fn run(args: SyncArgs) -> Result<()> {
         // Determine output path
         let output_path = resolve_output_path(&manifest.project.output_dir, &asset.output, name);
 
+        // Designer export settings replace the manifest format and scale
+        let setting_jobs = if args.use_export_settings {
+            match export_setting_jobs(&client, &parsed.file_key, &node_id, &output_path).await {
+                Ok(jobs) => {
+                    if jobs.is_empty() {
+                        output::print_status(&format!(
+                            "    {}: no export settings, using manifest format",
+                            "note".dimmed()
+                        ));
+                    }
+                    jobs
+                }
+                Err(e) => {
+                    output::print_status(&format!("    {}: {}", "error".red(), e));
+                    errors += 1;
+                    report_items.push(ReportItem::fail(name.to_string(), e.to_string()));
+                    continue;
+                }
+            }
+        } else {
+            Vec::new()
+        };
+        let output_paths: Vec<&Path> = if setting_jobs.is_empty() {
+            vec![output_path.as_path()]
+        } else {
+            setting_jobs
+                .iter()
+                .map(|(_, path)| path.as_path())
+                .collect()
+        };
+        let output_list = output_paths
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+
         // Check if file exists and force flag
-        if output_path.exists() && !args.force && !args.dry_run {
+        if output_paths.iter().all(|path| path.exists()) && !args.force && !args.dry_run {
             output::print_status(&format!(
                 "    {}: {} (use --force to overwrite)",
                 "exists".dimmed(),
-                output_path.display()
+                output_list
             ));
             skipped += 1;
             report_items.push(ReportItem::warn(
                 name.to_string(),
-                format!("Skipped existing file {}", output_path.display()),
+                format!("Skipped existing file {}", output_list),
             ));
             continue;
         }
 
         if args.dry_run {
-            output::print_status(&format!(
-                "    would export {} → {}",
-                node_id,
-                output_path.display()
-            ));
+            output::print_status(&format!("    would export {} → {}", node_id, output_list));
             synced += 1;
             report_items.push(ReportItem::ok(
                 name.to_string(),
-                format!("Dry run: would export to {}", output_path.display()),
+                format!("Dry run: would export to {}", output_list),
             ));
             continue;
         }
 
+        if !setting_jobs.is_empty() {
+            let jobs: Vec<ExportJob> = setting_jobs.iter().map(|(job, _)| job.clone()).collect();
+            match export_setting_assets(
+                &client,
+                &parsed.file_key,
+                &jobs,
+                setting_dir(&output_path),
+                svg_options.as_ref(),
+            )
+            .await
+            {
+                Ok(saved) => {
+                    for (_, path) in &setting_jobs {
+                        output::print_status(&format!("    {} {}", "✓".green(), path.display()));
+                    }
+                    svg_bytes_saved += saved;
+                    synced += 1;
+                    report_items.push(ReportItem::ok(
+                        name.to_string(),
+                        format!("Exported export settings to {}", output_list),
+                    ));
+                }
+                Err(e) => {
+                    output::print_status(&format!("    {}: {}", "error".red(), e));
+                    errors += 1;
+                    report_items.push(ReportItem::fail(name.to_string(), e.to_string()));
+                }
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(300)).await;
+            continue;
+        }
+
         // Export the asset
         let format = asset.format.as_deref().unwrap_or("png");
         let scale = asset.scale.unwrap_or(2.0);
@@ -110,16 +179,19 @@ pub async fn run(args: SyncArgs) -> Result<()> {
             format,
             scale,
             &output_path,
+            svg_options.as_ref().filter(|_| format == "svg"),
         )
         .await
         {
-            Ok(_) => {
+            Ok(saved) => {
                 output::print_status(&format!("    {} {}", "✓".green(), output_path.display()));
                 synced += 1;
-                report_items.push(ReportItem::ok(
-                    name.to_string(),
-                    format!("Exported to {}", output_path.display()),
-                ));
+                let mut message = format!("Exported to {}", output_path.display());
+                if let Some(saved) = saved {
+                    svg_bytes_saved += saved;
+                    message.push_str(&format!(" (SVG optimized, saved {} bytes)", saved));
+                }
+                report_items.push(ReportItem::ok(name.to_string(), message));
             }
             Err(e) => {
                 output::print_status(&format!("    {}: {}", "error".red(), e));
@@ -139,6 +211,12 @@ pub async fn run(args: SyncArgs) -> Result<()> {
         "  Synced: {} | Skipped: {} | Errors: {}",
         synced, skipped, errors
     ));
+    if svg_options.is_some() && !args.dry_run {
+        output::print_status(&format!(
+            "  SVG optimization saved {} bytes",
+            svg_bytes_saved
+        ));
+    }
 
     if args.dry_run {
         output::print_status("");
@@ -172,7 +250,8 @@ async fn export_asset(
     format: &str,
     scale: f32,
     output: &Path,
-) -> Result<()> {
+    optimize_svg: Option<&SvgOptimizeOptions>,
+) -> Result<Option<usize>> {
     // Ensure parent directory exists
     if let Some(parent) = output.parent() {
         fs::create_dir_all(parent)?;
@@ -192,10 +271,94 @@ async fn export_asset(
         .and_then(|u| u.as_ref())
         .ok_or_else(|| anyhow::anyhow!("No image URL returned"))?;
 
-    let bytes = client.download_image(url).await?;
+    let mut bytes = client.download_image(url).await?;
+    let mut saved = None;
+    if let Some(options) = optimize_svg {
+        match optimize_svg_bytes(&bytes, options) {
+            Ok(optimized) => {
+                saved = Some(bytes.len().saturating_sub(optimized.len()));
+                bytes = optimized;
+            }
+            Err(err) => {
+                output::print_warning(&format!("Could not optimize {}: {}", output.display(), err))
+            }
+        }
+    }
     fs::write(output, bytes)?;
 
-    Ok(())
+    Ok(saved)
+}
+
+/// Files for the export settings of a node and everything inside it, next to the
+/// manifest output path; the node's own files are named `<output stem><suffix>.<format>`
+async fn export_setting_jobs(
+    client: &FigmaClient,
+    file_key: &str,
+    node_id: &str,
+    output: &Path,
+) -> Result<Vec<(ExportJob, PathBuf)>> {
+    let node = client.get_node(file_key, node_id).await?;
+
+    let stem = output
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| node_id.replace(':', "-"));
+    let dir = setting_dir(output);
+    Ok(subtree_export_jobs(&node, &stem)
+        .into_iter()
+        .map(|job| {
+            let path = dir.join(&job.filename);
+            (job, path)
+        })
+        .collect())
+}
+
+fn setting_dir(output: &Path) -> &Path {
+    output.parent().unwrap_or_else(|| Path::new("."))
+}
+
+/// Export and write the jobs with one images API batch per format and scale,
+/// returning the bytes saved by SVG optimization
+async fn export_setting_assets(
+    client: &FigmaClient,
+    file_key: &str,
+    jobs: &[ExportJob],
+    dir: &Path,
+    optimize_svg: Option<&SvgOptimizeOptions>,
+) -> Result<usize> {
+    let mut telemetry = ExportTelemetry::default();
+    let planned = plan_export_setting_assets(client, file_key, jobs, false, &mut telemetry).await?;
+    if planned.len() < jobs.len() {
+        anyhow::bail!(
+            "{} of {} export(s) returned no image",
+            jobs.len() - planned.len(),
+            jobs.len()
+        );
+    }
+
+    let mut saved = 0usize;
+    for asset in download_assets(client, planned, false, "png", None).await? {
+        let path = dir.join(&asset.filename);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut bytes = asset.bytes;
+        if let Some(options) = optimize_svg.filter(|_| asset.source_format == "svg") {
+            match optimize_svg_bytes(&bytes, options) {
+                Ok(optimized) => {
+                    saved += bytes.len().saturating_sub(optimized.len());
+                    bytes = optimized;
+                }
+                Err(err) => output::print_warning(&format!(
+                    "Could not optimize {}: {}",
+                    path.display(),
+                    err
+                )),
+            }
+        }
+        fs::write(&path, bytes)?;
+    }
+    Ok(saved)
 }
 
 fn resolve_output_path(
@@ -0,0 +1,217 @@
+//! Color notations for exported color tokens
+//!
+//! Figma colors are sRGB. HSL is the same gamut in another notation; OKLCH
+//! and Display P3 values describe the same sRGB color in those spaces, so
+//! they render identically while staying editable in the wider space.
+
+use crate::cli::ColorSpace;
+use anyhow::{anyhow, Result};
+use clap::ValueEnum;
+
+impl ColorSpace {
+    /// Parse a `tokens.color_space` config value (`hex`, `hsl`, `oklch`, `display-p3`)
+    pub fn parse(value: &str) -> Result<ColorSpace> {
+        ColorSpace::from_str(value.trim(), true).map_err(|_| {
+            anyhow!(
+                "Invalid color space: {} (expected hex, hsl, oklch or display-p3)",
+                value
+            )
+        })
+    }
+
+    /// Name used on the command line and in the config file
+    pub fn name(self) -> String {
+        self.to_possible_value()
+            .map(|value| value.get_name().to_string())
+            .unwrap_or_default()
+    }
+}
+
+/// `#RRGGBB`, or `#RRGGBBAA` for translucent colors
+pub(super) fn hex_with_alpha(hex: &str, alpha: f64) -> String {
+    if alpha >= 1.0 {
+        hex.to_string()
+    } else {
+        format!(
+            "{}{:02X}",
+            hex,
+            (alpha.clamp(0.0, 1.0) * 255.0).round() as u8
+        )
+    }
+}
+
+/// CSS color value of an sRGB color in the requested notation
+pub(super) fn format_color(hex: &str, rgba: &[f64; 4], space: ColorSpace) -> String {
+    let [r, g, b, alpha] = *rgba;
+    match space {
+        ColorSpace::Hex => hex_with_alpha(hex, alpha),
+        ColorSpace::Hsl => {
+            let (h, s, l) = hsl(r, g, b);
+            format!(
+                "hsl({} {}% {}%{})",
+                round(h, 1),
+                round(s * 100.0, 1),
+                round(l * 100.0, 1),
+                alpha_suffix(alpha)
+            )
+        }
+        ColorSpace::Oklch => {
+            let (l, c, h) = oklch(r, g, b);
+            format!(
+                "oklch({}% {} {}{})",
+                round(l * 100.0, 2),
+                round(c, 4),
+                round(h, 2),
+                alpha_suffix(alpha)
+            )
+        }
+        ColorSpace::DisplayP3 => {
+            let [r, g, b] = display_p3(r, g, b);
+            format!(
+                "color(display-p3 {} {} {}{})",
+                round(r, 4),
+                round(g, 4),
+                round(b, 4),
+                alpha_suffix(alpha)
+            )
+        }
+    }
+}
+
+fn alpha_suffix(alpha: f64) -> String {
+    if alpha >= 1.0 {
+        String::new()
+    } else {
+        format!(" / {}", round(alpha, 3))
+    }
+}
+
+/// Number rounded to `decimals`, without trailing zeros or `-0`
+fn round(value: f64, decimals: usize) -> String {
+    let formatted = format!("{:.*}", decimals, value);
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+    if trimmed == "-0" {
+        "0".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// Hue in degrees, saturation and lightness in 0..1
+fn hsl(r: f64, g: f64, b: f64) -> (f64, f64, f64) {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let lightness = (max + min) / 2.0;
+    let delta = max - min;
+    if delta == 0.0 {
+        return (0.0, 0.0, lightness);
+    }
+    let saturation = delta / (1.0 - (2.0 * lightness - 1.0).abs());
+    let hue = if max == r {
+        ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        (b - r) / delta + 2.0
+    } else {
+        (r - g) / delta + 4.0
+    };
+    (hue * 60.0, saturation, lightness)
+}
+
+fn to_linear(channel: f64) -> f64 {
+    if channel <= 0.04045 {
+        channel / 12.92
+    } else {
+        ((channel + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn from_linear(channel: f64) -> f64 {
+    if channel <= 0.0031308 {
+        channel * 12.92
+    } else {
+        1.055 * channel.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Lightness in 0..1, chroma, and hue in degrees (Björn Ottosson's OKLab)
+fn oklch(r: f64, g: f64, b: f64) -> (f64, f64, f64) {
+    let (r, g, b) = (to_linear(r), to_linear(g), to_linear(b));
+    let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+    let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+    let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+
+    let lightness = 0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s;
+    let a = 1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s;
+    let b = 0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s;
+    let chroma = a.hypot(b);
+    // Hue is meaningless for grays; keep it at 0 instead of float noise
+    let hue = if chroma < 1e-4 {
+        0.0
+    } else {
+        b.atan2(a).to_degrees().rem_euclid(360.0)
+    };
+    (lightness, chroma, hue)
+}
+
+/// sRGB to Display P3 (same white point and transfer function)
+fn display_p3(r: f64, g: f64, b: f64) -> [f64; 3] {
+    let (r, g, b) = (to_linear(r), to_linear(g), to_linear(b));
+    [
+        0.8224621 * r + 0.1775380 * g,
+        0.0331941 * r + 0.9668058 * g,
+        0.0170827 * r + 0.0723974 * g + 0.9105199 * b,
+    ]
+    .map(|channel| from_linear(channel.clamp(0.0, 1.0)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_colors_in_each_space() {
+        let red = [1.0, 0.0, 0.0, 1.0];
+        assert_eq!(format_color("#FF0000", &red, ColorSpace::Hex), "#FF0000");
+        assert_eq!(
+            format_color("#FF0000", &red, ColorSpace::Hsl),
+            "hsl(0 100% 50%)"
+        );
+        assert_eq!(
+            format_color("#FF0000", &red, ColorSpace::Oklch),
+            "oklch(62.8% 0.2577 29.23)"
+        );
+        assert_eq!(
+            format_color("#FF0000", &red, ColorSpace::DisplayP3),
+            "color(display-p3 0.9175 0.2003 0.1386)"
+        );
+
+        let translucent = [0.0, 0.0, 1.0, 0.5];
+        assert_eq!(
+            format_color("#0000FF", &translucent, ColorSpace::Hex),
+            "#0000FF80"
+        );
+        assert_eq!(
+            format_color("#0000FF", &translucent, ColorSpace::Hsl),
+            "hsl(240 100% 50% / 0.5)"
+        );
+        let white = [1.0, 1.0, 1.0, 1.0];
+        assert_eq!(
+            format_color("#FFFFFF", &white, ColorSpace::Oklch),
+            "oklch(100% 0 0)"
+        );
+    }
+
+    #[test]
+    fn parses_config_names() {
+        assert_eq!(
+            ColorSpace::parse("display-p3").expect("p3"),
+            ColorSpace::DisplayP3
+        );
+        assert_eq!(
+            ColorSpace::parse("OKLCH").expect("oklch"),
+            ColorSpace::Oklch
+        );
+        assert_eq!(ColorSpace::DisplayP3.name(), "display-p3");
+        assert!(ColorSpace::parse("cmyk").is_err());
+    }
+}
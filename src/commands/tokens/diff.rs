@@ -0,0 +1,583 @@
+//! Token diffs between two file versions or exported token files
+//!
+//! Colors and typography are matched by name. Unnamed colors are named after
+//! their value, so a raw color that was retouched is paired with the closest
+//! new raw color within a small CIEDE2000 distance and reported as changed. Unnamed text styles have no stable
+//! name, so they are matched by value and only reported as added or removed.
+
+use super::{
+    css_color, extract_design_tokens, format_px, read_dtcg, ColorToken, DesignTokens,
+    TypographyToken,
+};
+use crate::api::{FigmaClient, FigmaUrl};
+use crate::auth::get_token;
+use crate::commands::perceptual::{ciede2000, srgb_to_lab};
+use crate::output;
+use crate::reporting::{write_report, ReportFormat, ReportItem, ReportStatus, ReportSummary};
+use anyhow::{anyhow, bail, Context, Result};
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// One side of a token diff
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffSource {
+    /// Output of `tokens export --format json` or `--format dtcg`
+    File(PathBuf),
+    /// File key or URL, optionally pinned to a version ID (`abc123@4567`)
+    Figma {
+        file_key: String,
+        version: Option<String>,
+    },
+}
+
+impl DiffSource {
+    /// Parse a tokens file path, `key`, `key@version` or Figma URL
+    pub fn parse(input: &str) -> Result<Self> {
+        let path = Path::new(input);
+        if path.is_file() {
+            return Ok(Self::File(path.to_path_buf()));
+        }
+        if input.ends_with(".json") {
+            bail!("Tokens file not found: {}", input);
+        }
+        let (target, version) = match input.rsplit_once('@') {
+            Some((target, version)) if !version.is_empty() && !version.contains('/') => {
+                (target, Some(version.to_string()))
+            }
+            _ => (input, None),
+        };
+        Ok(Self::Figma {
+            file_key: FigmaUrl::parse(target)?.file_key,
+            version,
+        })
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Self::File(path) => path.display().to_string(),
+            Self::Figma {
+                file_key,
+                version: Some(version),
+            } => format!("{}@{}", file_key, version),
+            Self::Figma { file_key, .. } => file_key.clone(),
+        }
+    }
+}
+
+/// Load tokens written by `tokens export`, detecting fgm JSON vs DTCG
+pub fn load_tokens_file(path: &Path) -> Result<DesignTokens> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read tokens file {}", path.display()))?;
+    let value: serde_json::Value = serde_json::from_str(&content)
+        .with_context(|| format!("Invalid JSON in {}", path.display()))?;
+    if value.get("colors").is_some_and(serde_json::Value::is_array) {
+        serde_json::from_value(value)
+            .with_context(|| format!("Unrecognized fgm tokens JSON in {}", path.display()))
+    } else {
+        read_dtcg(&content).with_context(|| format!("Failed to read {}", path.display()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// An added, removed or changed token value
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TokenChange {
+    pub kind: TokenChangeKind,
+    /// `color` or `typography`
+    pub category: &'static str,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+}
+
+/// Compare colors and typography of two token sets
+pub fn diff_tokens(before: &DesignTokens, after: &DesignTokens) -> Vec<TokenChange> {
+    let colors = |tokens: &DesignTokens| -> BTreeMap<String, (String, String)> {
+        tokens
+            .colors
+            .iter()
+            .map(|token| {
+                let value = css_color(&token.hex, &token.rgba);
+                (token.name.clone(), (token.name.clone(), value))
+            })
+            .collect()
+    };
+    let typography = |tokens: &DesignTokens| -> BTreeMap<String, (String, String)> {
+        let named = tokens.typography.iter().map(|token| {
+            let value = describe_typography(token);
+            (token.name.clone(), (token.name.clone(), value))
+        });
+        // Keyed by value; the NUL prefix keeps these apart from style names
+        let unnamed = tokens.unnamed_typography.iter().map(|token| {
+            let value = describe_typography(token);
+            (format!("\0{}", value), (token.name.clone(), value))
+        });
+        named.chain(unnamed).collect()
+    };
+
+    let mut changes = Vec::new();
+    diff_values("color", colors(before), colors(after), &mut changes);
+    diff_unnamed_colors(&before.unnamed_colors, &after.unnamed_colors, &mut changes);
+    diff_values(
+        "typography",
+        typography(before),
+        typography(after),
+        &mut changes,
+    );
+    changes
+}
+
+/// Diff `key -> (display name, value)` maps
+fn diff_values(
+    category: &'static str,
+    before: BTreeMap<String, (String, String)>,
+    mut after: BTreeMap<String, (String, String)>,
+    changes: &mut Vec<TokenChange>,
+) {
+    for (key, (name, old)) in before {
+        match after.remove(&key) {
+            Some((_, new)) if new == old => {}
+            Some((_, new)) => changes.push(TokenChange {
+                kind: TokenChangeKind::Changed,
+                category,
+                name,
+                before: Some(old),
+                after: Some(new),
+            }),
+            None => changes.push(TokenChange {
+                kind: TokenChangeKind::Removed,
+                category,
+                name,
+                before: Some(old),
+                after: None,
+            }),
+        }
+    }
+    for (name, new) in after.into_values() {
+        changes.push(TokenChange {
+            kind: TokenChangeKind::Added,
+            category,
+            name,
+            before: None,
+            after: Some(new),
+        });
+    }
+}
+
+/// Largest CIEDE2000 distance at which a removed and an added raw color count as one retouch
+const MAX_RETOUCH_DELTA_E: f64 = 10.0;
+/// Largest alpha difference at which a removed and an added raw color count as one retouch
+const MAX_RETOUCH_ALPHA: f64 = 0.25;
+
+/// Diff unnamed colors by value, pairing each removed color with the closest added one
+///
+/// Colors further apart than the retouch limits are reported as removed and added.
+fn diff_unnamed_colors(
+    before: &[ColorToken],
+    after: &[ColorToken],
+    changes: &mut Vec<TokenChange>,
+) {
+    let value = |token: &ColorToken| css_color(&token.hex, &token.rgba);
+    let mut added: Vec<&ColorToken> = after
+        .iter()
+        .filter(|new| !before.iter().any(|old| value(old) == value(new)))
+        .collect();
+    let removed = before
+        .iter()
+        .filter(|old| !after.iter().any(|new| value(old) == value(new)));
+
+    let lab = |token: &ColorToken| srgb_to_lab([0, 1, 2].map(|i| token.rgba[i] * 255.0));
+    // CIEDE2000 plus the alpha difference in percent, or None past the retouch limits
+    let distance = |a: &ColorToken, b: &ColorToken| -> Option<f64> {
+        let delta_e = ciede2000(lab(a), lab(b));
+        let delta_alpha = (a.rgba[3] - b.rgba[3]).abs();
+        (delta_e <= MAX_RETOUCH_DELTA_E && delta_alpha <= MAX_RETOUCH_ALPHA)
+            .then_some(delta_e + delta_alpha * 100.0)
+    };
+    for old in removed {
+        let closest = added
+            .iter()
+            .enumerate()
+            .filter_map(|(index, new)| distance(old, new).map(|distance| (index, distance)))
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(index, _)| index);
+        changes.push(match closest {
+            Some(index) => TokenChange {
+                kind: TokenChangeKind::Changed,
+                category: "color",
+                name: old.name.clone(),
+                before: Some(value(old)),
+                after: Some(value(added.remove(index))),
+            },
+            None => TokenChange {
+                kind: TokenChangeKind::Removed,
+                category: "color",
+                name: old.name.clone(),
+                before: Some(value(old)),
+                after: None,
+            },
+        });
+    }
+    for new in added {
+        changes.push(TokenChange {
+            kind: TokenChangeKind::Added,
+            category: "color",
+            name: new.name.clone(),
+            before: None,
+            after: Some(value(new)),
+        });
+    }
+}
+
+fn describe_typography(token: &TypographyToken) -> String {
+    let mut parts = Vec::new();
+    if let Some(family) = &token.family {
+        parts.push(family.clone());
+    }
+    if let Some(size) = token.size {
+        parts.push(format!("{}px", format_px(size)));
+    }
+    if let Some(weight) = token.weight {
+        parts.push(format!("weight {}", format_px(weight)));
+    }
+    if let Some(line_height) = token.line_height {
+        parts.push(format!("line-height {}px", format_px(line_height)));
+    }
+    if let Some(letter_spacing) = token.letter_spacing {
+        parts.push(format!("letter-spacing {}px", format_px(letter_spacing)));
+    }
+    if token.italic {
+        parts.push("italic".to_string());
+    }
+    if let Some(unit) = token.line_height_unit {
+        parts.push(format!("line-height unit {:?}", unit).to_lowercase());
+    }
+    if let Some(spacing) = token.paragraph_spacing {
+        parts.push(format!("paragraph-spacing {}px", format_px(spacing)));
+    }
+    if let Some(text_case) = token.text_case {
+        parts.push(format!("case {:?}", text_case).to_lowercase());
+    }
+    if let Some(decoration) = token.text_decoration {
+        parts.push(format!("{:?}", decoration).to_lowercase());
+    }
+    for (tag, value) in &token.font_features {
+        parts.push(format!("{} {}", tag.to_lowercase(), value));
+    }
+    parts.join(", ")
+}
+
+/// Report items for a diff: removals fail, changes warn (or fail when strict)
+pub fn diff_report(changes: &[TokenChange], title: String, fail_on_change: bool) -> ReportSummary {
+    let mut items: Vec<ReportItem> = changes
+        .iter()
+        .map(|change| {
+            let name = format!("{} {}", change.category, change.name);
+            let before = change.before.as_deref().unwrap_or_default();
+            let after = change.after.as_deref().unwrap_or_default();
+            match change.kind {
+                TokenChangeKind::Added => ReportItem::ok(name, format!("Added: {}", after)),
+                TokenChangeKind::Removed => ReportItem::fail(name, format!("Removed: {}", before)),
+                TokenChangeKind::Changed => ReportItem::new(
+                    name,
+                    if fail_on_change {
+                        ReportStatus::Fail
+                    } else {
+                        ReportStatus::Warn
+                    },
+                    format!("{} -> {}", before, after),
+                ),
+            }
+        })
+        .collect();
+    if items.is_empty() {
+        items.push(ReportItem::ok("tokens", "No token changes"));
+    }
+    ReportSummary { title, items }
+}
+
+pub(super) async fn diff(
+    from: &str,
+    to: &str,
+    fail_on_change: bool,
+    report: Option<&Path>,
+    report_format: ReportFormat,
+) -> Result<()> {
+    let from = DiffSource::parse(from)?;
+    let to = DiffSource::parse(to)?;
+    let mut client = None;
+    let before = load(&from, &mut client).await?;
+    let after = load(&to, &mut client).await?;
+
+    let changes = diff_tokens(&before, &after);
+    let summary = diff_report(
+        &changes,
+        format!("fgm tokens diff {} -> {}", from.label(), to.label()),
+        fail_on_change,
+    );
+
+    output::print_status(&summary.title.bold().to_string());
+    for change in &changes {
+        let (marker, value) = match change.kind {
+            TokenChangeKind::Added => ("+".green(), change.after.clone().unwrap_or_default()),
+            TokenChangeKind::Removed => ("-".red(), change.before.clone().unwrap_or_default()),
+            TokenChangeKind::Changed => (
+                "~".yellow(),
+                format!(
+                    "{} -> {}",
+                    change.before.as_deref().unwrap_or_default(),
+                    change.after.as_deref().unwrap_or_default()
+                ),
+            ),
+        };
+        output::print_status(&format!(
+            "  {} {} {} {}",
+            marker,
+            change.category.dimmed(),
+            change.name,
+            value.dimmed()
+        ));
+    }
+    let count = |kind: TokenChangeKind| changes.iter().filter(|c| c.kind == kind).count();
+    output::print_status(&format!(
+        "\n  Added: {} | Removed: {} | Changed: {}",
+        count(TokenChangeKind::Added),
+        count(TokenChangeKind::Removed),
+        count(TokenChangeKind::Changed)
+    ));
+
+    if let Some(report_path) = report {
+        write_report(report_path, report_format, &summary)?;
+        output::print_status(&format!("  Report: {}", report_path.display()));
+    }
+
+    if output::format() == crate::output::OutputFormat::Json {
+        output::print_json(&changes)?;
+    }
+
+    if summary.exit_code() != 0 {
+        bail!("Token diff found breaking changes");
+    }
+
+    Ok(())
+}
+
+async fn load(source: &DiffSource, client: &mut Option<FigmaClient>) -> Result<DesignTokens> {
+    match source {
+        DiffSource::File(path) => load_tokens_file(path),
+        DiffSource::Figma { file_key, version } => {
+            if client.is_none() {
+                *client = Some(FigmaClient::new(get_token()?)?);
+            }
+            let client = client
+                .as_ref()
+                .ok_or_else(|| anyhow!("Figma client not initialized"))?;
+            let file = match version {
+                Some(version) => client.get_file_version(file_key, version).await?,
+                None => client.get_file(file_key).await?,
+            };
+            Ok(extract_design_tokens(&file))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn color(name: &str, hex: &str, alpha: f64) -> ColorToken {
+        ColorToken {
+            name: name.to_string(),
+            hex: hex.to_string(),
+            rgb: [0, 0, 0],
+            rgba: [0.0, 0.0, 0.0, alpha],
+            description: None,
+        }
+    }
+
+    fn text(name: &str, size: f64) -> TypographyToken {
+        TypographyToken {
+            name: name.to_string(),
+            description: None,
+            family: Some("Inter".to_string()),
+            size: Some(size),
+            weight: Some(400.0),
+            line_height: None,
+            letter_spacing: None,
+            ..Default::default()
+        }
+    }
+
+    fn tokens(colors: Vec<ColorToken>, typography: Vec<TypographyToken>) -> DesignTokens {
+        DesignTokens {
+            colors,
+            typography,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn diff_lists_added_removed_and_changed_tokens() {
+        let before = tokens(
+            vec![
+                color("primary", "#000000", 1.0),
+                color("secondary", "#000000", 1.0),
+            ],
+            vec![text("body", 16.0)],
+        );
+        let mut after = tokens(
+            vec![
+                color("primary", "#000000", 0.5),
+                color("accent", "#000000", 1.0),
+            ],
+            vec![text("body", 16.004)],
+        );
+        after.unnamed_typography.push(text("caption", 12.0));
+
+        let changes = diff_tokens(&before, &after);
+
+        let summary: Vec<(TokenChangeKind, &str)> =
+            changes.iter().map(|c| (c.kind, c.name.as_str())).collect();
+        assert_eq!(
+            summary,
+            vec![
+                (TokenChangeKind::Changed, "primary"),
+                (TokenChangeKind::Removed, "secondary"),
+                (TokenChangeKind::Added, "accent"),
+                (TokenChangeKind::Added, "caption"),
+            ]
+        );
+        assert_eq!(changes[0].after.as_deref(), Some("rgba(0, 0, 0, 0.5)"));
+
+        let report = diff_report(&changes, "diff".to_string(), false);
+        assert_eq!(report.items[1].status, ReportStatus::Fail);
+        assert_eq!(report.items[0].status, ReportStatus::Warn);
+        assert_eq!(report.exit_code(), 1);
+        let strict = diff_report(&changes[..1], "diff".to_string(), true);
+        assert_eq!(strict.exit_code(), 1);
+        assert_eq!(diff_report(&[], "diff".to_string(), false).exit_code(), 0);
+    }
+
+    #[test]
+    fn retouched_unnamed_colors_are_changes() {
+        let mut white = color("color-ffffff", "#FFFFFF", 1.0);
+        white.rgba = [1.0, 1.0, 1.0, 1.0];
+        let mut before = tokens(Vec::new(), Vec::new());
+        before.unnamed_colors = vec![color("color-000000", "#000000", 1.0), white.clone()];
+        let mut after = tokens(Vec::new(), Vec::new());
+        after.unnamed_colors = vec![color("color-000000cc", "#000000", 0.8), white];
+
+        let changes = diff_tokens(&before, &after);
+
+        assert_eq!(
+            changes,
+            vec![TokenChange {
+                kind: TokenChangeKind::Changed,
+                category: "color",
+                name: "color-000000".to_string(),
+                before: Some("#000000".to_string()),
+                after: Some("rgba(0, 0, 0, 0.8)".to_string()),
+            }]
+        );
+
+        after.unnamed_colors.remove(0);
+        let changes = diff_tokens(&before, &after);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].kind, TokenChangeKind::Removed);
+    }
+
+    #[test]
+    fn unrelated_unnamed_colors_are_removed_and_added() {
+        let mut red = color("color-ff0000", "#FF0000", 1.0);
+        red.rgba = [1.0, 0.0, 0.0, 1.0];
+        let mut blue = color("color-0000ff", "#0000FF", 1.0);
+        blue.rgba = [0.0, 0.0, 1.0, 1.0];
+        let mut before = tokens(Vec::new(), Vec::new());
+        before.unnamed_colors = vec![red];
+        let mut after = tokens(Vec::new(), Vec::new());
+        after.unnamed_colors = vec![blue];
+
+        let changes = diff_tokens(&before, &after);
+
+        let summary: Vec<(TokenChangeKind, &str)> =
+            changes.iter().map(|c| (c.kind, c.name.as_str())).collect();
+        assert_eq!(
+            summary,
+            vec![
+                (TokenChangeKind::Removed, "color-ff0000"),
+                (TokenChangeKind::Added, "color-0000ff"),
+            ]
+        );
+        assert_eq!(
+            diff_report(&changes, "diff".to_string(), false).exit_code(),
+            1
+        );
+    }
+
+    #[test]
+    fn sources_parse_versions_urls_and_files() {
+        assert_eq!(
+            DiffSource::parse("abc123@4567").expect("parse"),
+            DiffSource::Figma {
+                file_key: "abc123".to_string(),
+                version: Some("4567".to_string()),
+            }
+        );
+        assert_eq!(
+            DiffSource::parse("https://www.figma.com/design/abc123/File").expect("parse"),
+            DiffSource::Figma {
+                file_key: "abc123".to_string(),
+                version: None,
+            }
+        );
+        assert!(DiffSource::parse("missing.tokens.json").is_err());
+
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("tokens.json");
+        fs::write(&path, "{}").expect("write");
+        assert_eq!(
+            DiffSource::parse(path.to_str().expect("utf-8")).expect("parse"),
+            DiffSource::File(path)
+        );
+    }
+
+    #[test]
+    fn exported_json_and_dtcg_files_load_the_same_tokens() {
+        let original = tokens(
+            vec![color("primary/500", "#FF0000", 1.0)],
+            vec![text("body", 16.0)],
+        );
+        let dir = tempdir().expect("tempdir");
+        let json_path = dir.path().join("tokens.json");
+        let dtcg_path = dir.path().join("design.tokens.json");
+        fs::write(
+            &json_path,
+            super::super::export_json(&original).expect("json"),
+        )
+        .expect("write");
+        fs::write(
+            &dtcg_path,
+            super::super::export_dtcg(&original).expect("dtcg"),
+        )
+        .expect("write");
+
+        let from_json = load_tokens_file(&json_path).expect("load json");
+        let from_dtcg = load_tokens_file(&dtcg_path).expect("load dtcg");
+
+        assert!(diff_tokens(&original, &from_json).is_empty());
+        assert!(diff_tokens(&from_json, &from_dtcg).is_empty());
+    }
+}
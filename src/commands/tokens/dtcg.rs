@@ -0,0 +1,1144 @@
+//! W3C Design Tokens Community Group (DTCG) format
+//!
+//! Tokens are `$type`/`$value` objects nested by style path (`primary/500`
+//! becomes `color.primary.500`). Variables are written per mode under
+//! `variables.<collection>.<mode>`, with aliases as `{references}`.
+//! [`read_dtcg`] turns such a file back into [`DesignTokens`] so it can be
+//! compared against a later export.
+
+use super::{
+    effects, format_px, hex_with_alpha, insert_token_path, BlurKind, BlurToken, ColorToken,
+    DesignTokens, DimensionToken, GradientKind, GradientStop, GradientToken, LineHeightUnit,
+    ShadowLayer, ShadowToken, SpacingToken, TypographyToken, VariableCollectionTokens,
+    VariableReference, VariableToken, VariableTokenType, VariableTokenValue, UNNAMED_GROUP,
+};
+use anyhow::{anyhow, Context, Result};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+
+/// Namespace for fgm data kept in `$extensions`
+const EXTENSION: &str = "fgm";
+/// Key used when a token and a group share a name (`primary` and `primary/500`)
+const DEFAULT_KEY: &str = "DEFAULT";
+const VARIABLES_GROUP: &str = "variables";
+const MAX_ALIAS_DEPTH: usize = 16;
+
+/// Render tokens as a DTCG `.tokens.json` document
+pub fn export_dtcg(tokens: &DesignTokens) -> Result<String> {
+    let mut root = Map::new();
+
+    let mut color = Map::new();
+    for token in &tokens.colors {
+        insert_token_path(&mut color, &token_path(&token.name), color_token(token));
+    }
+    for token in &tokens.unnamed_colors {
+        insert_token_path(&mut color, &unnamed_path(&token.name), color_token(token));
+    }
+    insert_group(&mut root, "color", color);
+
+    let mut typography = Map::new();
+    for token in &tokens.typography {
+        insert_token_path(
+            &mut typography,
+            &token_path(&token.name),
+            typography_token(token),
+        );
+    }
+    for token in &tokens.unnamed_typography {
+        insert_token_path(
+            &mut typography,
+            &unnamed_path(&token.name),
+            typography_token(token),
+        );
+    }
+    insert_group(&mut root, "typography", typography);
+
+    let mut spacing = Map::new();
+    for token in &tokens.spacing.values {
+        spacing.insert(
+            group_key(&token.name),
+            json!({ "$type": "dimension", "$value": dimension(token.value) }),
+        );
+    }
+    if let (Some(base), false) = (tokens.spacing.base, spacing.is_empty()) {
+        spacing.insert(
+            "$extensions".to_string(),
+            json!({ EXTENSION: { "base": base } }),
+        );
+    }
+    insert_group(&mut root, "spacing", spacing);
+
+    let mut shadow = Map::new();
+    for token in &tokens.shadows {
+        insert_token_path(&mut shadow, &token_path(&token.name), shadow_token(token));
+    }
+    for token in &tokens.unnamed_shadows {
+        insert_token_path(&mut shadow, &unnamed_path(&token.name), shadow_token(token));
+    }
+    insert_group(&mut root, "shadow", shadow);
+
+    let mut gradient = Map::new();
+    for token in &tokens.gradients {
+        insert_token_path(
+            &mut gradient,
+            &token_path(&token.name),
+            gradient_token(token),
+        );
+    }
+    for token in &tokens.unnamed_gradients {
+        insert_token_path(
+            &mut gradient,
+            &unnamed_path(&token.name),
+            gradient_token(token),
+        );
+    }
+    insert_group(&mut root, "gradient", gradient);
+
+    let mut blur = Map::new();
+    for token in &tokens.blurs {
+        insert_token_path(&mut blur, &token_path(&token.name), blur_token(token));
+    }
+    for token in &tokens.unnamed_blurs {
+        insert_token_path(&mut blur, &unnamed_path(&token.name), blur_token(token));
+    }
+    insert_group(&mut root, "blur", blur);
+
+    insert_group(&mut root, "radius", dimension_group(&tokens.radii));
+    insert_group(
+        &mut root,
+        "stroke-width",
+        dimension_group(&tokens.stroke_widths),
+    );
+
+    let mut variables = Map::new();
+    for collection in &tokens.variables {
+        variables.insert(group_key(&collection.name), variable_collection(collection));
+    }
+    insert_group(&mut root, VARIABLES_GROUP, variables);
+
+    Ok(serde_json::to_string_pretty(&Value::Object(root))?)
+}
+
+fn insert_group(root: &mut Map<String, Value>, name: &str, group: Map<String, Value>) {
+    if !group.is_empty() {
+        root.insert(name.to_string(), Value::Object(group));
+    }
+}
+
+/// Group or token key: DTCG names cannot contain `.`, `{` or `}` or start with `$`
+fn group_key(name: &str) -> String {
+    name.trim()
+        .trim_start_matches('$')
+        .replace(['.', '{', '}'], "_")
+}
+
+/// Slash-separated name as DTCG group path, keeping the original casing
+fn token_path(name: &str) -> Vec<String> {
+    let path: Vec<String> = name
+        .split('/')
+        .map(group_key)
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    if path.is_empty() {
+        vec![group_key(name)]
+    } else {
+        path
+    }
+}
+
+fn unnamed_path(name: &str) -> Vec<String> {
+    vec![UNNAMED_GROUP.to_string(), group_key(name)]
+}
+
+fn with_description(mut token: Value, description: &Option<String>) -> Value {
+    if let (Some(description), Some(object)) = (description, token.as_object_mut()) {
+        object.insert("$description".to_string(), json!(description));
+    }
+    token
+}
+
+fn dimension(px: f64) -> Value {
+    json!(format!("{}px", format_px(px)))
+}
+
+fn color_token(token: &ColorToken) -> Value {
+    with_description(
+        json!({ "$type": "color", "$value": hex_with_alpha(&token.hex, token.rgba[3]) }),
+        &token.description,
+    )
+}
+
+fn typography_token(token: &TypographyToken) -> Value {
+    let mut value = Map::new();
+    if let Some(family) = &token.family {
+        value.insert("fontFamily".to_string(), json!(family));
+    }
+    if let Some(size) = token.size {
+        value.insert("fontSize".to_string(), dimension(size));
+    }
+    if let Some(weight) = token.weight {
+        value.insert("fontWeight".to_string(), json!(weight));
+    }
+    if let Some(line_height) = token.line_height {
+        // DTCG line heights are unitless multipliers of the font size
+        let rendered = match token.line_height_ratio() {
+            Some(ratio) => json!(ratio),
+            None => dimension(line_height),
+        };
+        value.insert("lineHeight".to_string(), rendered);
+    }
+    if let Some(letter_spacing) = token.letter_spacing {
+        value.insert("letterSpacing".to_string(), dimension(letter_spacing));
+    }
+
+    // Properties the DTCG typography type has no field for
+    let mut extension = Map::new();
+    if let Some(name) = &token.post_script_name {
+        extension.insert("postScriptName".to_string(), json!(name));
+    }
+    if token.italic {
+        extension.insert("italic".to_string(), json!(true));
+    }
+    if let Some(unit) = token.line_height_unit {
+        extension.insert("lineHeightUnit".to_string(), json!(unit));
+    }
+    if let Some(spacing) = token.paragraph_spacing {
+        extension.insert("paragraphSpacing".to_string(), dimension(spacing));
+    }
+    if let Some(text_case) = token.text_case {
+        extension.insert("textCase".to_string(), json!(text_case));
+    }
+    if let Some(decoration) = token.text_decoration {
+        extension.insert("textDecoration".to_string(), json!(decoration));
+    }
+    if !token.font_features.is_empty() {
+        extension.insert("fontFeatures".to_string(), json!(token.font_features));
+    }
+    let mut rendered = json!({ "$type": "typography", "$value": value });
+    if !extension.is_empty() {
+        rendered["$extensions"] = json!({ EXTENSION: extension });
+    }
+    with_description(rendered, &token.description)
+}
+
+fn shadow_token(token: &ShadowToken) -> Value {
+    let layers: Vec<Value> = token
+        .layers
+        .iter()
+        .map(|layer| {
+            json!({
+                "color": hex_with_alpha(&layer.hex, layer.rgba[3]),
+                "offsetX": dimension(layer.offset_x),
+                "offsetY": dimension(layer.offset_y),
+                "blur": dimension(layer.blur),
+                "spread": dimension(layer.spread),
+                "inset": layer.inset
+            })
+        })
+        .collect();
+    let value = match <[Value; 1]>::try_from(layers) {
+        Ok([single]) => single,
+        Err(layers) => Value::Array(layers),
+    };
+    with_description(
+        json!({ "$type": "shadow", "$value": value }),
+        &token.description,
+    )
+}
+
+/// Gradient stops, with the kind and handle positions kept in `$extensions`
+fn gradient_token(token: &GradientToken) -> Value {
+    let stops: Vec<Value> = token
+        .stops
+        .iter()
+        .map(|stop| {
+            json!({
+                "color": hex_with_alpha(&stop.hex, stop.rgba[3]),
+                "position": stop.position
+            })
+        })
+        .collect();
+    with_description(
+        json!({
+            "$type": "gradient",
+            "$value": stops,
+            "$extensions": { EXTENSION: { "kind": token.kind, "handles": token.handles } }
+        }),
+        &token.description,
+    )
+}
+
+/// Blur radius in code units, with the blur kind kept in `$extensions`
+fn blur_token(token: &BlurToken) -> Value {
+    let (radius, kind) = effects::dtcg_blur(token);
+    with_description(
+        json!({
+            "$type": "dimension",
+            "$value": dimension(radius),
+            "$extensions": { EXTENSION: { "kind": kind } }
+        }),
+        &token.description,
+    )
+}
+
+fn dimension_group(tokens: &[DimensionToken]) -> Map<String, Value> {
+    tokens
+        .iter()
+        .map(|token| {
+            (
+                group_key(&token.name),
+                json!({ "$type": "dimension", "$value": dimension(token.value) }),
+            )
+        })
+        .collect()
+}
+
+fn variable_collection(collection: &VariableCollectionTokens) -> Value {
+    let mut group = Map::new();
+    group.insert(
+        "$extensions".to_string(),
+        json!({ EXTENSION: { "modes": collection.modes } }),
+    );
+    for mode in &collection.modes {
+        let mut entries = Map::new();
+        for variable in &collection.variables {
+            let Some(value) = variable.values.get(mode) else {
+                continue;
+            };
+            let rendered = match value {
+                VariableTokenValue::Color { hex, rgba } => json!(hex_with_alpha(hex, rgba[3])),
+                VariableTokenValue::Number(number) if variable.is_dimension() => dimension(*number),
+                VariableTokenValue::Number(number) => json!(number),
+                VariableTokenValue::String(text) => json!(text),
+                VariableTokenValue::Boolean(flag) => json!(flag),
+                VariableTokenValue::Alias { alias } => json!(format!(
+                    "{{{}.{}.{}.{}}}",
+                    VARIABLES_GROUP,
+                    group_key(&alias.collection),
+                    group_key(&alias.mode),
+                    token_path(&alias.name).join(".")
+                )),
+            };
+            let mut token = json!({ "$type": variable_type(variable), "$value": rendered });
+            if !variable.scopes.is_empty() {
+                token["$extensions"] = json!({ EXTENSION: { "scopes": variable.scopes } });
+            }
+            insert_token_path(
+                &mut entries,
+                &token_path(&variable.name),
+                with_description(token, &variable.description),
+            );
+        }
+        group.insert(group_key(mode), Value::Object(entries));
+    }
+    Value::Object(group)
+}
+
+fn variable_type(variable: &VariableToken) -> &'static str {
+    match variable.token_type {
+        VariableTokenType::Color => "color",
+        VariableTokenType::Number if variable.is_dimension() => "dimension",
+        VariableTokenType::Number => "number",
+        VariableTokenType::String => "string",
+        VariableTokenType::Boolean => "boolean",
+    }
+}
+
+/// A token found while walking the document, with its inherited `$type`
+struct Leaf {
+    path: Vec<String>,
+    token_type: Option<String>,
+    value: Value,
+    description: Option<String>,
+    extensions: Option<Value>,
+}
+
+/// Read a DTCG document back into design tokens
+///
+/// Color, typography, shadow and gradient tokens are read from any group; dimension
+/// tokens are read from the `spacing`, `blur`, `radius` and `stroke-width`
+/// groups. `{alias}` references are resolved. The `variables` group written by
+/// [`export_dtcg`] is read back per collection and mode, keeping aliases.
+pub fn read_dtcg(json: &str) -> Result<DesignTokens> {
+    let root: Value = serde_json::from_str(json).context("Invalid DTCG tokens JSON")?;
+    let groups = root
+        .as_object()
+        .ok_or_else(|| anyhow!("DTCG tokens file must contain a JSON object"))?;
+
+    let mut tokens = DesignTokens::default();
+
+    let mut leaves = Vec::new();
+    for (key, value) in groups {
+        if key.starts_with('$') {
+            continue;
+        }
+        if key == VARIABLES_GROUP {
+            tokens.variables = read_variables(value)?;
+            continue;
+        }
+        collect_leaves(value, vec![key.clone()], None, &mut leaves);
+    }
+
+    for leaf in leaves {
+        let Some(token_type) = leaf.token_type.as_deref() else {
+            continue;
+        };
+        let value = resolve_aliases(&root, &leaf.value, 0)
+            .with_context(|| format!("Failed to resolve token {}", leaf.path.join(".")))?;
+        let group = match token_type {
+            "dimension" => leaf.path[0].as_str(),
+            other => other,
+        };
+        let (name, unnamed) = token_name(&leaf.path, group);
+        match token_type {
+            "color" => {
+                let Some(color) = parse_color(&name, &value, leaf.description) else {
+                    continue;
+                };
+                if unnamed {
+                    tokens.unnamed_colors.push(color);
+                } else {
+                    tokens.colors.push(color);
+                }
+            }
+            "typography" => {
+                let extension = leaf
+                    .extensions
+                    .as_ref()
+                    .and_then(|extensions| extensions.get(EXTENSION));
+                let typography = parse_typography(name, &value, extension, leaf.description);
+                if unnamed {
+                    tokens.unnamed_typography.push(typography);
+                } else {
+                    tokens.typography.push(typography);
+                }
+            }
+            "shadow" => {
+                let layers = match &value {
+                    Value::Array(layers) => layers.iter().filter_map(parse_shadow_layer).collect(),
+                    layer => parse_shadow_layer(layer).into_iter().collect(),
+                };
+                let shadow = ShadowToken {
+                    name,
+                    description: leaf.description,
+                    layers,
+                };
+                if unnamed {
+                    tokens.unnamed_shadows.push(shadow);
+                } else {
+                    tokens.shadows.push(shadow);
+                }
+            }
+            "gradient" => {
+                let stops: Vec<GradientStop> = value
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(parse_gradient_stop)
+                    .collect();
+                if stops.is_empty() {
+                    continue;
+                }
+                let extension = |key: &str| {
+                    leaf.extensions
+                        .as_ref()
+                        .and_then(|extensions| extensions.get(EXTENSION))
+                        .and_then(|fgm| fgm.get(key))
+                        .cloned()
+                };
+                let gradient = GradientToken {
+                    name,
+                    kind: extension("kind")
+                        .and_then(|kind| serde_json::from_value(kind).ok())
+                        .unwrap_or(GradientKind::Linear),
+                    // Missing handles fall back to Figma's defaults when rendered
+                    handles: extension("handles")
+                        .and_then(|handles| serde_json::from_value(handles).ok())
+                        .unwrap_or_default(),
+                    description: leaf.description,
+                    stops,
+                };
+                if unnamed {
+                    tokens.unnamed_gradients.push(gradient);
+                } else {
+                    tokens.gradients.push(gradient);
+                }
+            }
+            "dimension" => {
+                let Some(px) = parse_dimension(&value) else {
+                    continue;
+                };
+                match group {
+                    "spacing" => tokens.spacing.values.push(SpacingToken {
+                        step: name
+                            .strip_prefix("space-")
+                            .and_then(|step| step.parse().ok()),
+                        name,
+                        value: px,
+                        usage: 0,
+                        sources: Vec::new(),
+                    }),
+                    "blur" => {
+                        let kind = leaf
+                            .extensions
+                            .as_ref()
+                            .and_then(|extensions| extensions.get(EXTENSION))
+                            .and_then(|fgm| fgm.get("kind"))
+                            .and_then(|kind| serde_json::from_value(kind.clone()).ok())
+                            .unwrap_or(BlurKind::Layer);
+                        let blur = BlurToken {
+                            name,
+                            description: leaf.description,
+                            kind,
+                            radius: effects::figma_blur(px),
+                        };
+                        if unnamed {
+                            tokens.unnamed_blurs.push(blur);
+                        } else {
+                            tokens.blurs.push(blur);
+                        }
+                    }
+                    "radius" => tokens.radii.push(DimensionToken {
+                        name,
+                        value: px,
+                        usage: 0,
+                    }),
+                    "stroke-width" => tokens.stroke_widths.push(DimensionToken {
+                        name,
+                        value: px,
+                        usage: 0,
+                    }),
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Same order as extraction: named tokens by name, unnamed colors by value
+    tokens.colors.sort_by(|a, b| a.name.cmp(&b.name));
+    tokens.unnamed_colors.sort_by(|a, b| a.hex.cmp(&b.hex));
+    tokens.typography.sort_by(|a, b| a.name.cmp(&b.name));
+    tokens
+        .unnamed_typography
+        .sort_by(|a, b| a.name.cmp(&b.name));
+    tokens.shadows.sort_by(|a, b| a.name.cmp(&b.name));
+    tokens.unnamed_shadows.sort_by(|a, b| a.name.cmp(&b.name));
+    tokens.gradients.sort_by(|a, b| a.name.cmp(&b.name));
+    tokens
+        .blurs
+        .sort_by(|a, b| (&a.name, a.kind).cmp(&(&b.name, b.kind)));
+    tokens
+        .unnamed_blurs
+        .sort_by(|a, b| a.kind.cmp(&b.kind).then(a.radius.total_cmp(&b.radius)));
+    tokens.radii.sort_by(|a, b| a.value.total_cmp(&b.value));
+    tokens
+        .stroke_widths
+        .sort_by(|a, b| a.value.total_cmp(&b.value));
+    tokens.spacing.base = root
+        .pointer(&format!("/spacing/$extensions/{}/base", EXTENSION))
+        .and_then(Value::as_f64);
+    tokens
+        .spacing
+        .values
+        .sort_by(|a, b| a.value.total_cmp(&b.value));
+    Ok(tokens)
+}
+
+fn collect_leaves(
+    value: &Value,
+    path: Vec<String>,
+    inherited_type: Option<&str>,
+    leaves: &mut Vec<Leaf>,
+) {
+    let Some(object) = value.as_object() else {
+        return;
+    };
+    let token_type = object
+        .get("$type")
+        .and_then(Value::as_str)
+        .or(inherited_type);
+    if let Some(token_value) = object.get("$value") {
+        leaves.push(Leaf {
+            path,
+            token_type: token_type.map(str::to_string),
+            value: token_value.clone(),
+            description: object
+                .get("$description")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            extensions: object.get("$extensions").cloned(),
+        });
+        return;
+    }
+    for (key, child) in object {
+        if key.starts_with('$') {
+            continue;
+        }
+        let mut child_path = path.clone();
+        child_path.push(key.clone());
+        collect_leaves(child, child_path, token_type, leaves);
+    }
+}
+
+/// Token name from its path, without the well-known top-level group
+///
+/// Returns whether the token sits in the `unnamed` group.
+fn token_name(path: &[String], group: &str) -> (String, bool) {
+    let mut segments: Vec<&str> = path.iter().map(String::as_str).collect();
+    if segments.len() > 1 && segments[0] == group {
+        segments.remove(0);
+    }
+    let unnamed = segments.len() > 1 && segments[0] == UNNAMED_GROUP;
+    if unnamed {
+        segments.remove(0);
+    }
+    if segments.len() > 1 && segments.last() == Some(&DEFAULT_KEY) {
+        segments.pop();
+    }
+    (segments.join("/"), unnamed)
+}
+
+/// Replace `{group.token}` references with the referenced `$value`
+fn resolve_aliases(root: &Value, value: &Value, depth: usize) -> Result<Value> {
+    if depth > MAX_ALIAS_DEPTH {
+        return Err(anyhow!("Alias chain is too deep (possible cycle)"));
+    }
+    match value {
+        Value::String(text) => match alias_path(text) {
+            Some(path) => {
+                let escaped: Vec<String> = path
+                    .iter()
+                    .map(|segment| segment.replace('~', "~0").replace('/', "~1"))
+                    .collect();
+                let pointer = format!("/{}/$value", escaped.join("/"));
+                let target = root
+                    .pointer(&pointer)
+                    .ok_or_else(|| anyhow!("Unknown token reference {}", text))?;
+                resolve_aliases(root, target, depth + 1)
+            }
+            None => Ok(value.clone()),
+        },
+        Value::Array(items) => items
+            .iter()
+            .map(|item| resolve_aliases(root, item, depth))
+            .collect::<Result<Vec<_>>>()
+            .map(Value::Array),
+        Value::Object(object) => object
+            .iter()
+            .map(|(key, item)| Ok((key.clone(), resolve_aliases(root, item, depth)?)))
+            .collect::<Result<Map<_, _>>>()
+            .map(Value::Object),
+        _ => Ok(value.clone()),
+    }
+}
+
+fn alias_path(text: &str) -> Option<Vec<String>> {
+    let inner = text.strip_prefix('{')?.strip_suffix('}')?;
+    if inner.is_empty() {
+        return None;
+    }
+    Some(inner.split('.').map(str::to_string).collect())
+}
+
+/// Parse `#RGB`, `#RRGGBB` or `#RRGGBBAA` (or an object with a `hex` and `alpha`)
+fn parse_hex(value: &Value) -> Option<(String, [f64; 4])> {
+    let (text, alpha) = match value {
+        Value::String(text) => (text.as_str(), None),
+        Value::Object(object) => (
+            object.get("hex")?.as_str()?,
+            object.get("alpha").and_then(Value::as_f64),
+        ),
+        _ => return None,
+    };
+    let digits = text.trim().strip_prefix('#')?;
+    let expanded: String = match digits.len() {
+        3 | 4 => digits.chars().flat_map(|c| [c, c]).collect(),
+        6 | 8 => digits.to_string(),
+        _ => return None,
+    };
+    let channel = |index: usize| u8::from_str_radix(&expanded[index..index + 2], 16).ok();
+    let (r, g, b) = (channel(0)?, channel(2)?, channel(4)?);
+    let a = match expanded.len() {
+        8 => f64::from(channel(6)?) / 255.0,
+        _ => alpha.unwrap_or(1.0),
+    };
+    Some((
+        format!("#{:02X}{:02X}{:02X}", r, g, b),
+        [
+            f64::from(r) / 255.0,
+            f64::from(g) / 255.0,
+            f64::from(b) / 255.0,
+            a,
+        ],
+    ))
+}
+
+fn parse_color(name: &str, value: &Value, description: Option<String>) -> Option<ColorToken> {
+    let (hex, rgba) = parse_hex(value)?;
+    Some(ColorToken {
+        name: name.to_string(),
+        rgb: [
+            (rgba[0] * 255.0).round() as u8,
+            (rgba[1] * 255.0).round() as u8,
+            (rgba[2] * 255.0).round() as u8,
+        ],
+        hex,
+        rgba,
+        description,
+    })
+}
+
+/// Dimension in px from `"16px"`, a bare number or `{ "value": 16, "unit": "px" }`
+fn parse_dimension(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(text) => text.trim().trim_end_matches("px").trim().parse().ok(),
+        Value::Object(object) => {
+            let unit = object.get("unit").and_then(Value::as_str).unwrap_or("px");
+            (unit == "px")
+                .then(|| object.get("value").and_then(Value::as_f64))
+                .flatten()
+        }
+        _ => None,
+    }
+}
+
+fn round_px(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+fn parse_typography(
+    name: String,
+    value: &Value,
+    extension: Option<&Value>,
+    description: Option<String>,
+) -> TypographyToken {
+    let size = value.get("fontSize").and_then(parse_dimension);
+    let ratio = value.get("lineHeight").and_then(Value::as_f64);
+    let line_height = value
+        .get("lineHeight")
+        .and_then(|line_height| match line_height {
+            Value::Number(_) => size.zip(ratio).map(|(size, ratio)| round_px(size * ratio)),
+            other => parse_dimension(other),
+        });
+    let extra = |key: &str| extension.and_then(|fgm| fgm.get(key)).cloned();
+    let line_height_unit: Option<LineHeightUnit> =
+        extra("lineHeightUnit").and_then(|unit| serde_json::from_value(unit).ok());
+    TypographyToken {
+        name,
+        description,
+        family: value
+            .get("fontFamily")
+            .and_then(|family| match family {
+                Value::Array(stack) => stack.first(),
+                other => Some(other),
+            })
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        size,
+        weight: value.get("fontWeight").and_then(|weight| match weight {
+            Value::String(text) => text.parse().ok(),
+            other => other.as_f64(),
+        }),
+        line_height,
+        line_height_unit,
+        line_height_percent: ratio
+            .filter(|_| line_height_unit == Some(LineHeightUnit::Percent))
+            .map(|ratio| round_px(ratio * 100.0)),
+        letter_spacing: value.get("letterSpacing").and_then(parse_dimension),
+        post_script_name: extra("postScriptName")
+            .and_then(|name| name.as_str().map(str::to_string)),
+        italic: extra("italic").and_then(|italic| italic.as_bool()) == Some(true),
+        paragraph_spacing: extra("paragraphSpacing").and_then(|spacing| parse_dimension(&spacing)),
+        text_case: extra("textCase").and_then(|case| serde_json::from_value(case).ok()),
+        text_decoration: extra("textDecoration")
+            .and_then(|decoration| serde_json::from_value(decoration).ok()),
+        font_features: extra("fontFeatures")
+            .and_then(|features| serde_json::from_value(features).ok())
+            .unwrap_or_default(),
+    }
+}
+
+fn parse_shadow_layer(value: &Value) -> Option<ShadowLayer> {
+    let (hex, rgba) = parse_hex(value.get("color")?)?;
+    let length = |key: &str| value.get(key).and_then(parse_dimension).unwrap_or(0.0);
+    Some(ShadowLayer {
+        inset: value.get("inset").and_then(Value::as_bool).unwrap_or(false),
+        hex,
+        rgba,
+        offset_x: length("offsetX"),
+        offset_y: length("offsetY"),
+        blur: length("blur"),
+        spread: length("spread"),
+    })
+}
+
+fn parse_gradient_stop(value: &Value) -> Option<GradientStop> {
+    let (hex, rgba) = parse_hex(value.get("color")?)?;
+    Some(GradientStop {
+        position: value.get("position").and_then(Value::as_f64).unwrap_or(0.0),
+        hex,
+        rgba,
+    })
+}
+
+fn read_variables(group: &Value) -> Result<Vec<VariableCollectionTokens>> {
+    let collections = group
+        .as_object()
+        .ok_or_else(|| anyhow!("The variables group must be an object"))?;
+    let mut result = Vec::new();
+    for (collection_name, collection) in collections {
+        if collection_name.starts_with('$') {
+            continue;
+        }
+        let Some(mode_groups) = collection.as_object() else {
+            continue;
+        };
+        let modes: Vec<String> = match collection
+            .pointer(&format!("/$extensions/{}/modes", EXTENSION))
+            .and_then(Value::as_array)
+        {
+            Some(modes) => modes
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect(),
+            None => mode_groups
+                .keys()
+                .filter(|key| !key.starts_with('$'))
+                .cloned()
+                .collect(),
+        };
+
+        let mut variables: BTreeMap<String, VariableToken> = BTreeMap::new();
+        for mode in &modes {
+            let Some(entries) = mode_groups.get(&group_key(mode)) else {
+                continue;
+            };
+            let mut leaves = Vec::new();
+            collect_leaves(entries, Vec::new(), None, &mut leaves);
+            for leaf in leaves {
+                let (name, _) = token_name(&leaf.path, "");
+                let token_type = match leaf.token_type.as_deref() {
+                    Some("color") => VariableTokenType::Color,
+                    Some("dimension") | Some("number") => VariableTokenType::Number,
+                    Some("boolean") => VariableTokenType::Boolean,
+                    _ => VariableTokenType::String,
+                };
+                let Some(value) = parse_variable_value(token_type, &leaf.value) else {
+                    continue;
+                };
+                let scopes = leaf
+                    .extensions
+                    .as_ref()
+                    .and_then(|extensions| extensions.pointer(&format!("/{}/scopes", EXTENSION)))
+                    .and_then(Value::as_array)
+                    .map(|scopes| {
+                        scopes
+                            .iter()
+                            .filter_map(Value::as_str)
+                            .map(str::to_string)
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                variables
+                    .entry(name.clone())
+                    .or_insert_with(|| VariableToken {
+                        name,
+                        token_type,
+                        description: leaf.description,
+                        scopes,
+                        values: BTreeMap::new(),
+                    })
+                    .values
+                    .insert(mode.clone(), value);
+            }
+        }
+
+        result.push(VariableCollectionTokens {
+            name: collection_name.clone(),
+            modes,
+            variables: variables.into_values().collect(),
+        });
+    }
+    Ok(result)
+}
+
+fn parse_variable_value(
+    token_type: VariableTokenType,
+    value: &Value,
+) -> Option<VariableTokenValue> {
+    if let Some(path) = value.as_str().and_then(alias_path) {
+        if path.len() < 4 || path[0] != VARIABLES_GROUP {
+            return None;
+        }
+        let (name, _) = token_name(&path[3..], "");
+        return Some(VariableTokenValue::Alias {
+            alias: VariableReference {
+                collection: path[1].clone(),
+                mode: path[2].clone(),
+                name,
+            },
+        });
+    }
+    Some(match token_type {
+        VariableTokenType::Color => {
+            let (hex, rgba) = parse_hex(value)?;
+            VariableTokenValue::Color { hex, rgba }
+        }
+        VariableTokenType::Number => VariableTokenValue::Number(parse_dimension(value)?),
+        VariableTokenType::Boolean => VariableTokenValue::Boolean(value.as_bool()?),
+        VariableTokenType::String => VariableTokenValue::String(value.as_str()?.to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::tokens::{SpacingScale, TextCase};
+
+    fn tokens() -> DesignTokens {
+        DesignTokens {
+            colors: vec![
+                ColorToken {
+                    name: "Primary".to_string(),
+                    hex: "#0000FF".to_string(),
+                    rgb: [0, 0, 255],
+                    rgba: [0.0, 0.0, 1.0, 1.0],
+                    description: None,
+                },
+                ColorToken {
+                    name: "Primary/500".to_string(),
+                    hex: "#FF0000".to_string(),
+                    rgb: [255, 0, 0],
+                    rgba: [1.0, 0.0, 0.0, 0.5],
+                    description: Some("Brand".to_string()),
+                },
+            ],
+            unnamed_colors: vec![ColorToken {
+                name: "color-00ff00".to_string(),
+                hex: "#00FF00".to_string(),
+                rgb: [0, 255, 0],
+                rgba: [0.0, 1.0, 0.0, 1.0],
+                description: None,
+            }],
+            typography: vec![TypographyToken {
+                name: "Heading/Large".to_string(),
+                description: None,
+                family: Some("Inter".to_string()),
+                size: Some(14.0),
+                weight: Some(700.0),
+                italic: true,
+                line_height: Some(20.0),
+                letter_spacing: Some(-0.5),
+                text_case: Some(TextCase::SmallCaps),
+                font_features: BTreeMap::from([("TNUM".to_string(), 1)]),
+                ..Default::default()
+            }],
+            unnamed_typography: Vec::new(),
+            spacing: SpacingScale {
+                base: Some(4.0),
+                values: vec![SpacingToken {
+                    name: "space-4".to_string(),
+                    value: 16.0,
+                    step: Some(4),
+                    usage: 3,
+                    sources: vec!["padding".to_string()],
+                }],
+            },
+            shadows: vec![ShadowToken {
+                name: "Elevation/1".to_string(),
+                description: None,
+                layers: vec![ShadowLayer {
+                    inset: false,
+                    hex: "#000000".to_string(),
+                    rgba: [0.0, 0.0, 0.0, 0.2],
+                    offset_x: 0.0,
+                    offset_y: 2.0,
+                    blur: 4.0,
+                    spread: 0.0,
+                }],
+            }],
+            blurs: vec![BlurToken {
+                name: "Glass".to_string(),
+                description: None,
+                kind: BlurKind::Background,
+                radius: 24.0,
+            }],
+            gradients: vec![GradientToken {
+                name: "Brand/Sunset".to_string(),
+                description: None,
+                kind: GradientKind::Angular,
+                handles: vec![[0.5, 0.5], [1.0, 0.5], [0.5, 1.0]],
+                stops: vec![
+                    GradientStop {
+                        position: 0.0,
+                        hex: "#FF0000".to_string(),
+                        rgba: [1.0, 0.0, 0.0, 1.0],
+                    },
+                    GradientStop {
+                        position: 1.0,
+                        hex: "#0000FF".to_string(),
+                        rgba: [0.0, 0.0, 1.0, 1.0],
+                    },
+                ],
+            }],
+            radii: vec![DimensionToken {
+                name: "radius-8".to_string(),
+                value: 8.0,
+                usage: 2,
+            }],
+            stroke_widths: vec![DimensionToken {
+                name: "stroke-1".to_string(),
+                value: 1.0,
+                usage: 5,
+            }],
+            variables: vec![VariableCollectionTokens {
+                name: "Theme".to_string(),
+                modes: vec!["Light".to_string(), "Dark".to_string()],
+                variables: vec![
+                    VariableToken {
+                        name: "bg/page".to_string(),
+                        token_type: VariableTokenType::Color,
+                        description: None,
+                        scopes: Vec::new(),
+                        values: BTreeMap::from([(
+                            "Light".to_string(),
+                            VariableTokenValue::Alias {
+                                alias: VariableReference {
+                                    collection: "Theme".to_string(),
+                                    mode: "Light".to_string(),
+                                    name: "bg/surface".to_string(),
+                                },
+                            },
+                        )]),
+                    },
+                    VariableToken {
+                        name: "bg/surface".to_string(),
+                        token_type: VariableTokenType::Color,
+                        description: None,
+                        scopes: Vec::new(),
+                        values: BTreeMap::from([
+                            (
+                                "Light".to_string(),
+                                VariableTokenValue::Color {
+                                    hex: "#FFFFFF".to_string(),
+                                    rgba: [1.0, 1.0, 1.0, 1.0],
+                                },
+                            ),
+                            (
+                                "Dark".to_string(),
+                                VariableTokenValue::Color {
+                                    hex: "#000000".to_string(),
+                                    rgba: [0.0, 0.0, 0.0, 1.0],
+                                },
+                            ),
+                        ]),
+                    },
+                ],
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn export_writes_typed_nested_tokens() {
+        let json: Value =
+            serde_json::from_str(&export_dtcg(&tokens()).expect("export")).expect("json");
+
+        assert_eq!(json["color"]["Primary"]["DEFAULT"]["$value"], "#0000FF");
+        assert_eq!(json["color"]["Primary"]["500"]["$value"], "#FF000080");
+        assert_eq!(json["color"]["Primary"]["500"]["$description"], "Brand");
+        assert_eq!(json["color"]["unnamed"]["color-00ff00"]["$type"], "color");
+        let heading = &json["typography"]["Heading"]["Large"];
+        assert_eq!(heading["$type"], "typography");
+        assert_eq!(heading["$value"]["fontSize"], "14px");
+        assert_eq!(heading["$value"]["lineHeight"], 1.4286);
+        assert_eq!(json["spacing"]["space-4"]["$value"], "16px");
+        assert_eq!(
+            json["shadow"]["Elevation"]["1"]["$value"]["color"],
+            "#00000033"
+        );
+        let sunset = &json["gradient"]["Brand"]["Sunset"];
+        assert_eq!(sunset["$type"], "gradient");
+        assert_eq!(sunset["$value"][1]["color"], "#0000FF");
+        assert_eq!(sunset["$extensions"]["fgm"]["kind"], "angular");
+        assert_eq!(json["blur"]["Glass"]["$value"], "12px");
+        assert_eq!(
+            json["blur"]["Glass"]["$extensions"]["fgm"]["kind"],
+            "background"
+        );
+        assert_eq!(json["radius"]["radius-8"]["$value"], "8px");
+        assert_eq!(json["stroke-width"]["stroke-1"]["$value"], "1px");
+        assert_eq!(
+            json["variables"]["Theme"]["Light"]["bg"]["page"]["$value"],
+            "{variables.Theme.Light.bg.surface}"
+        );
+    }
+
+    #[test]
+    fn read_round_trips_an_export() {
+        let original = tokens();
+        let read = read_dtcg(&export_dtcg(&original).expect("export")).expect("read");
+
+        let colors: Vec<(&str, &str, f64)> = read
+            .colors
+            .iter()
+            .map(|t| (t.name.as_str(), t.hex.as_str(), (t.rgba[3] * 100.0).round()))
+            .collect();
+        assert_eq!(
+            colors,
+            vec![
+                ("Primary", "#0000FF", 100.0),
+                ("Primary/500", "#FF0000", 50.0)
+            ]
+        );
+        assert_eq!(read.colors[1].description.as_deref(), Some("Brand"));
+        assert_eq!(read.unnamed_colors[0].name, "color-00ff00");
+
+        let heading = &read.typography[0];
+        assert_eq!(heading.name, "Heading/Large");
+        assert_eq!(heading.family.as_deref(), Some("Inter"));
+        assert_eq!(heading.line_height, Some(20.0));
+        assert_eq!(heading.letter_spacing, Some(-0.5));
+        assert!(heading.italic);
+        assert_eq!(heading.text_case, Some(TextCase::SmallCaps));
+        assert_eq!(heading.font_features["TNUM"], 1);
+
+        assert_eq!(read.spacing.base, Some(4.0));
+        assert_eq!(read.spacing.values[0].step, Some(4));
+        assert_eq!(read.blurs, original.blurs);
+        assert_eq!(read.gradients, original.gradients);
+        assert_eq!(read.radii[0].name, "radius-8");
+        assert_eq!(read.stroke_widths[0].value, 1.0);
+        assert_eq!(read.shadows[0].name, "Elevation/1");
+        assert_eq!(read.shadows[0].layers[0].offset_y, 2.0);
+
+        let theme = &read.variables[0];
+        assert_eq!(theme.modes, vec!["Light", "Dark"]);
+        assert_eq!(theme.variables, original.variables[0].variables[..]);
+    }
+
+    #[test]
+    fn read_resolves_references_between_tokens() {
+        let json = r##"{
+            "base": { "$type": "color", "blue": { "$value": "#00f" } },
+            "brand": { "$type": "color", "$value": "{base.blue}" },
+            "size": { "md": { "$type": "dimension", "$value": "16px" } },
+            "body": { "$type": "typography", "$value": { "fontFamily": ["Inter", "sans-serif"], "fontSize": "{size.md}", "lineHeight": 1.5 } }
+        }"##;
+
+        let read = read_dtcg(json).expect("read");
+
+        let names: Vec<(&str, &str)> = read
+            .colors
+            .iter()
+            .map(|t| (t.name.as_str(), t.hex.as_str()))
+            .collect();
+        assert_eq!(names, vec![("base/blue", "#0000FF"), ("brand", "#0000FF")]);
+        assert_eq!(read.typography[0].size, Some(16.0));
+        assert_eq!(read.typography[0].line_height, Some(24.0));
+        assert!(read_dtcg(r#"{ "a": { "$type": "color", "$value": "{missing}" } }"#).is_err());
+    }
+}
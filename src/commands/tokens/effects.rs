@@ -0,0 +1,851 @@
+//! Shadow, blur, corner radius and stroke width tokens
+//!
+//! Shadows and blurs come from effect styles (named) and from raw node
+//! effects (unnamed). Radii and stroke widths are value scales, like spacing.
+//! Figma blur radii are twice the CSS, SwiftUI and Compose blur radius, so
+//! code formats get half the Figma value; the JSON export keeps it as-is.
+
+use super::{
+    css_color, format_px, insert_token_path, kotlin_color, name_px, names_from_values,
+    sanitize_token_name, style_path, to_pascal_case, DesignTokens, UNNAMED_GROUP,
+};
+use crate::api::types::{Color, Document, Effect, Node, Vector};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Drop and inner shadows of an effect style or a node
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShadowToken {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Shadow layers in Figma's effect order
+    pub layers: Vec<ShadowLayer>,
+}
+
+/// One shadow of a (possibly layered) shadow token
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShadowLayer {
+    pub inset: bool,
+    pub hex: String,
+    pub rgba: [f64; 4],
+    pub offset_x: f64,
+    pub offset_y: f64,
+    pub blur: f64,
+    pub spread: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BlurKind {
+    /// LAYER_BLUR: blurs the layer itself
+    Layer,
+    /// BACKGROUND_BLUR: blurs what is behind the layer
+    Background,
+}
+
+/// A layer or background blur
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlurToken {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub kind: BlurKind,
+    /// Figma blur radius in px
+    pub radius: f64,
+}
+
+/// A distinct corner radius or stroke width
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DimensionToken {
+    pub name: String,
+    /// Value in px
+    pub value: f64,
+    /// Number of nodes using the value
+    pub usage: usize,
+}
+
+pub(super) fn shadow_layer(effect: &Effect) -> Option<ShadowLayer> {
+    let inset = match effect.effect_type.as_str() {
+        "DROP_SHADOW" => false,
+        "INNER_SHADOW" => true,
+        _ => return None,
+    };
+    if !effect.visible {
+        return None;
+    }
+    let color = effect.color.clone().unwrap_or(Color {
+        r: 0.0,
+        g: 0.0,
+        b: 0.0,
+        a: 0.25,
+    });
+    let offset = effect.offset.unwrap_or(Vector { x: 0.0, y: 0.0 });
+    Some(ShadowLayer {
+        inset,
+        hex: color.to_hex(),
+        rgba: [color.r, color.g, color.b, color.a],
+        offset_x: offset.x,
+        offset_y: offset.y,
+        blur: effect.radius,
+        spread: effect.spread.unwrap_or(0.0),
+    })
+}
+
+/// Kind and radius of a visible blur effect
+pub(super) fn blur_effect(effect: &Effect) -> Option<(BlurKind, f64)> {
+    let kind = match effect.effect_type.as_str() {
+        "LAYER_BLUR" => BlurKind::Layer,
+        "BACKGROUND_BLUR" => BlurKind::Background,
+        _ => return None,
+    };
+    (effect.visible && effect.radius > 0.0).then_some((kind, effect.radius))
+}
+
+/// Shadows and blurs on nodes without an effect style, minus values a style already covers
+///
+/// Shadows are ordered by elevation and named after a hash of their layers
+/// (`shadow-5d41402abc4b2a76`), so a name only changes with the shadow itself; blurs
+/// are named after their radius.
+pub(super) fn extract_unnamed_effects(
+    document: &Document,
+    shadows: &[ShadowToken],
+    blurs: &[BlurToken],
+) -> (Vec<ShadowToken>, Vec<BlurToken>) {
+    let mut shadow_layers: Vec<Vec<ShadowLayer>> = Vec::new();
+    let mut blur_values: Vec<(BlurKind, f64)> = Vec::new();
+    for node in document.children.iter().flatten() {
+        collect_unnamed_effects(node, &mut shadow_layers, &mut blur_values);
+    }
+
+    shadow_layers.retain(|layers| !shadows.iter().any(|token| &token.layers == layers));
+    shadow_layers.sort_by(|a, b| {
+        let key = |layers: &[ShadowLayer]| {
+            layers
+                .iter()
+                .map(|layer| (layer.blur, layer.offset_y))
+                .fold((0.0, 0.0), |acc: (f64, f64), v| {
+                    (acc.0.max(v.0), acc.1.max(v.1))
+                })
+        };
+        let (a, b) = (key(a), key(b));
+        a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1))
+    });
+    let names = names_from_values("shadow", &shadow_layers);
+    let unnamed_shadows = shadow_layers
+        .into_iter()
+        .zip(names)
+        .map(|(layers, name)| ShadowToken {
+            name,
+            description: None,
+            layers,
+        })
+        .collect();
+
+    blur_values.retain(|(kind, radius)| {
+        !blurs
+            .iter()
+            .any(|token| token.kind == *kind && token.radius == *radius)
+    });
+    blur_values.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.total_cmp(&b.1)));
+    let unnamed_blurs = blur_values
+        .into_iter()
+        .map(|(kind, radius)| BlurToken {
+            name: match kind {
+                BlurKind::Layer => format!("blur-{}", name_px(radius)),
+                BlurKind::Background => format!("backdrop-blur-{}", name_px(radius)),
+            },
+            description: None,
+            kind,
+            radius,
+        })
+        .collect();
+
+    (unnamed_shadows, unnamed_blurs)
+}
+
+fn collect_unnamed_effects(
+    node: &Node,
+    shadows: &mut Vec<Vec<ShadowLayer>>,
+    blurs: &mut Vec<(BlurKind, f64)>,
+) {
+    let styled = node
+        .styles
+        .as_ref()
+        .is_some_and(|styles| styles.contains_key("effect"));
+    if let (false, Some(effects)) = (styled, &node.effects) {
+        let layers: Vec<ShadowLayer> = effects.iter().filter_map(shadow_layer).collect();
+        if !layers.is_empty() && !shadows.contains(&layers) {
+            shadows.push(layers);
+        }
+        for blur in effects.iter().filter_map(blur_effect) {
+            if !blurs.contains(&blur) {
+                blurs.push(blur);
+            }
+        }
+    }
+
+    for child in node.children.iter().flatten() {
+        collect_unnamed_effects(child, shadows, blurs);
+    }
+}
+
+/// Distinct corner radii (`radius-8`), counting each node once per value
+pub fn extract_radii(document: &Document) -> Vec<DimensionToken> {
+    let mut found = BTreeMap::new();
+    for node in document.children.iter().flatten() {
+        collect_dimensions(
+            node,
+            &mut found,
+            &|node| match node.rectangle_corner_radii {
+                Some(corners) => corners.to_vec(),
+                None => node.corner_radius.into_iter().collect(),
+            },
+        );
+    }
+    dimension_tokens(found, "radius")
+}
+
+/// Distinct stroke widths (`stroke-1`) of nodes that have strokes
+pub fn extract_stroke_widths(document: &Document) -> Vec<DimensionToken> {
+    let mut found = BTreeMap::new();
+    for node in document.children.iter().flatten() {
+        collect_dimensions(node, &mut found, &|node| {
+            if node.strokes.as_ref().is_none_or(Vec::is_empty) {
+                return Vec::new();
+            }
+            match &node.individual_stroke_weights {
+                Some(weights) => vec![weights.top, weights.right, weights.bottom, weights.left],
+                None => node.stroke_weight.into_iter().collect(),
+            }
+        });
+    }
+    dimension_tokens(found, "stroke")
+}
+
+fn collect_dimensions(
+    node: &Node,
+    found: &mut BTreeMap<i64, usize>,
+    values: &dyn Fn(&Node) -> Vec<f64>,
+) {
+    let mut keys: Vec<i64> = values(node)
+        .into_iter()
+        .filter(|value| *value > 0.0)
+        // Key on hundredths of a px so float noise doesn't split values
+        .map(|value| (value * 100.0).round() as i64)
+        .collect();
+    keys.sort_unstable();
+    keys.dedup();
+    for key in keys {
+        *found.entry(key).or_default() += 1;
+    }
+
+    for child in node.children.iter().flatten() {
+        collect_dimensions(child, found, values);
+    }
+}
+
+fn dimension_tokens(found: BTreeMap<i64, usize>, prefix: &str) -> Vec<DimensionToken> {
+    found
+        .into_iter()
+        .map(|(key, usage)| {
+            let value = key as f64 / 100.0;
+            DimensionToken {
+                name: format!("{}-{}", prefix, name_px(value)),
+                value,
+                usage,
+            }
+        })
+        .collect()
+}
+
+/// Blur radius for CSS `blur()`, SwiftUI `.blur(radius:)` and Compose `Modifier.blur`
+fn code_blur(radius: f64) -> f64 {
+    radius / 2.0
+}
+
+fn css_shadow(layers: &[ShadowLayer]) -> String {
+    layers
+        .iter()
+        .map(|layer| {
+            format!(
+                "{}{}px {}px {}px {}px {}",
+                if layer.inset { "inset " } else { "" },
+                format_px(layer.offset_x),
+                format_px(layer.offset_y),
+                format_px(layer.blur),
+                format_px(layer.spread),
+                css_color(&layer.hex, &layer.rgba)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Shadows and blurs with their flat names, unnamed tokens keeping their generated names
+fn flat_shadows(tokens: &DesignTokens) -> Vec<(String, &ShadowToken)> {
+    let named = tokens.shadows.iter().map(|token| {
+        (
+            format!("shadow-{}", sanitize_token_name(&token.name)),
+            token,
+        )
+    });
+    let unnamed = tokens
+        .unnamed_shadows
+        .iter()
+        .map(|token| (token.name.clone(), token));
+    named.chain(unnamed).collect()
+}
+
+fn flat_blurs(tokens: &DesignTokens) -> Vec<(String, &BlurToken)> {
+    let named = tokens.blurs.iter().map(|token| {
+        let prefix = match token.kind {
+            BlurKind::Layer => "blur",
+            BlurKind::Background => "backdrop-blur",
+        };
+        (
+            format!("{}-{}", prefix, sanitize_token_name(&token.name)),
+            token,
+        )
+    });
+    let unnamed = tokens
+        .unnamed_blurs
+        .iter()
+        .map(|token| (token.name.clone(), token));
+    named.chain(unnamed).collect()
+}
+
+// CSS: custom properties inside the :root block
+
+pub(super) fn css(tokens: &DesignTokens, css_prefix: &str) -> String {
+    let mut css = String::new();
+    let shadows = flat_shadows(tokens);
+    if !shadows.is_empty() {
+        css.push_str("\n  /* Shadows */\n");
+        for (name, token) in shadows {
+            css.push_str(&format!(
+                "  {}{}: {};\n",
+                css_prefix,
+                name,
+                css_shadow(&token.layers)
+            ));
+        }
+    }
+    let blurs = flat_blurs(tokens);
+    if !blurs.is_empty() {
+        css.push_str("\n  /* Blurs (filter / backdrop-filter) */\n");
+        for (name, token) in blurs {
+            css.push_str(&format!(
+                "  {}{}: blur({}px);\n",
+                css_prefix,
+                name,
+                format_px(code_blur(token.radius))
+            ));
+        }
+    }
+    for (title, values) in [
+        ("Corner radii", &tokens.radii),
+        ("Stroke widths", &tokens.stroke_widths),
+    ] {
+        if values.is_empty() {
+            continue;
+        }
+        css.push_str(&format!("\n  /* {} */\n", title));
+        for token in values {
+            css.push_str(&format!(
+                "  {}{}: {}px;\n",
+                css_prefix,
+                token.name,
+                format_px(token.value)
+            ));
+        }
+    }
+    css
+}
+
+// SwiftUI: shadow layers applied through a view modifier, CGFloat scales
+
+pub(super) fn swift(tokens: &DesignTokens, type_prefix: &str) -> String {
+    let mut swift = String::new();
+    let shadows = flat_shadows(tokens);
+    if !shadows.is_empty() {
+        swift.push_str("\nstruct ShadowToken {\n");
+        swift.push_str("    let color: Color\n");
+        swift.push_str("    let radius: CGFloat\n");
+        swift.push_str("    let x: CGFloat\n");
+        swift.push_str("    let y: CGFloat\n");
+        swift.push_str("}\n\n");
+        swift.push_str("struct ShadowTokenModifier: ViewModifier {\n");
+        swift.push_str("    let layers: [ShadowToken]\n\n");
+        swift.push_str("    func body(content: Content) -> some View {\n");
+        swift.push_str("        layers.reduce(AnyView(content)) { view, layer in\n");
+        swift.push_str(
+            "            AnyView(view.shadow(color: layer.color, radius: layer.radius, x: layer.x, y: layer.y))\n",
+        );
+        swift.push_str("        }\n    }\n}\n\n");
+        swift.push_str("extension View {\n");
+        swift.push_str("    func shadow(_ layers: [ShadowToken]) -> some View {\n");
+        swift.push_str("        modifier(ShadowTokenModifier(layers: layers))\n");
+        swift.push_str("    }\n}\n\n");
+
+        swift.push_str(&format!("enum {}Shadows {{\n", type_prefix));
+        for (name, token) in shadows {
+            let layers: Vec<String> = token
+                .layers
+                .iter()
+                // View.shadow has no inner shadows
+                .filter(|layer| !layer.inset)
+                .map(|layer| {
+                    format!(
+                        "ShadowToken(color: Color(red: {:.3}, green: {:.3}, blue: {:.3}, opacity: {:.3}), radius: {}, x: {}, y: {})",
+                        layer.rgba[0],
+                        layer.rgba[1],
+                        layer.rgba[2],
+                        layer.rgba[3],
+                        format_px(code_blur(layer.blur)),
+                        format_px(layer.offset_x),
+                        format_px(layer.offset_y)
+                    )
+                })
+                .collect();
+            swift.push_str(&format!(
+                "    static let {}: [ShadowToken] = [{}]\n",
+                to_pascal_case(&name),
+                layers.join(", ")
+            ));
+        }
+        swift.push_str("}\n");
+    }
+
+    let blurs = flat_blurs(tokens);
+    let scales = [
+        ("Blur", blurs_as_dimensions(&blurs)),
+        ("Radius", tokens.radii.iter().map(as_pair).collect()),
+        (
+            "StrokeWidth",
+            tokens.stroke_widths.iter().map(as_pair).collect(),
+        ),
+    ];
+    for (kind, values) in scales {
+        if values.is_empty() {
+            continue;
+        }
+        swift.push_str(&format!("\nenum {}{} {{\n", type_prefix, kind));
+        for (name, value) in values {
+            swift.push_str(&format!(
+                "    static let {}: CGFloat = {}\n",
+                to_pascal_case(&name),
+                format_px(value)
+            ));
+        }
+        swift.push_str("}\n");
+    }
+    swift
+}
+
+fn as_pair(token: &DimensionToken) -> (String, f64) {
+    (token.name.clone(), token.value)
+}
+
+fn blurs_as_dimensions(blurs: &[(String, &BlurToken)]) -> Vec<(String, f64)> {
+    blurs
+        .iter()
+        .map(|(name, token)| (name.clone(), code_blur(token.radius)))
+        .collect()
+}
+
+// Compose: Modifier.shadow layers and Dp scales
+
+/// Imports the Compose output needs on top of the color and dp imports
+pub(super) fn kotlin_imports(tokens: &DesignTokens) -> &'static str {
+    if tokens.shadows.is_empty() && tokens.unnamed_shadows.is_empty() {
+        ""
+    } else {
+        "import androidx.compose.ui.Modifier\nimport androidx.compose.ui.draw.shadow\nimport androidx.compose.ui.graphics.RectangleShape\nimport androidx.compose.ui.graphics.Shape\nimport androidx.compose.ui.unit.Dp\n"
+    }
+}
+
+pub(super) fn kotlin(tokens: &DesignTokens, type_prefix: &str) -> String {
+    let mut kotlin = String::new();
+    let shadows = flat_shadows(tokens);
+    if !shadows.is_empty() {
+        kotlin.push_str("\ndata class ShadowToken(\n");
+        kotlin.push_str("    val color: Color,\n");
+        kotlin.push_str("    val offsetX: Dp,\n");
+        kotlin.push_str("    val offsetY: Dp,\n");
+        kotlin.push_str("    val blur: Dp,\n");
+        kotlin.push_str("    val spread: Dp\n");
+        kotlin.push_str(") {\n");
+        kotlin.push_str("    /** Approximate elevation for Modifier.shadow */\n");
+        kotlin.push_str("    val elevation: Dp get() = blur / 2\n");
+        kotlin.push_str("}\n\n");
+        kotlin.push_str(
+            "fun Modifier.shadow(layers: List<ShadowToken>, shape: Shape = RectangleShape): Modifier =\n",
+        );
+        kotlin.push_str("    layers.fold(this) { modifier, layer ->\n");
+        kotlin.push_str(
+            "        modifier.shadow(layer.elevation, shape, ambientColor = layer.color, spotColor = layer.color)\n",
+        );
+        kotlin.push_str("    }\n\n");
+
+        kotlin.push_str(&format!("object {}Shadows {{\n", type_prefix));
+        for (name, token) in shadows {
+            let layers: Vec<String> = token
+                .layers
+                .iter()
+                // Modifier.shadow has no inner shadows
+                .filter(|layer| !layer.inset)
+                .map(|layer| {
+                    format!(
+                        "ShadowToken({}, {}.dp, {}.dp, {}.dp, {}.dp)",
+                        kotlin_color(&layer.hex, layer.rgba[3]),
+                        format_px(layer.offset_x),
+                        format_px(layer.offset_y),
+                        format_px(layer.blur),
+                        format_px(layer.spread)
+                    )
+                })
+                .collect();
+            kotlin.push_str(&format!(
+                "    val {} = listOf({})\n",
+                to_pascal_case(&name),
+                layers.join(", ")
+            ));
+        }
+        kotlin.push_str("}\n");
+    }
+
+    let blurs = flat_blurs(tokens);
+    let scales = [
+        ("Blur", blurs_as_dimensions(&blurs)),
+        ("Radius", tokens.radii.iter().map(as_pair).collect()),
+        (
+            "StrokeWidth",
+            tokens.stroke_widths.iter().map(as_pair).collect(),
+        ),
+    ];
+    for (kind, values) in scales {
+        if values.is_empty() {
+            continue;
+        }
+        kotlin.push_str(&format!("\nobject {}{} {{\n", type_prefix, kind));
+        for (name, value) in values {
+            kotlin.push_str(&format!(
+                "    val {} = {}.dp\n",
+                to_pascal_case(&name),
+                format_px(value)
+            ));
+        }
+        kotlin.push_str("}\n");
+    }
+    kotlin
+}
+
+// Tailwind: theme.extend sections, keyed without the category prefix
+
+pub(super) fn tailwind(tokens: &DesignTokens) -> String {
+    let mut output = String::new();
+    let short = |name: &str, prefix: &str| -> String {
+        name.strip_prefix(prefix)
+            .filter(|rest| !rest.is_empty())
+            .unwrap_or(name)
+            .to_string()
+    };
+
+    let mut sections: Vec<(&str, Vec<(String, String)>)> = Vec::new();
+    sections.push((
+        "boxShadow",
+        flat_shadows(tokens)
+            .into_iter()
+            .map(|(name, token)| (short(&name, "shadow-"), css_shadow(&token.layers)))
+            .collect(),
+    ));
+    let blurs = flat_blurs(tokens);
+    for (section, kind, prefix) in [
+        ("blur", BlurKind::Layer, "blur-"),
+        ("backdropBlur", BlurKind::Background, "backdrop-blur-"),
+    ] {
+        sections.push((
+            section,
+            blurs
+                .iter()
+                .filter(|(_, token)| token.kind == kind)
+                .map(|(name, token)| {
+                    (
+                        short(name, prefix),
+                        format!("{}px", format_px(code_blur(token.radius))),
+                    )
+                })
+                .collect(),
+        ));
+    }
+    for (section, values, prefix) in [
+        ("borderRadius", &tokens.radii, "radius-"),
+        ("borderWidth", &tokens.stroke_widths, "stroke-"),
+    ] {
+        sections.push((
+            section,
+            values
+                .iter()
+                .map(|token| {
+                    (
+                        short(&token.name, prefix),
+                        format!("{}px", format_px(token.value)),
+                    )
+                })
+                .collect(),
+        ));
+    }
+
+    for (section, entries) in sections {
+        if entries.is_empty() {
+            continue;
+        }
+        output.push_str(&format!("      {}: {{\n", section));
+        for (key, value) in entries {
+            output.push_str(&format!("        '{}': '{}',\n", key, value));
+        }
+        output.push_str("      },\n");
+    }
+    output
+}
+
+// Style Dictionary: shadow/blur groups nested by style path, flat scales
+
+pub(super) fn style_dictionary(tokens: &DesignTokens, root: &mut serde_json::Value) {
+    let mut shadow = serde_json::Map::new();
+    for token in &tokens.shadows {
+        insert_token_path(
+            &mut shadow,
+            &style_path(&token.name),
+            serde_json::json!({ "value": css_shadow(&token.layers) }),
+        );
+    }
+    for token in &tokens.unnamed_shadows {
+        insert_token_path(
+            &mut shadow,
+            &[UNNAMED_GROUP.to_string(), token.name.clone()],
+            serde_json::json!({ "value": css_shadow(&token.layers) }),
+        );
+    }
+
+    let mut blur = serde_json::Map::new();
+    for token in &tokens.blurs {
+        let mut path = style_path(&token.name);
+        if token.kind == BlurKind::Background {
+            path.insert(0, "backdrop".to_string());
+        }
+        insert_token_path(&mut blur, &path, blur_value(token));
+    }
+    for token in &tokens.unnamed_blurs {
+        insert_token_path(
+            &mut blur,
+            &[UNNAMED_GROUP.to_string(), token.name.clone()],
+            blur_value(token),
+        );
+    }
+
+    let scale = |values: &[DimensionToken]| -> serde_json::Map<String, serde_json::Value> {
+        values
+            .iter()
+            .map(|token| {
+                (
+                    token.name.clone(),
+                    serde_json::json!({ "value": format!("{}px", format_px(token.value)) }),
+                )
+            })
+            .collect()
+    };
+
+    for (key, group) in [
+        ("shadow", shadow),
+        ("blur", blur),
+        ("radius", scale(&tokens.radii)),
+        ("stroke-width", scale(&tokens.stroke_widths)),
+    ] {
+        if !group.is_empty() {
+            root[key] = serde_json::Value::Object(group);
+        }
+    }
+}
+
+fn blur_value(token: &BlurToken) -> serde_json::Value {
+    serde_json::json!({ "value": format!("{}px", format_px(code_blur(token.radius))) })
+}
+
+// Android: elevation and dimen resources
+
+pub(super) fn android_resources(tokens: &DesignTokens) -> String {
+    let mut xml = String::new();
+    for (name, token) in flat_shadows(tokens) {
+        // Android elevation approximates the shadow by its largest blur
+        let blur = token
+            .layers
+            .iter()
+            .filter(|layer| !layer.inset)
+            .map(|layer| layer.blur)
+            .fold(0.0, f64::max);
+        xml.push_str(&format!(
+            "  <dimen name=\"elevation-{}\">{}dp</dimen>\n",
+            name.strip_prefix("shadow-").unwrap_or(&name),
+            format_px(code_blur(blur))
+        ));
+    }
+    for (name, token) in flat_blurs(tokens) {
+        xml.push_str(&format!(
+            "  <dimen name=\"{}\">{}dp</dimen>\n",
+            name,
+            format_px(code_blur(token.radius))
+        ));
+    }
+    for token in tokens.radii.iter().chain(&tokens.stroke_widths) {
+        xml.push_str(&format!(
+            "  <dimen name=\"{}\">{}dp</dimen>\n",
+            token.name,
+            format_px(token.value)
+        ));
+    }
+    xml
+}
+
+// DTCG: shadow composites plus dimension groups (read back by dtcg::read_dtcg)
+
+/// DTCG blur value in code units; `read_dtcg` doubles it back to the Figma radius
+pub(super) fn dtcg_blur(token: &BlurToken) -> (f64, &'static str) {
+    let kind = match token.kind {
+        BlurKind::Layer => "layer",
+        BlurKind::Background => "background",
+    };
+    (code_blur(token.radius), kind)
+}
+
+pub(super) fn figma_blur(code_radius: f64) -> f64 {
+    code_radius * 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::ColorSpace;
+    use crate::commands::tokens::{export_css, export_kotlin, export_swift, export_tailwind};
+
+    fn document() -> Document {
+        serde_json::from_value(serde_json::json!({
+            "id": "0:0",
+            "name": "Document",
+            "type": "DOCUMENT",
+            "children": [{
+                "id": "1:1",
+                "name": "Card",
+                "type": "FRAME",
+                "cornerRadius": 8,
+                "strokes": [{ "type": "SOLID", "color": { "r": 0.0, "g": 0.0, "b": 0.0, "a": 1.0 } }],
+                "strokeWeight": 1,
+                "effects": [
+                    { "type": "DROP_SHADOW", "radius": 4, "offset": { "x": 0, "y": 2 },
+                      "color": { "r": 0.0, "g": 0.0, "b": 0.0, "a": 0.2 } },
+                    { "type": "BACKGROUND_BLUR", "radius": 16 }
+                ],
+                "children": [{
+                    "id": "1:2",
+                    "name": "Chip",
+                    "type": "FRAME",
+                    "rectangleCornerRadii": [8, 8, 0, 0],
+                    "strokeWeight": 2,
+                    "effects": [{ "type": "LAYER_BLUR", "radius": 6, "visible": false }]
+                }, {
+                    "id": "1:3",
+                    "name": "Badge",
+                    "type": "FRAME",
+                    "cornerRadius": 999,
+                    "strokes": [{ "type": "SOLID", "color": { "r": 0.0, "g": 0.0, "b": 0.0, "a": 1.0 } }],
+                    "individualStrokeWeights": { "top": 0, "right": 0, "bottom": 1.5, "left": 0 }
+                }]
+            }]
+        }))
+        .expect("document")
+    }
+
+    fn tokens() -> DesignTokens {
+        let document = document();
+        let (unnamed_shadows, unnamed_blurs) = extract_unnamed_effects(&document, &[], &[]);
+        DesignTokens {
+            unnamed_shadows,
+            blurs: vec![BlurToken {
+                name: "Glass".to_string(),
+                description: None,
+                kind: BlurKind::Background,
+                radius: 24.0,
+            }],
+            unnamed_blurs,
+            radii: extract_radii(&document),
+            stroke_widths: extract_stroke_widths(&document),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn extracts_unnamed_effects_and_scales() {
+        let tokens = tokens();
+
+        assert_eq!(tokens.unnamed_shadows.len(), 1);
+        assert!(tokens.unnamed_shadows[0].name.starts_with("shadow-"));
+        assert_eq!(tokens.unnamed_shadows[0].layers[0].offset_y, 2.0);
+        let blurs: Vec<&str> = tokens
+            .unnamed_blurs
+            .iter()
+            .map(|t| t.name.as_str())
+            .collect();
+        assert_eq!(blurs, vec!["backdrop-blur-16"]);
+
+        let radii: Vec<(&str, usize)> = tokens
+            .radii
+            .iter()
+            .map(|t| (t.name.as_str(), t.usage))
+            .collect();
+        assert_eq!(radii, vec![("radius-8", 2), ("radius-999", 1)]);
+        // Chip has no strokes, so its weight is ignored
+        let strokes: Vec<&str> = tokens
+            .stroke_widths
+            .iter()
+            .map(|t| t.name.as_str())
+            .collect();
+        assert_eq!(strokes, vec!["stroke-1", "stroke-1_5"]);
+    }
+
+    #[test]
+    fn code_formats_emit_effects_and_scales() {
+        let tokens = tokens();
+        let shadow = tokens.unnamed_shadows[0].name.clone();
+
+        let css = export_css(&tokens, "", ColorSpace::Hex);
+        assert!(css.contains(&format!(
+            "--{}: 0px 2px 4px 0px rgba(0, 0, 0, 0.2);",
+            shadow
+        )));
+        assert!(css.contains("--backdrop-blur-glass: blur(12px);"));
+        assert!(css.contains("--radius-8: 8px;"));
+        assert!(css.contains("--stroke-1_5: 1.5px;"));
+
+        let swift = export_swift(&tokens, "");
+        assert!(swift.contains(&format!("static let {}: [ShadowToken] = [ShadowToken(color: Color(red: 0.000, green: 0.000, blue: 0.000, opacity: 0.200), radius: 2, x: 0, y: 2)]", to_pascal_case(&shadow))));
+        assert!(swift.contains("static let Radius8: CGFloat = 8"));
+
+        let kotlin = export_kotlin(&tokens, "");
+        assert!(kotlin.contains("import androidx.compose.ui.draw.shadow\n"));
+        assert!(kotlin.contains(&format!(
+            "val {} = listOf(ShadowToken(Color(0x33000000), 0.dp, 2.dp, 4.dp, 0.dp))",
+            to_pascal_case(&shadow)
+        )));
+        assert!(kotlin.contains("val Stroke1 = 1.dp"));
+
+        let tailwind = export_tailwind(&tokens, ColorSpace::Hex);
+        assert!(tailwind.contains(&format!(
+            "      boxShadow: {{\n        '{}': '0px 2px 4px 0px rgba(0, 0, 0, 0.2)',\n",
+            shadow.trim_start_matches("shadow-")
+        )));
+        assert!(tailwind
+            .contains("      backdropBlur: {\n        'glass': '12px',\n        '16': '8px',\n"));
+        assert!(tailwind.contains("      borderRadius: {\n        '8': '8px',\n"));
+    }
+}
@@ -0,0 +1,1816 @@
+mod color;
+mod diff;
+mod dtcg;
+mod effects;
+mod paints;
+mod typography;
+mod variables;
+
+pub use diff::{
+    diff_report, diff_tokens, load_tokens_file, DiffSource, TokenChange, TokenChangeKind,
+};
+pub use dtcg::{export_dtcg, read_dtcg};
+pub use effects::{
+    extract_radii, extract_stroke_widths, BlurKind, BlurToken, DimensionToken, ShadowLayer,
+    ShadowToken,
+};
+pub use paints::{
+    extract_unnamed_paints, GradientKind, GradientStop, GradientToken, ImagePaintToken,
+};
+pub use typography::{kotlin_weight, swift_weight, LineHeightUnit, TextCase, TextDecoration};
+
+pub use variables::{
+    build_variable_tokens, AliasMode, VariableCollectionTokens, VariableReference, VariableToken,
+    VariableTokenType, VariableTokenValue,
+};
+
+use crate::api::types::{Color, Document, File, Node, Paint, Style};
+use crate::api::{ApiError, FigmaClient};
+use crate::auth::get_token;
+use crate::cli::{ColorSpace, TokenFormat, TokensCommands, VariablesSource};
+use crate::config::{Config, TokensConfig};
+use crate::output;
+use crate::util::hash::fnv1a64;
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use color::{format_color, hex_with_alpha};
+
+pub async fn run(command: TokensCommands) -> Result<()> {
+    // Diffing two exported files needs no token, so clients are created per command
+    let client = || -> Result<FigmaClient> { FigmaClient::new(get_token()?) };
+
+    match command {
+        TokensCommands::Colors { file_key } => colors(&client()?, &file_key).await,
+        TokensCommands::Typography { file_key } => typography(&client()?, &file_key).await,
+        TokensCommands::Spacing { file_key } => spacing(&client()?, &file_key).await,
+        TokensCommands::Export {
+            file_key,
+            format,
+            output,
+            variables,
+            preserve_aliases,
+            color_space,
+        } => {
+            let alias_mode = if preserve_aliases {
+                AliasMode::Preserve
+            } else {
+                AliasMode::Resolve
+            };
+            let mut config = Config::load().unwrap_or_default();
+            if let Some(color_space) = color_space {
+                config.tokens.color_space = color_space.name();
+            }
+            export(
+                &client()?,
+                &file_key,
+                format,
+                output,
+                variables,
+                alias_mode,
+                &config,
+            )
+            .await
+        }
+        TokensCommands::Diff {
+            from,
+            to,
+            fail_on_change,
+            report,
+            report_format,
+        } => diff::diff(&from, &to, fail_on_change, report.as_deref(), report_format).await,
+    }
+}
+
+/// A solid color found in the file
+///
+/// Named tokens use the fill style name (`primary/500`); unnamed ones are
+/// derived from the hex value, with alpha for translucent colors
+/// (`color-ff000080`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColorToken {
+    pub name: String,
+    /// `#RRGGBB`; alpha, with paint opacity applied, is in `rgba`
+    pub hex: String,
+    pub rgb: [u8; 3],
+    pub rgba: [f64; 4],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// A distinct text style found in the file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TypographyToken {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub family: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_script_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f64>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub italic: bool,
+    /// Line height in px, as computed by Figma whatever the unit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line_height: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line_height_unit: Option<LineHeightUnit>,
+    /// Line height in % of the font size, when set in percent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line_height_percent: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub letter_spacing: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paragraph_spacing: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text_case: Option<TextCase>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text_decoration: Option<TextDecoration>,
+    /// OpenType feature tags (`TNUM`, `LIGA`, ...) set on or off
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub font_features: BTreeMap<String, u32>,
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+/// A distinct spacing or padding value used by auto-layout frames
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpacingToken {
+    pub name: String,
+    /// Value in px
+    pub value: f64,
+    /// Multiple of the scale base, when the value sits on the scale
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub step: Option<u32>,
+    /// Number of times the value is used
+    pub usage: usize,
+    /// Properties the value was found in (item-spacing, padding, ...)
+    pub sources: Vec<String>,
+}
+
+/// Spacing values with the inferred base unit of the scale
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SpacingScale {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base: Option<f64>,
+    pub values: Vec<SpacingToken>,
+}
+
+/// All tokens extracted from a file, ready for any export format
+///
+/// Also read back from `--format json` exports, so everything but `colors`
+/// and `typography` is optional.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct DesignTokens {
+    /// Colors backed by fill styles, keyed by style name
+    pub colors: Vec<ColorToken>,
+    /// Raw colors not covered by any fill style
+    #[serde(default)]
+    pub unnamed_colors: Vec<ColorToken>,
+    /// Text styles, keyed by style name
+    pub typography: Vec<TypographyToken>,
+    /// Text properties on nodes without a text style
+    #[serde(default)]
+    pub unnamed_typography: Vec<TypographyToken>,
+    #[serde(default)]
+    pub spacing: SpacingScale,
+    /// Shadows backed by effect styles, keyed by style name
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub shadows: Vec<ShadowToken>,
+    /// Shadows on nodes without an effect style
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unnamed_shadows: Vec<ShadowToken>,
+    /// Layer and background blurs backed by effect styles
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blurs: Vec<BlurToken>,
+    /// Blurs on nodes without an effect style
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unnamed_blurs: Vec<BlurToken>,
+    /// Distinct corner radii
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub radii: Vec<DimensionToken>,
+    /// Distinct stroke widths
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stroke_widths: Vec<DimensionToken>,
+    /// Gradients backed by fill styles, keyed by style name
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub gradients: Vec<GradientToken>,
+    /// Gradient fills on nodes without a fill style
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unnamed_gradients: Vec<GradientToken>,
+    /// Image fills, named after their fill style or image hash
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub images: Vec<ImagePaintToken>,
+    /// Figma Variables, one entry per collection
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variables: Vec<VariableCollectionTokens>,
+}
+
+async fn colors(client: &FigmaClient, file_key: &str) -> Result<()> {
+    let file = client.get_file(file_key).await?;
+    output::print_status(
+        &format!("Color Styles from: {}", file.name)
+            .bold()
+            .to_string(),
+    );
+
+    let mut color_styles: Vec<(&String, &str)> = file
+        .styles
+        .iter()
+        .filter(|(_, s)| s.style_type == "FILL")
+        .map(|(k, s)| (k, s.name.as_str()))
+        .collect();
+    color_styles.sort_by(|a, b| a.1.cmp(b.1));
+
+    output::print_status(&format!("\n{}", "Named Styles:".bold()));
+    for (key, name) in &color_styles {
+        output::print_status(&format!("  {} ({})", name.cyan(), key.dimmed()));
+    }
+
+    let mut unique_colors: HashSet<String> = HashSet::new();
+    extract_colors_from_node(&file.document, &mut unique_colors);
+
+    output::print_status(&format!(
+        "\n{}",
+        format!("Unique Colors Found: {}", unique_colors.len()).bold()
+    ));
+    let mut colors: Vec<_> = unique_colors.into_iter().collect();
+    colors.sort();
+    for hex in colors.iter().take(20) {
+        output::print_status(&format!("  {}", hex.cyan()));
+    }
+    if colors.len() > 20 {
+        output::print_status(&format!(
+            "  {} more...",
+            format!("... and {}", colors.len() - 20).dimmed()
+        ));
+    }
+
+    let (gradients, images) = extract_unnamed_paints(&file.document, &[], &[]);
+    if !gradients.is_empty() {
+        output::print_status(&format!(
+            "\n{}",
+            format!("Unique Gradients Found: {}", gradients.len()).bold()
+        ));
+        for gradient in &gradients {
+            output::print_status(&format!("  {}", paints::css_gradient(gradient).cyan()));
+        }
+    }
+    if !images.is_empty() {
+        output::print_status(&format!(
+            "\n{}",
+            format!("Image Fills Found: {}", images.len()).bold()
+        ));
+    }
+
+    Ok(())
+}
+
+fn extract_colors_from_node(node: &impl HasFillsAndChildren, colors: &mut HashSet<String>) {
+    if let Some(fills) = node.fills() {
+        for color in fills.iter().filter_map(Paint::solid_color) {
+            colors.insert(color.to_hex_alpha());
+        }
+    }
+    if let Some(children) = node.children() {
+        for child in children {
+            extract_colors_from_node(child, colors);
+        }
+    }
+}
+
+trait HasFillsAndChildren {
+    fn fills(&self) -> Option<&Vec<crate::api::types::Paint>>;
+    fn children(&self) -> Option<&Vec<Node>>;
+}
+
+impl HasFillsAndChildren for crate::api::types::Document {
+    fn fills(&self) -> Option<&Vec<crate::api::types::Paint>> {
+        None
+    }
+    fn children(&self) -> Option<&Vec<Node>> {
+        self.children.as_ref()
+    }
+}
+
+impl HasFillsAndChildren for Node {
+    fn fills(&self) -> Option<&Vec<crate::api::types::Paint>> {
+        self.fills.as_ref()
+    }
+    fn children(&self) -> Option<&Vec<Node>> {
+        self.children.as_ref()
+    }
+}
+
+async fn typography(client: &FigmaClient, file_key: &str) -> Result<()> {
+    let file = client.get_file(file_key).await?;
+    output::print_status(
+        &format!("Typography Styles from: {}", file.name)
+            .bold()
+            .to_string(),
+    );
+
+    let mut text_styles: Vec<(&String, &str, Option<&str>)> = file
+        .styles
+        .iter()
+        .filter(|(_, s)| s.style_type == "TEXT")
+        .map(|(k, s)| (k, s.name.as_str(), s.description.as_deref()))
+        .collect();
+    text_styles.sort_by(|a, b| a.1.cmp(b.1));
+
+    output::print_status(&format!("\n{}", "Text Styles:".bold()));
+    for (key, name, desc) in &text_styles {
+        let mut line = format!("  {} ({})", name.cyan(), key.dimmed());
+        if let Some(d) = desc {
+            if !d.is_empty() {
+                line.push_str(&format!(" - {}", d.dimmed()));
+            }
+        }
+        output::print_status(&line);
+    }
+
+    let mut unique_fonts: HashSet<String> = HashSet::new();
+    extract_fonts_from_node(&file.document, &mut unique_fonts);
+
+    output::print_status(&format!(
+        "\n{}",
+        format!("Font Families Used: {}", unique_fonts.len()).bold()
+    ));
+    let mut fonts: Vec<_> = unique_fonts.into_iter().collect();
+    fonts.sort();
+    for font in &fonts {
+        output::print_status(&format!("  {}", font.cyan()));
+    }
+
+    Ok(())
+}
+
+fn extract_fonts_from_node(node: &impl HasStyleAndChildren, fonts: &mut HashSet<String>) {
+    if let Some(style) = node.type_style() {
+        if let Some(family) = &style.font_family {
+            fonts.insert(family.clone());
+        }
+    }
+    if let Some(children) = node.node_children() {
+        for child in children {
+            extract_fonts_from_node(child, fonts);
+        }
+    }
+}
+
+trait HasStyleAndChildren {
+    fn type_style(&self) -> Option<&crate::api::types::TypeStyle>;
+    fn node_children(&self) -> Option<&Vec<Node>>;
+    fn node_name(&self) -> Option<String>;
+}
+
+impl HasStyleAndChildren for crate::api::types::Document {
+    fn type_style(&self) -> Option<&crate::api::types::TypeStyle> {
+        None
+    }
+    fn node_children(&self) -> Option<&Vec<Node>> {
+        self.children.as_ref()
+    }
+    fn node_name(&self) -> Option<String> {
+        Some(self.name.clone())
+    }
+}
+
+impl HasStyleAndChildren for Node {
+    fn type_style(&self) -> Option<&crate::api::types::TypeStyle> {
+        self.style.as_ref()
+    }
+    fn node_children(&self) -> Option<&Vec<Node>> {
+        self.children.as_ref()
+    }
+    fn node_name(&self) -> Option<String> {
+        Some(self.name.clone())
+    }
+}
+
+async fn spacing(client: &FigmaClient, file_key: &str) -> Result<()> {
+    let file = client.get_file(file_key).await?;
+    output::print_status(
+        &format!("Spacing Analysis from: {}", file.name)
+            .bold()
+            .to_string(),
+    );
+
+    let scale = extract_spacing_scale(&file.document);
+    if scale.values.is_empty() {
+        output::print_warning("No auto-layout spacing or padding found");
+        return Ok(());
+    }
+
+    match scale.base {
+        Some(base) => output::print_status(&format!(
+            "\n{} {}px",
+            "Inferred scale base:".bold(),
+            format_px(base)
+        )),
+        None => output::print_status(&format!("\n{}", "No consistent scale base found".yellow())),
+    }
+
+    output::print_status(&format!(
+        "\n{}",
+        format!("Spacing Values Found: {}", scale.values.len()).bold()
+    ));
+    for token in &scale.values {
+        output::print_status(&format!(
+            "  {} {} {}",
+            format!("{}px", format_px(token.value)).cyan(),
+            token.name,
+            format!("({} uses: {})", token.usage, token.sources.join(", ")).dimmed()
+        ));
+    }
+
+    Ok(())
+}
+
+impl DesignTokens {
+    /// Named colors followed by unnamed ones
+    pub fn all_colors(&self) -> impl Iterator<Item = &ColorToken> {
+        self.colors.iter().chain(self.unnamed_colors.iter())
+    }
+
+    /// Named text styles followed by unnamed ones
+    pub fn all_typography(&self) -> impl Iterator<Item = &TypographyToken> {
+        self.typography.iter().chain(self.unnamed_typography.iter())
+    }
+}
+
+/// Collect auto-layout spacing and padding values and infer a spacing scale
+///
+/// Zero and negative values are ignored. The base is the largest of 8, 4 and 2
+/// that covers at least three quarters of all usages.
+pub fn extract_spacing_scale(document: &Document) -> SpacingScale {
+    let mut found: BTreeMap<i64, (usize, BTreeSet<&'static str>)> = BTreeMap::new();
+    if let Some(children) = &document.children {
+        for child in children {
+            collect_spacing(child, &mut found);
+        }
+    }
+    if found.is_empty() {
+        return SpacingScale::default();
+    }
+
+    let total_usage: usize = found.values().map(|(usage, _)| usage).sum();
+    let base = SPACING_BASE_CANDIDATES.into_iter().find(|base| {
+        let on_scale: usize = found
+            .iter()
+            .filter(|(key, _)| spacing_step(spacing_value(**key), *base).is_some())
+            .map(|(_, (usage, _))| usage)
+            .sum();
+        on_scale * 4 >= total_usage * 3
+    });
+
+    let values = found
+        .into_iter()
+        .map(|(key, (usage, sources))| {
+            let value = spacing_value(key);
+            let step = base.and_then(|base| spacing_step(value, base));
+            let name = match step {
+                Some(step) => format!("space-{}", step),
+                None => format!("space-{}px", name_px(value)),
+            };
+            SpacingToken {
+                name,
+                value,
+                step,
+                usage,
+                sources: sources.into_iter().map(str::to_string).collect(),
+            }
+        })
+        .collect();
+
+    SpacingScale { base, values }
+}
+
+const SPACING_BASE_CANDIDATES: [f64; 3] = [8.0, 4.0, 2.0];
+
+fn collect_spacing(node: &Node, found: &mut BTreeMap<i64, (usize, BTreeSet<&'static str>)>) {
+    let is_auto_layout = node
+        .layout_mode
+        .as_deref()
+        .is_some_and(|mode| mode != "NONE");
+    if is_auto_layout {
+        let values = [
+            (node.item_spacing, "item-spacing"),
+            (node.counter_axis_spacing, "counter-axis-spacing"),
+            (node.padding_left, "padding"),
+            (node.padding_right, "padding"),
+            (node.padding_top, "padding"),
+            (node.padding_bottom, "padding"),
+        ];
+        for (value, source) in values {
+            let Some(value) = value.filter(|v| *v > 0.0) else {
+                continue;
+            };
+            // Key on hundredths of a px so float noise doesn't split values
+            let entry = found
+                .entry((value * 100.0).round() as i64)
+                .or_insert_with(|| (0, BTreeSet::new()));
+            entry.0 += 1;
+            entry.1.insert(source);
+        }
+    }
+
+    if let Some(children) = &node.children {
+        for child in children {
+            collect_spacing(child, found);
+        }
+    }
+}
+
+fn spacing_value(key: i64) -> f64 {
+    key as f64 / 100.0
+}
+
+fn spacing_step(value: f64, base: f64) -> Option<u32> {
+    let step = value / base;
+    if (step - step.round()).abs() < 0.001 {
+        Some(step.round() as u32)
+    } else {
+        None
+    }
+}
+
+fn format_px(value: f64) -> String {
+    let formatted = format!("{:.2}", value);
+    formatted
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
+async fn export(
+    client: &FigmaClient,
+    file_key: &str,
+    format: TokenFormat,
+    output_path: Option<std::path::PathBuf>,
+    variables: VariablesSource,
+    alias_mode: AliasMode,
+    config: &Config,
+) -> Result<()> {
+    let file = client.get_file(file_key).await?;
+    output::print_status(
+        &format!("Extracting tokens from: {}", file.name)
+            .bold()
+            .to_string(),
+    );
+
+    let mut tokens = extract_design_tokens(&file);
+    tokens.variables = load_variable_tokens(client, file_key, variables, alias_mode).await?;
+    let output_str = render_tokens(&tokens, format.clone(), &config.tokens)?;
+    let night_xml = match format {
+        TokenFormat::AndroidXml => export_android_night_xml(&tokens),
+        _ => None,
+    };
+
+    if let Some(path) = output_path {
+        fs::write(&path, &output_str)?;
+        output::print_success(&format!("Exported to: {}", path.display()));
+        if let Some(night_xml) = night_xml {
+            let night_path = night_resource_path(&path);
+            if let Some(parent) = night_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&night_path, night_xml)?;
+            output::print_success(&format!("Exported to: {}", night_path.display()));
+        }
+    } else {
+        output::print_raw(&format!("\n{}", output_str));
+        if let Some(night_xml) = night_xml {
+            output::print_raw(&format!("\n<!-- values-night -->\n{}", night_xml));
+        }
+    }
+
+    Ok(())
+}
+
+/// Fetch variables for the export, honoring `--variables`
+async fn load_variable_tokens(
+    client: &FigmaClient,
+    file_key: &str,
+    source: VariablesSource,
+    alias_mode: AliasMode,
+) -> Result<Vec<VariableCollectionTokens>> {
+    let local = match source {
+        VariablesSource::None => return Ok(Vec::new()),
+        VariablesSource::Auto => match client.get_local_variables(file_key).await {
+            Ok(response) => response,
+            Err(err) if variables_unavailable(&err) => {
+                // The variables endpoint needs an Enterprise plan and the file_variables:read scope
+                output::print_verbose(&format!("Skipping variables ({})", err));
+                return Ok(Vec::new());
+            }
+            Err(err) => {
+                output::print_warning(&format!("Skipping variables: {}", err));
+                return Ok(Vec::new());
+            }
+        },
+        VariablesSource::Local | VariablesSource::Published => {
+            client.get_local_variables(file_key).await?
+        }
+    };
+    let meta = local
+        .meta
+        .ok_or_else(|| anyhow!("Variables response did not include any data"))?;
+
+    let published: Option<HashSet<String>> = match source {
+        VariablesSource::Published => {
+            let response = client.get_published_variables(file_key).await?;
+            Some(
+                response
+                    .meta
+                    .unwrap_or_default()
+                    .variables
+                    .into_values()
+                    .map(|variable| variable.id)
+                    .collect(),
+            )
+        }
+        _ => None,
+    };
+
+    let (collections, warnings) = build_variable_tokens(&meta, published.as_ref(), alias_mode);
+    for warning in &warnings {
+        output::print_warning(warning);
+    }
+    Ok(collections)
+}
+
+/// Whether the variables endpoint is off-limits for this plan or token (403/404)
+fn variables_unavailable(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<ApiError>().is_some_and(|err| {
+        matches!(
+            err.status,
+            reqwest::StatusCode::FORBIDDEN | reqwest::StatusCode::NOT_FOUND
+        )
+    })
+}
+
+/// Where the `values-night` copy of an Android resource file goes
+fn night_resource_path(path: &Path) -> PathBuf {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_os_string())
+        .unwrap_or_else(|| "fgm_tokens.xml".into());
+    match path.parent() {
+        Some(parent) if parent.file_name().is_some_and(|name| name == "values") => {
+            parent.with_file_name("values-night").join(file_name)
+        }
+        _ => {
+            let stem = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_else(|| "fgm_tokens".to_string());
+            path.with_file_name(format!("{}-night.xml", stem))
+        }
+    }
+}
+
+/// Extract color, typography and spacing tokens from a file
+///
+/// Nodes referencing a fill or text style produce tokens named after the
+/// style. Values used without a style go to the `unnamed_*` buckets, unless
+/// they match a named token.
+pub fn extract_design_tokens(file: &File) -> DesignTokens {
+    let mut named = StyledTokens::default();
+    if let Some(children) = &file.document.children {
+        for child in children {
+            collect_styled_tokens(child, &file.styles, &mut named);
+        }
+    }
+    let colors: Vec<ColorToken> = std::mem::take(&mut named.colors).into_values().collect();
+    let typography: Vec<TypographyToken> = std::mem::take(&mut named.typography)
+        .into_values()
+        .collect();
+
+    let named_hexes: HashSet<String> = colors
+        .iter()
+        .map(|token| hex_with_alpha(&token.hex, token.rgba[3]))
+        .collect();
+    let mut color_map: HashMap<String, Color> = HashMap::new();
+    extract_all_colors(&file.document, &mut color_map);
+    let mut unnamed_colors: Vec<ColorToken> = color_map
+        .into_iter()
+        .filter(|(hex, _)| !named_hexes.contains(hex))
+        .map(|(hex, color)| color_token(color_name_from_hex(&hex), &color, None))
+        .collect();
+    unnamed_colors.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let named_styles: HashSet<TypographyKey> = typography.iter().map(TypographyKey::from).collect();
+    let mut unnamed_typography: Vec<TypographyToken> = Vec::new();
+    extract_typography_tokens(&file.document, &mut unnamed_typography);
+    unnamed_typography.retain(|token| !named_styles.contains(&TypographyKey::from(token)));
+    unnamed_typography.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let shadows: Vec<ShadowToken> = named.shadows.into_values().collect();
+    let blurs: Vec<BlurToken> = named.blurs.into_values().collect();
+    let (unnamed_shadows, unnamed_blurs) =
+        effects::extract_unnamed_effects(&file.document, &shadows, &blurs);
+
+    let gradients: Vec<GradientToken> = named.gradients.into_values().collect();
+    let mut images: Vec<ImagePaintToken> = named.images.into_values().collect();
+    let (unnamed_gradients, unnamed_images) =
+        extract_unnamed_paints(&file.document, &gradients, &images);
+    images.extend(unnamed_images);
+
+    DesignTokens {
+        colors,
+        unnamed_colors,
+        typography,
+        unnamed_typography,
+        spacing: extract_spacing_scale(&file.document),
+        shadows,
+        unnamed_shadows,
+        blurs,
+        unnamed_blurs,
+        radii: extract_radii(&file.document),
+        stroke_widths: extract_stroke_widths(&file.document),
+        gradients,
+        unnamed_gradients,
+        images,
+        variables: Vec::new(),
+    }
+}
+
+/// Render tokens in the given format using the configured name prefixes and color space
+pub fn render_tokens(
+    tokens: &DesignTokens,
+    format: TokenFormat,
+    config: &TokensConfig,
+) -> Result<String> {
+    let color_space = ColorSpace::parse(&config.color_space)?;
+    Ok(match format {
+        TokenFormat::Json => export_json(tokens)?,
+        TokenFormat::Css => export_css(tokens, &config.css_prefix, color_space),
+        TokenFormat::Swift => export_swift(tokens, &config.swift_prefix),
+        TokenFormat::Kotlin => export_kotlin(tokens, &config.swift_prefix),
+        TokenFormat::Tailwind => export_tailwind(tokens, color_space),
+        TokenFormat::StyleDictionary => export_style_dictionary(tokens, color_space)?,
+        TokenFormat::AndroidXml => export_android_xml(tokens),
+        TokenFormat::Dtcg => export_dtcg(tokens)?,
+    })
+}
+
+/// Tokens backed by styles, keyed (and so sorted) by style name
+#[derive(Default)]
+struct StyledTokens {
+    colors: BTreeMap<String, ColorToken>,
+    typography: BTreeMap<String, TypographyToken>,
+    shadows: BTreeMap<String, ShadowToken>,
+    blurs: BTreeMap<(String, BlurKind), BlurToken>,
+    gradients: BTreeMap<String, GradientToken>,
+    images: BTreeMap<String, ImagePaintToken>,
+}
+
+fn collect_styled_tokens(node: &Node, styles: &HashMap<String, Style>, found: &mut StyledTokens) {
+    let style_for = |usage: &str, style_type: &str| {
+        node.styles
+            .as_ref()
+            .and_then(|refs| refs.get(usage))
+            .and_then(|id| styles.get(id))
+            .filter(|style| style.style_type == style_type)
+    };
+
+    for (usage, paints) in [("fill", &node.fills), ("stroke", &node.strokes)] {
+        let Some(style) = style_for(usage, "FILL") else {
+            continue;
+        };
+        if found.colors.contains_key(&style.name)
+            || found.gradients.contains_key(&style.name)
+            || found.images.contains_key(&style.name)
+        {
+            continue;
+        }
+        // The last paint is the topmost one in Figma's paint stack
+        let paints = paints.as_deref().unwrap_or_default();
+        let color = paints.iter().rev().find_map(Paint::solid_color);
+        let description = non_empty(&style.description);
+        if let Some(color) = color {
+            found.colors.insert(
+                style.name.clone(),
+                color_token(style.name.clone(), &color, description),
+            );
+        } else if let Some(gradient) = paints.iter().rev().find_map(|paint| {
+            paints::gradient_token(style.name.clone(), description.clone(), paint)
+        }) {
+            found.gradients.insert(style.name.clone(), gradient);
+        } else if let Some(image) = paints
+            .iter()
+            .rev()
+            .find_map(|paint| paints::image_token(style.name.clone(), description.clone(), paint))
+        {
+            found.images.insert(style.name.clone(), image);
+        }
+    }
+
+    if let (Some(style), Some(type_style)) = (style_for("text", "TEXT"), &node.style) {
+        found
+            .typography
+            .entry(style.name.clone())
+            .or_insert_with(|| {
+                typography::typography_token(
+                    style.name.clone(),
+                    non_empty(&style.description),
+                    type_style,
+                )
+            });
+    }
+
+    if let Some(style) = style_for("effect", "EFFECT") {
+        let layers: Vec<ShadowLayer> = node
+            .effects
+            .iter()
+            .flatten()
+            .filter_map(effects::shadow_layer)
+            .collect();
+        if !layers.is_empty() && !found.shadows.contains_key(&style.name) {
+            found.shadows.insert(
+                style.name.clone(),
+                ShadowToken {
+                    name: style.name.clone(),
+                    description: non_empty(&style.description),
+                    layers,
+                },
+            );
+        }
+        for (kind, radius) in node
+            .effects
+            .iter()
+            .flatten()
+            .filter_map(effects::blur_effect)
+        {
+            found
+                .blurs
+                .entry((style.name.clone(), kind))
+                .or_insert_with(|| BlurToken {
+                    name: style.name.clone(),
+                    description: non_empty(&style.description),
+                    kind,
+                    radius,
+                });
+        }
+    }
+
+    if let Some(children) = &node.children {
+        for child in children {
+            collect_styled_tokens(child, styles, found);
+        }
+    }
+}
+
+fn color_token(name: String, color: &Color, description: Option<String>) -> ColorToken {
+    ColorToken {
+        name,
+        hex: color.to_hex(),
+        rgb: color.to_rgb(),
+        rgba: [color.r, color.g, color.b, color.a],
+        description,
+    }
+}
+
+fn non_empty(value: &Option<String>) -> Option<String> {
+    value.clone().filter(|v| !v.trim().is_empty())
+}
+
+/// Visible solid colors with paint opacity applied, keyed by `#RRGGBB[AA]`
+fn extract_all_colors(node: &impl HasFillsAndChildren, colors: &mut HashMap<String, Color>) {
+    if let Some(fills) = node.fills() {
+        // Keyed with alpha so translucent variants stay distinct tokens
+        for color in fills.iter().filter_map(Paint::solid_color) {
+            colors.insert(color.to_hex_alpha(), color);
+        }
+    }
+    if let Some(children) = node.children() {
+        for child in children {
+            extract_all_colors(child, colors);
+        }
+    }
+}
+
+pub fn export_json(tokens: &DesignTokens) -> Result<String> {
+    Ok(serde_json::to_string_pretty(tokens)?)
+}
+
+pub fn export_css(tokens: &DesignTokens, prefix: &str, color_space: ColorSpace) -> String {
+    let css_prefix = ensure_css_prefix(prefix);
+    let mut css = String::from(":root {\n  /* Colors */\n");
+
+    for (index, color) in tokens.all_colors().enumerate() {
+        if index == tokens.colors.len() {
+            css.push_str("\n  /* Unnamed colors */\n");
+        }
+        let name = sanitize_token_name(&color.name);
+        css.push_str(&format!(
+            "  {}{}: {};\n",
+            css_prefix,
+            name,
+            format_color(&color.hex, &color.rgba, color_space)
+        ));
+    }
+
+    css.push_str(&typography::css(tokens, &css_prefix));
+
+    if !tokens.spacing.values.is_empty() {
+        css.push_str("\n  /* Spacing */\n");
+        for token in &tokens.spacing.values {
+            css.push_str(&format!(
+                "  {}{}: {}px;\n",
+                css_prefix,
+                token.name,
+                format_px(token.value)
+            ));
+        }
+    }
+
+    css.push_str(&paints::css(tokens, &css_prefix));
+    css.push_str(&effects::css(tokens, &css_prefix));
+    css.push_str("}\n");
+    css.push_str(&variables::css_blocks(&tokens.variables, &css_prefix));
+    css
+}
+
+pub fn export_swift(tokens: &DesignTokens, prefix: &str) -> String {
+    let type_prefix = sanitize_type_name(prefix);
+    let mut swift = String::from("import SwiftUI\n\n");
+
+    swift.push_str(&format!("enum {}Colors {{\n", type_prefix));
+    for color in tokens.all_colors() {
+        let name = to_pascal_case(&color.name);
+        swift.push_str(&format!(
+            "    static let {} = {}\n",
+            name,
+            swift_color(&color.rgba)
+        ));
+    }
+    swift.push_str("}\n\n");
+
+    swift.push_str(&typography::swift(tokens, &type_prefix));
+
+    if !tokens.spacing.values.is_empty() {
+        swift.push_str(&format!("\nenum {}Spacing {{\n", type_prefix));
+        for token in &tokens.spacing.values {
+            swift.push_str(&format!(
+                "    static let {}: CGFloat = {}\n",
+                to_pascal_case(&token.name),
+                format_px(token.value)
+            ));
+        }
+        swift.push_str("}\n");
+    }
+    swift.push_str(&paints::swift(tokens, &type_prefix));
+    swift.push_str(&effects::swift(tokens, &type_prefix));
+    swift.push_str(&variables::swift(&tokens.variables, &type_prefix));
+    swift
+}
+
+pub fn export_kotlin(tokens: &DesignTokens, prefix: &str) -> String {
+    let type_prefix = sanitize_type_name(prefix);
+    let mut kotlin = format!(
+        "package design.tokens\n\n{}{}import androidx.compose.ui.graphics.Color\n{}import androidx.compose.ui.unit.dp\n\n",
+        effects::kotlin_imports(tokens),
+        paints::kotlin_imports(tokens),
+        typography::kotlin_imports()
+    );
+
+    kotlin.push_str(&format!("object {}Colors {{\n", type_prefix));
+    for color in tokens.all_colors() {
+        let name = to_pascal_case(&color.name);
+        kotlin.push_str(&format!(
+            "    val {} = {}\n",
+            name,
+            kotlin_color(&color.hex, color.rgba[3])
+        ));
+    }
+    kotlin.push_str("}\n\n");
+
+    kotlin.push_str(&typography::kotlin(tokens, &type_prefix));
+
+    if !tokens.spacing.values.is_empty() {
+        kotlin.push_str(&format!("\nobject {}Spacing {{\n", type_prefix));
+        for token in &tokens.spacing.values {
+            kotlin.push_str(&format!(
+                "    val {} = {}.dp\n",
+                to_pascal_case(&token.name),
+                format_px(token.value)
+            ));
+        }
+        kotlin.push_str("}\n");
+    }
+    kotlin.push_str(&paints::kotlin(tokens, &type_prefix));
+    kotlin.push_str(&effects::kotlin(tokens, &type_prefix));
+    kotlin.push_str(&variables::kotlin(&tokens.variables, &type_prefix));
+    kotlin
+}
+
+pub fn export_tailwind(tokens: &DesignTokens, color_space: ColorSpace) -> String {
+    let mut output = String::from("export default {\n  theme: {\n    extend: {\n      colors: {\n");
+    let mut named = serde_json::Map::new();
+    for color in &tokens.colors {
+        insert_token_path(
+            &mut named,
+            &style_path(&color.name),
+            serde_json::json!(format_color(&color.hex, &color.rgba, color_space)),
+        );
+    }
+    render_js_entries(&named, 8, &mut output);
+    for color in &tokens.unnamed_colors {
+        output.push_str(&format!(
+            "        '{}': '{}',\n",
+            sanitize_token_name(&color.name),
+            format_color(&color.hex, &color.rgba, color_space)
+        ));
+    }
+    output.push_str(&variables::tailwind_colors(&tokens.variables));
+    output.push_str("      },\n      fontSize: {\n");
+    for token in tokens.all_typography() {
+        if let Some(size) = token.size {
+            // [size, { lineHeight, letterSpacing, fontWeight }]
+            let mut options = Vec::new();
+            if let Some(line_height) = token.fixed_line_height() {
+                options.push(format!("lineHeight: '{}px'", format_px(line_height)));
+            }
+            if let Some(letter_spacing) = token.letter_spacing {
+                options.push(format!("letterSpacing: '{}px'", format_px(letter_spacing)));
+            }
+            if let Some(weight) = token.weight {
+                options.push(format!("fontWeight: '{}'", weight.round()));
+            }
+            let value = if options.is_empty() {
+                format!("'{}px'", size.round())
+            } else {
+                format!("['{}px', {{ {} }}]", size.round(), options.join(", "))
+            };
+            output.push_str(&format!(
+                "        '{}': {},\n",
+                sanitize_token_name(&token.name),
+                value
+            ));
+        }
+    }
+    output.push_str("      },\n      spacing: {\n");
+    for token in &tokens.spacing.values {
+        output.push_str(&format!(
+            "        '{}': '{}px',\n",
+            token.name,
+            format_px(token.value)
+        ));
+    }
+    output.push_str("      },\n");
+    output.push_str(&paints::tailwind(tokens));
+    output.push_str(&effects::tailwind(tokens));
+    output.push_str("    },\n  },\n};\n");
+    output
+}
+
+pub fn export_style_dictionary(tokens: &DesignTokens, color_space: ColorSpace) -> Result<String> {
+    let mut color = serde_json::Map::new();
+    for token in &tokens.colors {
+        insert_token_path(
+            &mut color,
+            &style_path(&token.name),
+            serde_json::json!({ "value": format_color(&token.hex, &token.rgba, color_space) }),
+        );
+    }
+    for token in &tokens.unnamed_colors {
+        insert_token_path(
+            &mut color,
+            &[UNNAMED_GROUP.to_string(), token.name.clone()],
+            serde_json::json!({ "value": format_color(&token.hex, &token.rgba, color_space) }),
+        );
+    }
+
+    let mut typography = serde_json::Map::new();
+    for (index, token) in tokens.all_typography().enumerate() {
+        let path = if index < tokens.typography.len() {
+            style_path(&token.name)
+        } else {
+            vec![UNNAMED_GROUP.to_string(), token.name.clone()]
+        };
+        insert_token_path(&mut typography, &path, typography::style_dictionary(token));
+    }
+
+    let mut spacing = serde_json::Map::new();
+    for token in &tokens.spacing.values {
+        spacing.insert(
+            token.name.clone(),
+            serde_json::json!({ "value": format!("{}px", format_px(token.value)) }),
+        );
+    }
+
+    let mut root = serde_json::json!({
+        "color": color,
+        "typography": typography,
+        "spacing": spacing
+    });
+    paints::style_dictionary(tokens, &mut root);
+    effects::style_dictionary(tokens, &mut root);
+    if !tokens.variables.is_empty() {
+        root["variables"] = variables::style_dictionary(&tokens.variables);
+    }
+
+    Ok(serde_json::to_string_pretty(&root)?)
+}
+
+pub fn export_android_xml(tokens: &DesignTokens) -> String {
+    let mut xml = String::from("<resources>\n");
+    for color in tokens.all_colors() {
+        xml.push_str(&format!(
+            "  <color name=\"{}\">{}</color>\n",
+            sanitize_token_name(&color.name),
+            android_color(&color.hex, color.rgba[3])
+        ));
+    }
+    for token in tokens.all_typography() {
+        if let Some(size) = token.size {
+            xml.push_str(&format!(
+                "  <dimen name=\"font_{}_size\">{:.0}sp</dimen>\n",
+                sanitize_token_name(&token.name),
+                size
+            ));
+        }
+    }
+    for token in &tokens.spacing.values {
+        xml.push_str(&format!(
+            "  <dimen name=\"{}\">{}dp</dimen>\n",
+            token.name,
+            format_px(token.value)
+        ));
+    }
+    xml.push_str(&effects::android_resources(tokens));
+    xml.push_str(&variables::android_resources(&tokens.variables, false));
+    xml.push_str("</resources>\n");
+    xml
+}
+
+/// Dark-mode variable overrides for `values-night`, if any collection has a dark mode
+pub fn export_android_night_xml(tokens: &DesignTokens) -> Option<String> {
+    let resources = variables::android_resources(&tokens.variables, true);
+    if resources.is_empty() {
+        return None;
+    }
+    Some(format!("<resources>\n{}</resources>\n", resources))
+}
+
+/// Group used for values without a style in nested formats
+const UNNAMED_GROUP: &str = "unnamed";
+
+/// Slash-separated style name as sanitized path segments (`Primary/500` -> `primary`, `500`)
+fn style_path(name: &str) -> Vec<String> {
+    let path: Vec<String> = name
+        .split('/')
+        .map(sanitize_token_name)
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    if path.is_empty() {
+        vec![sanitize_token_name(name)]
+    } else {
+        path
+    }
+}
+
+/// Insert a value into nested groups; a group that is also a token keeps it under `DEFAULT`
+fn insert_token_path(
+    map: &mut serde_json::Map<String, serde_json::Value>,
+    path: &[String],
+    value: serde_json::Value,
+) {
+    let Some((last, groups)) = path.split_last() else {
+        return;
+    };
+    let mut current = map;
+    for segment in groups {
+        let entry = current
+            .entry(segment.clone())
+            .or_insert_with(|| serde_json::Value::Object(serde_json::Map::new()));
+        if !is_group(entry) {
+            let leaf = entry.take();
+            *entry = serde_json::json!({ "DEFAULT": leaf });
+        }
+        current = entry.as_object_mut().expect("group is an object");
+    }
+    match current.get_mut(last) {
+        Some(existing) if is_group(existing) => {
+            existing
+                .as_object_mut()
+                .expect("group is an object")
+                .insert("DEFAULT".to_string(), value);
+        }
+        _ => {
+            current.insert(last.clone(), value);
+        }
+    }
+}
+
+/// Whether a nested value is a group rather than a token (tokens are strings or carry a value)
+fn is_group(value: &serde_json::Value) -> bool {
+    value.as_object().is_some_and(|object| {
+        !["value", "$value", "family"]
+            .iter()
+            .any(|key| object.contains_key(*key))
+    })
+}
+
+/// Render nested string groups as JS object entries with single-quoted keys
+fn render_js_entries(
+    map: &serde_json::Map<String, serde_json::Value>,
+    indent: usize,
+    output: &mut String,
+) {
+    let pad = " ".repeat(indent);
+    for (key, value) in map {
+        match value {
+            serde_json::Value::Object(children) => {
+                output.push_str(&format!("{}'{}': {{\n", pad, key));
+                render_js_entries(children, indent + 2, output);
+                output.push_str(&format!("{}}},\n", pad));
+            }
+            serde_json::Value::String(text) => {
+                output.push_str(&format!("{}'{}': '{}',\n", pad, key, text));
+            }
+            other => output.push_str(&format!("{}'{}': {},\n", pad, key, other)),
+        }
+    }
+}
+
+/// CSS color value: hex when opaque, rgba() otherwise
+fn css_color(hex: &str, rgba: &[f64; 4]) -> String {
+    if rgba[3] >= 1.0 {
+        hex.to_string()
+    } else {
+        format!(
+            "rgba({}, {}, {}, {})",
+            (rgba[0] * 255.0).round() as u8,
+            (rgba[1] * 255.0).round() as u8,
+            (rgba[2] * 255.0).round() as u8,
+            format_px(rgba[3])
+        )
+    }
+}
+
+/// SwiftUI `Color(red:green:blue:)`, with `opacity:` for translucent colors
+fn swift_color(rgba: &[f64; 4]) -> String {
+    if rgba[3] >= 1.0 {
+        format!(
+            "Color(red: {:.3}, green: {:.3}, blue: {:.3})",
+            rgba[0], rgba[1], rgba[2]
+        )
+    } else {
+        format!(
+            "Color(red: {:.3}, green: {:.3}, blue: {:.3}, opacity: {:.3})",
+            rgba[0], rgba[1], rgba[2], rgba[3]
+        )
+    }
+}
+
+/// Android `#RRGGBB`, or `#AARRGGBB` for translucent colors
+fn android_color(hex: &str, alpha: f64) -> String {
+    if alpha >= 1.0 {
+        hex.to_string()
+    } else {
+        format!(
+            "#{:02X}{}",
+            (alpha * 255.0).round() as u8,
+            hex.trim_start_matches('#')
+        )
+    }
+}
+
+/// Compose `Color(0xAARRGGBB)` literal
+fn kotlin_color(hex: &str, alpha: f64) -> String {
+    format!(
+        "Color(0x{:02X}{})",
+        (alpha * 255.0).round() as u8,
+        hex.trim_start_matches('#')
+    )
+}
+
+fn ensure_css_prefix(prefix: &str) -> String {
+    if prefix.starts_with("--") {
+        prefix.to_string()
+    } else {
+        format!("--{}", prefix)
+    }
+}
+
+pub(crate) fn sanitize_token_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect::<String>()
+        .trim_matches('-')
+        .to_string()
+}
+
+fn sanitize_type_name(prefix: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in prefix.chars().enumerate() {
+        if c.is_ascii_alphanumeric() {
+            if i == 0 && c.is_ascii_digit() {
+                out.push('_');
+            }
+            out.push(c);
+        }
+    }
+    if out.is_empty() {
+        "Figma".to_string()
+    } else {
+        out
+    }
+}
+
+/// Identifier for Swift and Kotlin; an underscore between digits is kept, so
+/// `space-1_5px` and `space-15px` stay apart
+fn to_pascal_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut out = String::new();
+    let mut next_upper = true;
+    for (index, &c) in chars.iter().enumerate() {
+        if c.is_ascii_alphanumeric() {
+            if next_upper {
+                out.push(c.to_ascii_uppercase());
+                next_upper = false;
+            } else {
+                out.push(c.to_ascii_lowercase());
+            }
+        } else {
+            let between_digits = index > 0
+                && chars[index - 1].is_ascii_digit()
+                && chars.get(index + 1).is_some_and(char::is_ascii_digit);
+            if c == '_' && between_digits {
+                out.push('_');
+            }
+            next_upper = true;
+        }
+    }
+    if out.is_empty() {
+        "Token".to_string()
+    } else {
+        if out
+            .chars()
+            .next()
+            .map(|c| c.is_ascii_digit())
+            .unwrap_or(false)
+        {
+            out.insert(0, '_');
+        }
+        out
+    }
+}
+
+/// px value usable in a token name (`1.5` -> `1_5`)
+pub(super) fn name_px(value: f64) -> String {
+    format_px(value).replace('.', "_")
+}
+
+fn color_name_from_hex(hex: &str) -> String {
+    format!("color-{}", hex.trim_start_matches('#').to_lowercase())
+}
+
+/// `<kind>-<hash>` names for distinct unnamed values, each stable as long as its value is
+///
+/// The hash is the full 64-bit FNV-1a of the value's JSON, which should leave
+/// out the token's name and description. Should two values still collide, the
+/// later one gets a numeric suffix, so names never repeat.
+pub(super) fn names_from_values<T: Serialize>(kind: &str, values: &[T]) -> Vec<String> {
+    let mut used = HashSet::new();
+    values
+        .iter()
+        .map(|value| {
+            let json = serde_json::to_string(value).unwrap_or_default();
+            let base = format!("{}-{:016x}", kind, fnv1a64(json.as_bytes()));
+            let mut name = base.clone();
+            let mut suffix = 2;
+            while !used.insert(name.clone()) {
+                name = format!("{}-{}", base, suffix);
+                suffix += 1;
+            }
+            name
+        })
+        .collect()
+}
+
+/// Every text style property of a token, without its name and description
+#[derive(Hash, Eq, PartialEq)]
+struct TypographyKey(String);
+
+impl From<&TypographyToken> for TypographyKey {
+    fn from(token: &TypographyToken) -> Self {
+        let properties = TypographyToken {
+            name: String::new(),
+            description: None,
+            ..token.clone()
+        };
+        Self(serde_json::to_string(&properties).unwrap_or_default())
+    }
+}
+
+fn extract_typography_tokens(
+    document: &crate::api::types::Document,
+    tokens: &mut Vec<TypographyToken>,
+) {
+    let mut seen: HashSet<TypographyKey> = HashSet::new();
+    let mut used_names: HashSet<String> = HashSet::new();
+    collect_typography(document, tokens, &mut seen, &mut used_names);
+}
+
+fn collect_typography(
+    node: &impl HasStyleAndChildren,
+    tokens: &mut Vec<TypographyToken>,
+    seen: &mut HashSet<TypographyKey>,
+    used_names: &mut HashSet<String>,
+) {
+    if let Some(style) = node.type_style() {
+        let mut token = typography::typography_token(String::new(), None, style);
+        if seen.insert(TypographyKey::from(&token)) {
+            let base_name = node.node_name().unwrap_or_else(|| "text-style".to_string());
+            let mut name = sanitize_token_name(&base_name);
+            if name.is_empty() {
+                name = "text-style".to_string();
+            }
+            let mut final_name = name.clone();
+            let mut counter = 2;
+            while used_names.contains(&final_name) {
+                final_name = format!("{}-{}", name, counter);
+                counter += 1;
+            }
+            used_names.insert(final_name.clone());
+
+            token.name = final_name;
+            tokens.push(token);
+        }
+    }
+
+    if let Some(children) = node.node_children() {
+        for child in children {
+            collect_typography(child, tokens, seen, used_names);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_tokens() -> DesignTokens {
+        DesignTokens {
+            colors: vec![ColorToken {
+                name: "Brand Primary".to_string(),
+                hex: "#112233".to_string(),
+                rgb: [17, 34, 51],
+                rgba: [0.067, 0.133, 0.2, 1.0],
+                description: None,
+            }],
+            unnamed_colors: Vec::new(),
+            typography: vec![TypographyToken {
+                name: "Heading".to_string(),
+                description: None,
+                family: Some("IBM Plex Sans".to_string()),
+                size: Some(24.0),
+                weight: Some(700.0),
+                line_height: Some(28.0),
+                letter_spacing: Some(0.0),
+                ..Default::default()
+            }],
+            unnamed_typography: Vec::new(),
+            spacing: SpacingScale {
+                base: Some(4.0),
+                values: vec![SpacingToken {
+                    name: "space-4".to_string(),
+                    value: 16.0,
+                    step: Some(4),
+                    usage: 3,
+                    sources: vec!["padding".to_string()],
+                }],
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn tailwind_export_contains_colors_and_sizes() {
+        let output = export_tailwind(&sample_tokens(), ColorSpace::Hex);
+        assert!(output.contains("brand-primary"));
+        assert!(output.contains("24px"));
+    }
+
+    #[test]
+    fn android_xml_export_contains_color_entry() {
+        let output = export_android_xml(&sample_tokens());
+        assert!(output.contains("<color name=\"brand-primary\">#112233</color>"));
+        assert!(output.contains("<dimen name=\"space-4\">16dp</dimen>"));
+    }
+
+    #[test]
+    fn css_swift_and_kotlin_exports_include_spacing() {
+        let tokens = sample_tokens();
+        assert!(export_css(&tokens, "fgm-", ColorSpace::Hex).contains("--fgm-space-4: 16px;"));
+        assert!(export_swift(&tokens, "Figma").contains("static let Space4: CGFloat = 16"));
+        assert!(export_kotlin(&tokens, "Figma").contains("val Space4 = 16.dp"));
+        assert!(export_tailwind(&tokens, ColorSpace::Hex).contains("'space-4': '16px'"));
+    }
+
+    #[test]
+    fn night_resources_go_next_to_values_directory() {
+        assert_eq!(
+            night_resource_path(Path::new("res/values/fgm_tokens.xml")),
+            PathBuf::from("res/values-night/fgm_tokens.xml")
+        );
+        assert_eq!(
+            night_resource_path(Path::new("out/tokens.xml")),
+            PathBuf::from("out/tokens-night.xml")
+        );
+    }
+
+    #[test]
+    fn spacing_scale_dedupes_values_and_infers_base() {
+        let document: Document = serde_json::from_value(serde_json::json!({
+            "id": "0:0",
+            "name": "Document",
+            "type": "DOCUMENT",
+            "children": [{
+                "id": "1:1",
+                "name": "Card",
+                "type": "FRAME",
+                "layoutMode": "VERTICAL",
+                "itemSpacing": 8,
+                "paddingLeft": 16,
+                "paddingRight": 16,
+                "paddingTop": 0,
+                "children": [{
+                    "id": "1:2",
+                    "name": "Row",
+                    "type": "FRAME",
+                    "layoutMode": "HORIZONTAL",
+                    "itemSpacing": 12,
+                    "paddingTop": 8,
+                    "paddingBottom": 6
+                }, {
+                    "id": "1:3",
+                    "name": "Absolute",
+                    "type": "FRAME",
+                    "itemSpacing": 40
+                }]
+            }]
+        }))
+        .expect("document");
+
+        let scale = extract_spacing_scale(&document);
+
+        assert_eq!(scale.base, Some(4.0));
+        let values: Vec<(f64, usize)> = scale.values.iter().map(|t| (t.value, t.usage)).collect();
+        assert_eq!(values, vec![(6.0, 1), (8.0, 2), (12.0, 1), (16.0, 2)]);
+        assert_eq!(scale.values[1].name, "space-2");
+        assert_eq!(scale.values[1].sources, vec!["item-spacing", "padding"]);
+        assert_eq!(scale.values[0].name, "space-6px");
+        assert_eq!(scale.values[0].step, None);
+    }
+
+    #[test]
+    fn fractional_spacing_names_stay_distinct_in_code() {
+        let document: Document = serde_json::from_value(serde_json::json!({
+            "id": "0:0",
+            "name": "Document",
+            "type": "DOCUMENT",
+            "children": [{
+                "id": "0:1",
+                "name": "Page",
+                "type": "CANVAS",
+                "children": [{
+                    "id": "1:1",
+                    "name": "Tight",
+                    "type": "FRAME",
+                    "layoutMode": "VERTICAL",
+                    "itemSpacing": 1.5
+                }, {
+                    "id": "1:2",
+                    "name": "Loose",
+                    "type": "FRAME",
+                    "layoutMode": "VERTICAL",
+                    "itemSpacing": 15
+                }]
+            }]
+        }))
+        .expect("document");
+
+        let tokens = DesignTokens {
+            spacing: extract_spacing_scale(&document),
+            ..Default::default()
+        };
+        let names: Vec<&str> = tokens
+            .spacing
+            .values
+            .iter()
+            .map(|t| t.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["space-1_5px", "space-15px"]);
+
+        let swift = export_swift(&tokens, "");
+        assert!(swift.contains("static let Space1_5px: CGFloat = 1.5"));
+        assert!(swift.contains("static let Space15px: CGFloat = 15"));
+        let kotlin = export_kotlin(&tokens, "");
+        assert!(kotlin.contains("val Space1_5px = 1.5.dp"));
+        assert!(kotlin.contains("val Space15px = 15.dp"));
+    }
+
+    fn styled_file() -> File {
+        serde_json::from_value(serde_json::json!({
+            "name": "Styles",
+            "lastModified": "2024-01-01T00:00:00Z",
+            "version": "1",
+            "components": {},
+            "styles": {
+                "S:1": { "key": "k1", "name": "Primary/500", "styleType": "FILL", "description": "Brand" },
+                "S:2": { "key": "k2", "name": "Primary", "styleType": "FILL", "description": "" },
+                "S:3": { "key": "k3", "name": "Heading/Large", "styleType": "TEXT" },
+                "S:4": { "key": "k4", "name": "Elevation/1", "styleType": "EFFECT" }
+            },
+            "document": {
+                "id": "0:0",
+                "name": "Document",
+                "type": "DOCUMENT",
+                "children": [{
+                    "id": "1:1",
+                    "name": "Card",
+                    "type": "FRAME",
+                    "styles": { "fill": "S:1", "effect": "S:4" },
+                    "effects": [
+                        { "type": "DROP_SHADOW", "radius": 4.0, "offset": { "x": 0.0, "y": 2.0 },
+                          "color": { "r": 0.0, "g": 0.0, "b": 0.0, "a": 0.2 } },
+                        { "type": "LAYER_BLUR", "radius": 8.0 }
+                    ],
+                    "fills": [{ "type": "SOLID", "color": { "r": 1.0, "g": 0.0, "b": 0.0, "a": 1.0 } }],
+                    "children": [{
+                        "id": "1:2",
+                        "name": "Title",
+                        "type": "TEXT",
+                        "styles": { "text": "S:3", "stroke": "S:2" },
+                        "strokes": [{ "type": "SOLID", "color": { "r": 0.0, "g": 0.0, "b": 1.0, "a": 1.0 } }],
+                        "fills": [{ "type": "SOLID", "color": { "r": 0.0, "g": 1.0, "b": 0.0, "a": 1.0 } }],
+                        "style": { "fontFamily": "Inter", "fontSize": 32.0, "fontWeight": 700.0 }
+                    }, {
+                        "id": "1:3",
+                        "name": "Body",
+                        "type": "TEXT",
+                        "fills": [{ "type": "SOLID", "color": { "r": 1.0, "g": 0.0, "b": 0.0, "a": 1.0 } }],
+                        "style": { "fontFamily": "Inter", "fontSize": 16.0, "fontWeight": 400.0 }
+                    }]
+                }]
+            }
+        }))
+        .expect("file")
+    }
+
+    #[test]
+    fn translucent_colors_are_distinct_tokens() {
+        let file: File = serde_json::from_value(serde_json::json!({
+            "name": "Colors",
+            "lastModified": "2024-01-01T00:00:00Z",
+            "version": "1",
+            "components": {},
+            "styles": {},
+            "document": {
+                "id": "0:0",
+                "name": "Document",
+                "type": "DOCUMENT",
+                "children": [{
+                    "id": "1:1",
+                    "name": "Solid",
+                    "type": "RECTANGLE",
+                    "fills": [{ "type": "SOLID", "color": { "r": 1.0, "g": 0.0, "b": 0.0, "a": 1.0 } }],
+                    "children": [{
+                        "id": "1:2",
+                        "name": "Scrim",
+                        "type": "RECTANGLE",
+                        "fills": [{ "type": "SOLID", "opacity": 0.5,
+                                    "color": { "r": 1.0, "g": 0.0, "b": 0.0, "a": 1.0 } }]
+                    }, {
+                        "id": "1:3",
+                        "name": "Gray",
+                        "type": "RECTANGLE",
+                        "fills": [{ "type": "SOLID", "color": { "r": 0.5, "g": 0.5, "b": 0.5, "a": 1.0 } }]
+                    }]
+                }]
+            }
+        }))
+        .expect("file");
+
+        let tokens = extract_design_tokens(&file);
+        let names: Vec<&str> = tokens
+            .unnamed_colors
+            .iter()
+            .map(|t| t.name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec!["color-808080", "color-ff0000", "color-ff000080"]
+        );
+
+        let css = export_css(&tokens, "", ColorSpace::Hex);
+        assert!(css.contains("--color-ff000080: #FF000080;"));
+        let css = export_css(&tokens, "", ColorSpace::Hsl);
+        assert!(css.contains("--color-ff000080: hsl(0 100% 50% / 0.5);"));
+        assert!(export_kotlin(&tokens, "").contains("val ColorFf000080 = Color(0x80FF0000)"));
+        assert!(export_android_xml(&tokens).contains(">#80FF0000</color>"));
+    }
+
+    #[test]
+    fn tokens_are_named_after_styles() {
+        let tokens = extract_design_tokens(&styled_file());
+
+        let named: Vec<(&str, &str)> = tokens
+            .colors
+            .iter()
+            .map(|t| (t.name.as_str(), t.hex.as_str()))
+            .collect();
+        assert_eq!(
+            named,
+            vec![("Primary", "#0000FF"), ("Primary/500", "#FF0000")]
+        );
+        assert_eq!(tokens.colors[1].description.as_deref(), Some("Brand"));
+        assert_eq!(tokens.colors[0].description, None);
+        assert_eq!(tokens.typography.len(), 1);
+        assert_eq!(tokens.typography[0].name, "Heading/Large");
+
+        // Red is covered by a style; green is only used raw
+        let unnamed: Vec<&str> = tokens
+            .unnamed_colors
+            .iter()
+            .map(|t| t.hex.as_str())
+            .collect();
+        assert_eq!(unnamed, vec!["#00FF00"]);
+        assert_eq!(tokens.unnamed_typography.len(), 1);
+        assert_eq!(tokens.unnamed_typography[0].name, "body");
+        assert_eq!(tokens.shadows.len(), 1);
+        assert_eq!(tokens.shadows[0].name, "Elevation/1");
+        assert_eq!(tokens.shadows[0].layers.len(), 1);
+        assert_eq!(tokens.shadows[0].layers[0].blur, 4.0);
+    }
+
+    #[test]
+    fn nested_formats_group_by_style_path() {
+        let tokens = extract_design_tokens(&styled_file());
+
+        let sd: serde_json::Value = serde_json::from_str(
+            &export_style_dictionary(&tokens, ColorSpace::Hex).expect("export"),
+        )
+        .expect("json");
+        assert_eq!(sd["color"]["primary"]["500"]["value"], "#FF0000");
+        assert_eq!(sd["color"]["primary"]["DEFAULT"]["value"], "#0000FF");
+        assert_eq!(sd["color"]["unnamed"]["color-00ff00"]["value"], "#00FF00");
+
+        let tailwind = export_tailwind(&tokens, ColorSpace::Hex);
+        assert!(tailwind.contains("        'primary': {\n          '500': '#FF0000',\n          'DEFAULT': '#0000FF',\n        },\n"));
+
+        let css = export_css(&tokens, "", ColorSpace::Hex);
+        assert!(css.contains("--primary-500: #FF0000;"));
+        assert!(css.contains("/* Unnamed colors */"));
+    }
+
+    #[test]
+    fn only_plan_and_scope_errors_skip_variables() {
+        let api_error = |status| {
+            anyhow::Error::from(ApiError {
+                status,
+                body: String::new(),
+            })
+        };
+        assert!(variables_unavailable(&api_error(
+            reqwest::StatusCode::FORBIDDEN
+        )));
+        assert!(variables_unavailable(&api_error(
+            reqwest::StatusCode::NOT_FOUND
+        )));
+        assert!(!variables_unavailable(&api_error(
+            reqwest::StatusCode::INTERNAL_SERVER_ERROR
+        )));
+        assert!(!variables_unavailable(&anyhow!("connection reset")));
+    }
+
+    #[test]
+    fn value_names_never_repeat() {
+        let names = names_from_values("shadow", &[1.0, 2.0, 1.0]);
+        assert!(names[0].starts_with("shadow-"));
+        assert_eq!(names[0].len(), "shadow-".len() + 16);
+        assert_ne!(names[0], names[1]);
+        assert_eq!(names[2], format!("{}-2", names[0]));
+    }
+}
@@ -0,0 +1,666 @@
+//! Gradient and image paint tokens
+//!
+//! Gradients come from fill styles (named) and from raw node fills (unnamed).
+//! Handle positions are normalized to the node bounds with y pointing down,
+//! so code formats render them relative to the view they are applied to.
+//! CSS, SwiftUI and Compose have no diamond gradient; it is rendered as a
+//! radial one. Image paints only carry an image hash, so they are kept in the
+//! JSON export for tooling that downloads them through the images endpoint.
+
+use super::{
+    css_color, format_px, insert_token_path, kotlin_color, names_from_values, sanitize_token_name,
+    style_path, to_pascal_case, DesignTokens, UNNAMED_GROUP,
+};
+use crate::api::types::{Document, Node, Paint};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GradientKind {
+    Linear,
+    Radial,
+    Angular,
+    Diamond,
+}
+
+/// Color at a position (0..1) along the gradient
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GradientStop {
+    pub position: f64,
+    pub hex: String,
+    pub rgba: [f64; 4],
+}
+
+/// A linear, radial, angular or diamond gradient
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GradientToken {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub kind: GradientKind,
+    /// Start, end and width handles as `[x, y]`, normalized to the node bounds
+    pub handles: Vec<[f64; 2]>,
+    /// Stops with the paint opacity applied to their alpha
+    pub stops: Vec<GradientStop>,
+}
+
+/// An image fill, referenced by its image hash
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImagePaintToken {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub image_ref: String,
+    /// FILL, FIT, TILE or STRETCH
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scale_mode: Option<String>,
+}
+
+impl GradientToken {
+    fn handle(&self, index: usize) -> [f64; 2] {
+        self.handles
+            .get(index)
+            .copied()
+            .unwrap_or(default_handles(self.kind)[index])
+    }
+
+    fn same_paint(&self, other: &GradientToken) -> bool {
+        self.kind == other.kind && self.handles == other.handles && self.stops == other.stops
+    }
+}
+
+/// Figma's handles for a freshly added gradient: top to bottom, or centered
+fn default_handles(kind: GradientKind) -> [[f64; 2]; 3] {
+    match kind {
+        GradientKind::Linear => [[0.5, 0.0], [0.5, 1.0], [0.0, 0.0]],
+        _ => [[0.5, 0.5], [0.5, 1.0], [1.0, 0.5]],
+    }
+}
+
+/// Gradient of a visible gradient paint, named by the caller
+pub(super) fn gradient_token(
+    name: String,
+    description: Option<String>,
+    paint: &Paint,
+) -> Option<GradientToken> {
+    let kind = match paint.paint_type.as_str() {
+        "GRADIENT_LINEAR" => GradientKind::Linear,
+        "GRADIENT_RADIAL" => GradientKind::Radial,
+        "GRADIENT_ANGULAR" => GradientKind::Angular,
+        "GRADIENT_DIAMOND" => GradientKind::Diamond,
+        _ => return None,
+    };
+    let stops = paint
+        .gradient_stops
+        .as_ref()
+        .filter(|stops| !stops.is_empty());
+    let (true, Some(stops)) = (paint.visible, stops) else {
+        return None;
+    };
+    let opacity = paint.opacity.unwrap_or(1.0);
+    let handles = match &paint.gradient_handle_positions {
+        Some(handles) if handles.len() >= 2 => {
+            handles.iter().map(|handle| [handle.x, handle.y]).collect()
+        }
+        _ => default_handles(kind).to_vec(),
+    };
+    Some(GradientToken {
+        name,
+        description,
+        kind,
+        handles,
+        stops: stops
+            .iter()
+            .map(|stop| GradientStop {
+                position: stop.position,
+                hex: stop.color.to_hex(),
+                rgba: [
+                    stop.color.r,
+                    stop.color.g,
+                    stop.color.b,
+                    stop.color.a * opacity,
+                ],
+            })
+            .collect(),
+    })
+}
+
+/// Image reference of a visible image paint, named by the caller
+pub(super) fn image_token(
+    name: String,
+    description: Option<String>,
+    paint: &Paint,
+) -> Option<ImagePaintToken> {
+    if paint.paint_type != "IMAGE" || !paint.visible {
+        return None;
+    }
+    Some(ImagePaintToken {
+        name,
+        description,
+        image_ref: paint.image_ref.clone()?,
+        scale_mode: paint.scale_mode.clone(),
+    })
+}
+
+/// Gradients and images in fills without a fill style, minus paints a style already covers
+///
+/// Gradients are named after a hash of their kind, handles and stops
+/// (`gradient-9f86d081e0ac2b3f`), so a name only changes with the gradient itself;
+/// images are named after the start of their image hash (`image-1a2b3c4d`).
+pub fn extract_unnamed_paints(
+    document: &Document,
+    gradients: &[GradientToken],
+    images: &[ImagePaintToken],
+) -> (Vec<GradientToken>, Vec<ImagePaintToken>) {
+    let mut found_gradients: Vec<GradientToken> = Vec::new();
+    let mut found_images: Vec<ImagePaintToken> = Vec::new();
+    for node in document.children.iter().flatten() {
+        collect_unnamed_paints(node, &mut found_gradients, &mut found_images);
+    }
+
+    found_gradients.retain(|token| !gradients.iter().any(|named| named.same_paint(token)));
+    let names = names_from_values(
+        "gradient",
+        &found_gradients
+            .iter()
+            .map(|token| (token.kind, &token.handles, &token.stops))
+            .collect::<Vec<_>>(),
+    );
+    for (token, name) in found_gradients.iter_mut().zip(names) {
+        token.name = name;
+    }
+    found_gradients.sort_by(|a, b| a.name.cmp(&b.name));
+
+    found_images.retain(|token| {
+        !images
+            .iter()
+            .any(|named| named.image_ref == token.image_ref)
+    });
+    found_images.sort_by(|a, b| a.name.cmp(&b.name));
+
+    (found_gradients, found_images)
+}
+
+fn collect_unnamed_paints(
+    node: &Node,
+    gradients: &mut Vec<GradientToken>,
+    images: &mut Vec<ImagePaintToken>,
+) {
+    let styled = node
+        .styles
+        .as_ref()
+        .is_some_and(|styles| styles.contains_key("fill"));
+    if let (false, Some(fills)) = (styled, &node.fills) {
+        for fill in fills {
+            if let Some(token) = gradient_token(String::new(), None, fill) {
+                if !gradients.iter().any(|found| found.same_paint(&token)) {
+                    gradients.push(token);
+                }
+            }
+            if let Some(ref_name) = fill.image_ref.as_deref() {
+                let name = format!("image-{}", ref_name.chars().take(8).collect::<String>());
+                if let Some(token) = image_token(name, None, fill) {
+                    if !images
+                        .iter()
+                        .any(|found| found.image_ref == token.image_ref)
+                    {
+                        images.push(token);
+                    }
+                }
+            }
+        }
+    }
+
+    for child in node.children.iter().flatten() {
+        collect_unnamed_paints(child, gradients, images);
+    }
+}
+
+/// Gradients with their flat names, unnamed tokens keeping their generated names
+fn flat_gradients(tokens: &DesignTokens) -> Vec<(String, &GradientToken)> {
+    let named = tokens.gradients.iter().map(|token| {
+        (
+            format!("gradient-{}", sanitize_token_name(&token.name)),
+            token,
+        )
+    });
+    let unnamed = tokens
+        .unnamed_gradients
+        .iter()
+        .map(|token| (token.name.clone(), token));
+    named.chain(unnamed).collect()
+}
+
+/// Fraction with up to 4 decimals, for unit points and stop locations
+fn unit(value: f64) -> String {
+    let formatted = format!("{:.4}", value);
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+    match trimmed {
+        "-0" => "0".to_string(),
+        _ => trimmed.to_string(),
+    }
+}
+
+fn distance(a: [f64; 2], b: [f64; 2]) -> f64 {
+    (b[0] - a[0]).hypot(b[1] - a[1])
+}
+
+/// Direction from `from` to `to` in CSS degrees: 0 points up, clockwise
+fn css_angle(from: [f64; 2], to: [f64; 2]) -> f64 {
+    let angle = (to[0] - from[0])
+        .atan2(from[1] - to[1])
+        .to_degrees()
+        .rem_euclid(360.0);
+    (angle * 100.0).round() / 100.0 % 360.0
+}
+
+// CSS: linear-, radial- and conic-gradient() values
+
+/// Gradient as a CSS `background-image` value
+pub(super) fn css_gradient(token: &GradientToken) -> String {
+    let (start, end) = (token.handle(0), token.handle(1));
+    match token.kind {
+        GradientKind::Linear => {
+            // Map stops from the handle line onto the CSS gradient line, which
+            // runs through the box center and spans it at the given angle
+            let angle = css_angle(start, end);
+            let (sin, cos) = angle.to_radians().sin_cos();
+            let length = sin.abs() + cos.abs();
+            let project =
+                |point: [f64; 2]| ((point[0] - 0.5) * sin - (point[1] - 0.5) * cos) / length + 0.5;
+            let (from, to) = (project(start), project(end));
+            let stops = css_stops(token, |position| from + position * (to - from));
+            format!("linear-gradient({}deg, {})", format_px(angle), stops)
+        }
+        GradientKind::Radial | GradientKind::Diamond => format!(
+            "radial-gradient(ellipse {}% {}% at {}% {}%, {})",
+            format_px(distance(start, end) * 100.0),
+            format_px(distance(start, token.handle(2)) * 100.0),
+            format_px(start[0] * 100.0),
+            format_px(start[1] * 100.0),
+            css_stops(token, |position| position)
+        ),
+        GradientKind::Angular => format!(
+            "conic-gradient(from {}deg at {}% {}%, {})",
+            format_px(css_angle(start, end)),
+            format_px(start[0] * 100.0),
+            format_px(start[1] * 100.0),
+            css_stops(token, |position| position)
+        ),
+    }
+}
+
+fn css_stops(token: &GradientToken, position: impl Fn(f64) -> f64) -> String {
+    token
+        .stops
+        .iter()
+        .map(|stop| {
+            format!(
+                "{} {}%",
+                css_color(&stop.hex, &stop.rgba),
+                format_px(position(stop.position) * 100.0)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+pub(super) fn css(tokens: &DesignTokens, css_prefix: &str) -> String {
+    let gradients = flat_gradients(tokens);
+    if gradients.is_empty() {
+        return String::new();
+    }
+    let mut css = String::from("\n  /* Gradients */\n");
+    for (name, token) in gradients {
+        css.push_str(&format!(
+            "  {}{}: {};\n",
+            css_prefix,
+            name,
+            css_gradient(token)
+        ));
+    }
+    css
+}
+
+// SwiftUI: LinearGradient, EllipticalGradient and AngularGradient in unit points
+
+pub(super) fn swift(tokens: &DesignTokens, type_prefix: &str) -> String {
+    let gradients = flat_gradients(tokens);
+    if gradients.is_empty() {
+        return String::new();
+    }
+    let mut swift = format!("\nenum {}Gradients {{\n", type_prefix);
+    for (name, token) in gradients {
+        let (start, end) = (token.handle(0), token.handle(1));
+        let stops: Vec<String> = token
+            .stops
+            .iter()
+            .map(|stop| {
+                format!(
+                    ".init(color: Color(red: {:.3}, green: {:.3}, blue: {:.3}, opacity: {:.3}), location: {})",
+                    stop.rgba[0],
+                    stop.rgba[1],
+                    stop.rgba[2],
+                    stop.rgba[3],
+                    unit(stop.position)
+                )
+            })
+            .collect();
+        let stops = stops.join(", ");
+        let gradient = match token.kind {
+            GradientKind::Linear => format!(
+                "LinearGradient(stops: [{}], startPoint: {}, endPoint: {})",
+                stops,
+                swift_point(start),
+                swift_point(end)
+            ),
+            // A fraction of 1 reaches the edges of the view, half a unit from the center
+            GradientKind::Radial | GradientKind::Diamond => format!(
+                "EllipticalGradient(stops: [{}], center: {}, startRadiusFraction: 0, endRadiusFraction: {})",
+                stops,
+                swift_point(start),
+                unit(distance(start, end) * 2.0)
+            ),
+            // SwiftUI angles start at the trailing edge instead of the top
+            GradientKind::Angular => format!(
+                "AngularGradient(stops: [{}], center: {}, angle: .degrees({}))",
+                stops,
+                swift_point(start),
+                format_px(css_angle(start, end) - 90.0)
+            ),
+        };
+        swift.push_str(&format!(
+            "    static let {} = {}\n",
+            to_pascal_case(&name),
+            gradient
+        ));
+    }
+    swift.push_str("}\n");
+    swift
+}
+
+fn swift_point(point: [f64; 2]) -> String {
+    format!("UnitPoint(x: {}, y: {})", unit(point[0]), unit(point[1]))
+}
+
+// Compose: size-relative ShaderBrush instances
+
+/// Imports the Compose output needs for gradient brushes
+pub(super) fn kotlin_imports(tokens: &DesignTokens) -> &'static str {
+    if tokens.gradients.is_empty() && tokens.unnamed_gradients.is_empty() {
+        ""
+    } else {
+        "import androidx.compose.ui.geometry.Offset\nimport androidx.compose.ui.geometry.Size\nimport androidx.compose.ui.graphics.Brush\nimport androidx.compose.ui.graphics.LinearGradientShader\nimport androidx.compose.ui.graphics.RadialGradientShader\nimport androidx.compose.ui.graphics.Shader\nimport androidx.compose.ui.graphics.ShaderBrush\nimport androidx.compose.ui.graphics.SweepGradientShader\n"
+    }
+}
+
+pub(super) fn kotlin(tokens: &DesignTokens, type_prefix: &str) -> String {
+    let gradients = flat_gradients(tokens);
+    if gradients.is_empty() {
+        return String::new();
+    }
+    let mut kotlin =
+        String::from("\n/** Brush whose shader is laid out on the size of what it paints */\n");
+    kotlin.push_str(
+        "private fun gradientBrush(shader: (Size) -> Shader): Brush = object : ShaderBrush() {\n",
+    );
+    kotlin.push_str("    override fun createShader(size: Size): Shader = shader(size)\n");
+    kotlin.push_str("}\n\n");
+
+    kotlin.push_str(&format!("object {}Gradients {{\n", type_prefix));
+    for (name, token) in gradients {
+        let (start, end) = (token.handle(0), token.handle(1));
+        let colors: Vec<String> = token
+            .stops
+            .iter()
+            .map(|stop| kotlin_color(&stop.hex, stop.rgba[3]))
+            .collect();
+        let positions: Vec<String> = token
+            .stops
+            .iter()
+            .map(|stop| format!("{}f", unit(stop.position)))
+            .collect();
+        let stops = format!(
+            "colors = listOf({}), colorStops = listOf({})",
+            colors.join(", "),
+            positions.join(", ")
+        );
+        let shader = match token.kind {
+            GradientKind::Linear => format!(
+                "LinearGradientShader(from = {}, to = {}, {})",
+                kotlin_offset(start),
+                kotlin_offset(end),
+                stops
+            ),
+            GradientKind::Radial | GradientKind::Diamond => format!(
+                "RadialGradientShader(center = {}, radius = Offset(size.width * {}f, size.height * {}f).getDistance(), {})",
+                kotlin_offset(start),
+                unit(end[0] - start[0]),
+                unit(end[1] - start[1]),
+                stops
+            ),
+            // Sweep gradients always start at 3 o'clock
+            GradientKind::Angular => format!(
+                "SweepGradientShader(center = {}, {})",
+                kotlin_offset(start),
+                stops
+            ),
+        };
+        kotlin.push_str(&format!(
+            "    val {}: Brush = gradientBrush {{ size -> {} }}\n",
+            to_pascal_case(&name),
+            shader
+        ));
+    }
+    kotlin.push_str("}\n");
+    kotlin
+}
+
+fn kotlin_offset(point: [f64; 2]) -> String {
+    format!(
+        "Offset(size.width * {}f, size.height * {}f)",
+        unit(point[0]),
+        unit(point[1])
+    )
+}
+
+// Tailwind: backgroundImage utilities (bg-gradient-*)
+
+pub(super) fn tailwind(tokens: &DesignTokens) -> String {
+    let gradients = flat_gradients(tokens);
+    if gradients.is_empty() {
+        return String::new();
+    }
+    let mut output = String::from("      backgroundImage: {\n");
+    for (name, token) in gradients {
+        output.push_str(&format!("        '{}': '{}',\n", name, css_gradient(token)));
+    }
+    output.push_str("      },\n");
+    output
+}
+
+// Style Dictionary: CSS gradient values nested by style path
+
+pub(super) fn style_dictionary(tokens: &DesignTokens, root: &mut serde_json::Value) {
+    let mut gradient = serde_json::Map::new();
+    for token in &tokens.gradients {
+        insert_token_path(
+            &mut gradient,
+            &style_path(&token.name),
+            serde_json::json!({ "value": css_gradient(token) }),
+        );
+    }
+    for token in &tokens.unnamed_gradients {
+        insert_token_path(
+            &mut gradient,
+            &[UNNAMED_GROUP.to_string(), token.name.clone()],
+            serde_json::json!({ "value": css_gradient(token) }),
+        );
+    }
+    if !gradient.is_empty() {
+        root["gradient"] = serde_json::Value::Object(gradient);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::ColorSpace;
+    use crate::commands::tokens::{export_css, export_kotlin, export_swift, extract_design_tokens};
+
+    fn file() -> crate::api::types::File {
+        serde_json::from_value(serde_json::json!({
+            "name": "Gradients",
+            "lastModified": "2024-01-01T00:00:00Z",
+            "version": "1",
+            "components": {},
+            "document": {
+                "id": "0:0",
+                "name": "Document",
+                "type": "DOCUMENT",
+                "children": [{
+                    "id": "1:1",
+                    "name": "Hero",
+                    "type": "FRAME",
+                    "styles": { "fill": "S:brand" },
+                    "fills": [{
+                        "type": "GRADIENT_LINEAR",
+                        "gradientHandlePositions": [
+                            { "x": 0.0, "y": 0.5 }, { "x": 1.0, "y": 0.5 }, { "x": 0.0, "y": 1.0 }
+                        ],
+                        "gradientStops": [
+                            { "position": 0.0, "color": { "r": 1.0, "g": 0.0, "b": 0.0, "a": 1.0 } },
+                            { "position": 1.0, "color": { "r": 0.0, "g": 0.0, "b": 1.0, "a": 1.0 } }
+                        ]
+                    }],
+                    "children": [{
+                        "id": "1:2",
+                        "name": "Glow",
+                        "type": "ELLIPSE",
+                        "fills": [{
+                            "type": "GRADIENT_RADIAL",
+                            "opacity": 0.5,
+                            "gradientHandlePositions": [
+                                { "x": 0.5, "y": 0.5 }, { "x": 1.0, "y": 0.5 }, { "x": 0.5, "y": 1.0 }
+                            ],
+                            "gradientStops": [
+                                { "position": 0.0, "color": { "r": 1.0, "g": 1.0, "b": 1.0, "a": 1.0 } },
+                                { "position": 1.0, "color": { "r": 1.0, "g": 1.0, "b": 1.0, "a": 0.0 } }
+                            ]
+                        }]
+                    }, {
+                        "id": "1:3",
+                        "name": "Avatar",
+                        "type": "RECTANGLE",
+                        "fills": [
+                            { "type": "IMAGE", "imageRef": "1a2b3c4d5e6f", "scaleMode": "FILL" },
+                            { "type": "GRADIENT_ANGULAR", "visible": false, "gradientStops": [
+                                { "position": 0.0, "color": { "r": 0.0, "g": 0.0, "b": 0.0, "a": 1.0 } }
+                            ] }
+                        ]
+                    }]
+                }]
+            },
+            "styles": {
+                "S:brand": {
+                    "key": "brand",
+                    "name": "Brand/Sunset",
+                    "styleType": "FILL",
+                    "description": ""
+                }
+            }
+        }))
+        .expect("file")
+    }
+
+    #[test]
+    fn extracts_named_and_unnamed_paints() {
+        let tokens = extract_design_tokens(&file());
+
+        assert_eq!(tokens.gradients.len(), 1);
+        assert_eq!(tokens.gradients[0].name, "Brand/Sunset");
+        assert_eq!(tokens.gradients[0].kind, GradientKind::Linear);
+        assert_eq!(tokens.unnamed_gradients.len(), 1);
+        let glow = &tokens.unnamed_gradients[0];
+        assert!(glow.name.starts_with("gradient-"));
+        assert_eq!(glow.kind, GradientKind::Radial);
+        assert_eq!(glow.stops[0].rgba[3], 0.5);
+        assert_eq!(tokens.images.len(), 1);
+        assert_eq!(tokens.images[0].name, "image-1a2b3c4d");
+        assert_eq!(tokens.images[0].scale_mode.as_deref(), Some("FILL"));
+        assert!(tokens.colors.is_empty());
+    }
+
+    #[test]
+    fn unnamed_gradient_names_do_not_depend_on_order() {
+        let glow = extract_design_tokens(&file()).unnamed_gradients[0]
+            .name
+            .clone();
+
+        let mut file = file();
+        let hero = &mut file.document.children.as_mut().unwrap()[0];
+        let mut first = hero.children.as_ref().unwrap()[0].clone();
+        first.id = "1:0".to_string();
+        first.fills.as_mut().unwrap()[0].opacity = Some(0.25);
+        hero.children.as_mut().unwrap().insert(0, first);
+
+        let tokens = extract_design_tokens(&file);
+        assert_eq!(tokens.unnamed_gradients.len(), 2);
+        assert_ne!(
+            tokens.unnamed_gradients[0].name,
+            tokens.unnamed_gradients[1].name
+        );
+        assert!(tokens
+            .unnamed_gradients
+            .iter()
+            .any(|token| token.name == glow));
+    }
+
+    #[test]
+    fn css_gradients_follow_the_handles() {
+        let tokens = extract_design_tokens(&file());
+
+        assert_eq!(
+            css_gradient(&tokens.gradients[0]),
+            "linear-gradient(90deg, #FF0000 0%, #0000FF 100%)"
+        );
+        assert_eq!(
+            css_gradient(&tokens.unnamed_gradients[0]),
+            "radial-gradient(ellipse 50% 50% at 50% 50%, rgba(255, 255, 255, 0.5) 0%, rgba(255, 255, 255, 0) 100%)"
+        );
+
+        // A diagonal handle shorter than the CSS gradient line moves the stops inwards
+        let mut diagonal = tokens.gradients[0].clone();
+        diagonal.handles = vec![[0.25, 0.25], [0.75, 0.75]];
+        assert_eq!(
+            css_gradient(&diagonal),
+            "linear-gradient(135deg, #FF0000 25%, #0000FF 75%)"
+        );
+    }
+
+    #[test]
+    fn code_formats_emit_gradients() {
+        let tokens = extract_design_tokens(&file());
+
+        let css = export_css(&tokens, "--", ColorSpace::Hex);
+        assert!(css.contains("--gradient-brand-sunset: linear-gradient(90deg"));
+        assert!(css.contains(&format!(
+            "--{}: radial-gradient(",
+            tokens.unnamed_gradients[0].name
+        )));
+
+        let swift = export_swift(&tokens, "");
+        assert!(swift.contains(
+            "static let GradientBrandSunset = LinearGradient(stops: [.init(color: Color(red: 1.000, green: 0.000, blue: 0.000, opacity: 1.000), location: 0)"
+        ));
+        assert!(swift
+            .contains("startPoint: UnitPoint(x: 0, y: 0.5), endPoint: UnitPoint(x: 1, y: 0.5))"));
+        assert!(swift.contains("EllipticalGradient("));
+
+        let kotlin = export_kotlin(&tokens, "");
+        assert!(kotlin.contains("import androidx.compose.ui.graphics.ShaderBrush"));
+        assert!(kotlin.contains(
+            "val GradientBrandSunset: Brush = gradientBrush { size -> LinearGradientShader(from = Offset(size.width * 0f, size.height * 0.5f), to = Offset(size.width * 1f, size.height * 0.5f), colors = listOf(Color(0xFFFF0000), Color(0xFF0000FF)), colorStops = listOf(0f, 1f)) }"
+        ));
+    }
+}
@@ -0,0 +1,523 @@
+//! Text style properties and their per-platform rendering
+//!
+//! Figma line heights are set in px, in % of the font size, or left on auto.
+//! Every token keeps the px value Figma computed; CSS and DTCG also get the
+//! relative form, and auto line heights are left to the platform default in
+//! code formats. Numeric weights map to the nearest named platform weight.
+
+use super::{format_px, sanitize_token_name, to_pascal_case, DesignTokens, TypographyToken};
+use crate::api::types::TypeStyle;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LineHeightUnit {
+    Pixels,
+    /// Percent of the font size
+    Percent,
+    /// Font default (Figma's INTRINSIC_%)
+    Auto,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TextCase {
+    Upper,
+    Lower,
+    Title,
+    SmallCaps,
+    /// Small caps for uppercase letters too
+    SmallCapsForced,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TextDecoration {
+    Underline,
+    Strikethrough,
+}
+
+pub(super) fn typography_token(
+    name: String,
+    description: Option<String>,
+    style: &TypeStyle,
+) -> TypographyToken {
+    let line_height_unit = match style.line_height_unit.as_deref() {
+        Some("PIXELS") => Some(LineHeightUnit::Pixels),
+        Some("FONT_SIZE_%") => Some(LineHeightUnit::Percent),
+        Some("INTRINSIC_%") => Some(LineHeightUnit::Auto),
+        _ => None,
+    };
+    TypographyToken {
+        name,
+        description,
+        family: style.font_family.clone(),
+        post_script_name: style.font_post_script_name.clone(),
+        size: style.font_size,
+        weight: style.font_weight,
+        italic: style.italic,
+        line_height: style.line_height_px,
+        line_height_unit,
+        line_height_percent: style
+            .line_height_percent_font_size
+            .filter(|_| line_height_unit == Some(LineHeightUnit::Percent)),
+        letter_spacing: style.letter_spacing,
+        paragraph_spacing: style.paragraph_spacing.filter(|spacing| *spacing != 0.0),
+        text_case: match style.text_case.as_deref() {
+            Some("UPPER") => Some(TextCase::Upper),
+            Some("LOWER") => Some(TextCase::Lower),
+            Some("TITLE") => Some(TextCase::Title),
+            Some("SMALL_CAPS") => Some(TextCase::SmallCaps),
+            Some("SMALL_CAPS_FORCED") => Some(TextCase::SmallCapsForced),
+            _ => None,
+        },
+        text_decoration: match style.text_decoration.as_deref() {
+            Some("UNDERLINE") => Some(TextDecoration::Underline),
+            Some("STRIKETHROUGH") => Some(TextDecoration::Strikethrough),
+            _ => None,
+        },
+        font_features: style.opentype_flags.clone(),
+    }
+}
+
+impl TypographyToken {
+    /// Line height in px for code formats; `None` when left on auto
+    pub fn fixed_line_height(&self) -> Option<f64> {
+        match self.line_height_unit {
+            Some(LineHeightUnit::Auto) => None,
+            _ => self.line_height,
+        }
+    }
+
+    /// Line height relative to the font size (1.5 for 150%)
+    pub fn line_height_ratio(&self) -> Option<f64> {
+        let ratio = match (self.line_height_percent, self.size, self.line_height) {
+            (Some(percent), _, _) => percent / 100.0,
+            (None, Some(size), Some(line_height)) if size > 0.0 => line_height / size,
+            _ => return None,
+        };
+        Some((ratio * 10_000.0).round() / 10_000.0)
+    }
+}
+
+/// Weight rounded to the nearest hundred, 100 to 900
+fn weight_step(weight: f64) -> usize {
+    ((weight / 100.0).round() as usize).clamp(1, 9)
+}
+
+/// SwiftUI `Font.Weight` for a numeric weight (`.semibold` for 600)
+pub fn swift_weight(weight: f64) -> &'static str {
+    [
+        ".ultraLight",
+        ".thin",
+        ".light",
+        ".regular",
+        ".medium",
+        ".semibold",
+        ".bold",
+        ".heavy",
+        ".black",
+    ][weight_step(weight) - 1]
+}
+
+/// Compose `FontWeight` for a numeric weight (`FontWeight.SemiBold` for 600)
+pub fn kotlin_weight(weight: f64) -> &'static str {
+    [
+        "FontWeight.Thin",
+        "FontWeight.ExtraLight",
+        "FontWeight.Light",
+        "FontWeight.Normal",
+        "FontWeight.Medium",
+        "FontWeight.SemiBold",
+        "FontWeight.Bold",
+        "FontWeight.ExtraBold",
+        "FontWeight.Black",
+    ][weight_step(weight) - 1]
+}
+
+/// CSS `font-feature-settings` syntax, also accepted by Compose
+fn feature_settings(features: &BTreeMap<String, u32>) -> Option<String> {
+    if features.is_empty() {
+        return None;
+    }
+    let settings: Vec<String> = features
+        .iter()
+        .map(|(tag, value)| format!("'{}' {}", tag.to_lowercase(), value))
+        .collect();
+    Some(settings.join(", "))
+}
+
+fn quoted(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+pub(super) fn css(tokens: &DesignTokens, css_prefix: &str) -> String {
+    let mut css = String::from("\n  /* Typography */\n");
+    for token in tokens.all_typography() {
+        let property = |suffix: &str, value: String| {
+            format!(
+                "  {}font-{}-{}: {};\n",
+                css_prefix,
+                sanitize_token_name(&token.name),
+                suffix,
+                value
+            )
+        };
+        css.push_str(&format!("  /* {} */\n", token.name));
+        if let Some(family) = &token.family {
+            css.push_str(&property("family", format!("\"{}\"", family)));
+        }
+        if let Some(size) = token.size {
+            css.push_str(&property("size", format!("{:.2}px", size)));
+        }
+        if let Some(weight) = token.weight {
+            css.push_str(&property("weight", format!("{:.0}", weight)));
+        }
+        if token.italic {
+            css.push_str(&property("style", "italic".to_string()));
+        }
+        let line_height = match token.line_height_unit {
+            Some(LineHeightUnit::Auto) => Some("normal".to_string()),
+            Some(LineHeightUnit::Percent) => {
+                token.line_height_ratio().map(|ratio| ratio.to_string())
+            }
+            _ => None,
+        }
+        .or_else(|| {
+            token
+                .line_height
+                .map(|line_height| format!("{:.2}px", line_height))
+        });
+        if let Some(line_height) = line_height {
+            css.push_str(&property("line-height", line_height));
+        }
+        if let Some(letter_spacing) = token.letter_spacing {
+            css.push_str(&property(
+                "letter-spacing",
+                format!("{:.2}px", letter_spacing),
+            ));
+        }
+        if let Some(spacing) = token.paragraph_spacing {
+            css.push_str(&property(
+                "paragraph-spacing",
+                format!("{}px", format_px(spacing)),
+            ));
+        }
+        match token.text_case {
+            Some(TextCase::Upper) => css.push_str(&property("text-transform", "uppercase".into())),
+            Some(TextCase::Lower) => css.push_str(&property("text-transform", "lowercase".into())),
+            Some(TextCase::Title) => css.push_str(&property("text-transform", "capitalize".into())),
+            Some(TextCase::SmallCaps) => {
+                css.push_str(&property("variant-caps", "small-caps".into()))
+            }
+            Some(TextCase::SmallCapsForced) => {
+                css.push_str(&property("variant-caps", "all-small-caps".into()))
+            }
+            None => {}
+        }
+        match token.text_decoration {
+            Some(TextDecoration::Underline) => {
+                css.push_str(&property("text-decoration", "underline".into()))
+            }
+            Some(TextDecoration::Strikethrough) => {
+                css.push_str(&property("text-decoration", "line-through".into()))
+            }
+            None => {}
+        }
+        if let Some(settings) = feature_settings(&token.font_features) {
+            css.push_str(&property("feature-settings", settings));
+        }
+    }
+    css
+}
+
+pub(super) fn swift(tokens: &DesignTokens, type_prefix: &str) -> String {
+    let mut swift = String::from("struct TypographyToken {\n");
+    swift.push_str("    var family: String? = nil\n");
+    swift.push_str("    var postScriptName: String? = nil\n");
+    swift.push_str("    var size: Double? = nil\n");
+    swift.push_str("    var weight: Font.Weight? = nil\n");
+    swift.push_str("    var italic: Bool = false\n");
+    swift.push_str("    var lineHeight: Double? = nil\n");
+    swift.push_str("    var letterSpacing: Double? = nil\n");
+    swift.push_str("    var paragraphSpacing: Double? = nil\n");
+    swift.push_str("    var textCase: Text.Case? = nil\n");
+    swift.push_str("    var smallCaps: Bool = false\n");
+    swift.push_str("    var underline: Bool = false\n");
+    swift.push_str("    var strikethrough: Bool = false\n");
+    swift.push_str("    var fontFeatures: [String: Int] = [:]\n\n");
+    swift.push_str("    var font: Font {\n");
+    swift.push_str("        let size = CGFloat(self.size ?? 17)\n");
+    swift.push_str(
+        "        var font = (postScriptName ?? family).map { Font.custom($0, size: size) } ?? .system(size: size)\n",
+    );
+    swift.push_str("        if let weight = weight { font = font.weight(weight) }\n");
+    swift.push_str("        if italic { font = font.italic() }\n");
+    swift.push_str("        if smallCaps { font = font.smallCaps() }\n");
+    swift.push_str("        return font\n");
+    swift.push_str("    }\n");
+    swift.push_str("}\n\n");
+
+    swift.push_str(&format!("enum {}Typography {{\n", type_prefix));
+    for token in tokens.all_typography() {
+        // Arguments must follow the declaration order of the memberwise init
+        let mut arguments = Vec::new();
+        if let Some(family) = &token.family {
+            arguments.push(format!("family: {}", quoted(family)));
+        }
+        if let Some(name) = &token.post_script_name {
+            arguments.push(format!("postScriptName: {}", quoted(name)));
+        }
+        if let Some(size) = token.size {
+            arguments.push(format!("size: {:.2}", size));
+        }
+        if let Some(weight) = token.weight {
+            arguments.push(format!("weight: {}", swift_weight(weight)));
+        }
+        if token.italic {
+            arguments.push("italic: true".to_string());
+        }
+        if let Some(line_height) = token.fixed_line_height() {
+            arguments.push(format!("lineHeight: {:.2}", line_height));
+        }
+        if let Some(letter_spacing) = token.letter_spacing {
+            arguments.push(format!("letterSpacing: {:.2}", letter_spacing));
+        }
+        if let Some(spacing) = token.paragraph_spacing {
+            arguments.push(format!("paragraphSpacing: {:.2}", spacing));
+        }
+        match token.text_case {
+            // SwiftUI has no title case
+            Some(TextCase::Upper) => arguments.push("textCase: .uppercase".to_string()),
+            Some(TextCase::Lower) => arguments.push("textCase: .lowercase".to_string()),
+            Some(TextCase::SmallCaps | TextCase::SmallCapsForced) => {
+                arguments.push("smallCaps: true".to_string())
+            }
+            Some(TextCase::Title) | None => {}
+        }
+        match token.text_decoration {
+            Some(TextDecoration::Underline) => arguments.push("underline: true".to_string()),
+            Some(TextDecoration::Strikethrough) => {
+                arguments.push("strikethrough: true".to_string())
+            }
+            None => {}
+        }
+        if !token.font_features.is_empty() {
+            let features: Vec<String> = token
+                .font_features
+                .iter()
+                .map(|(tag, value)| format!("{}: {}", quoted(&tag.to_lowercase()), value))
+                .collect();
+            arguments.push(format!("fontFeatures: [{}]", features.join(", ")));
+        }
+        swift.push_str(&format!(
+            "    static let {} = TypographyToken({})\n",
+            to_pascal_case(&token.name),
+            arguments.join(", ")
+        ));
+    }
+    swift.push_str("}\n");
+    swift
+}
+
+pub(super) fn kotlin_imports() -> &'static str {
+    "import androidx.compose.ui.text.font.FontStyle\nimport androidx.compose.ui.text.font.FontWeight\nimport androidx.compose.ui.text.style.TextDecoration\n"
+}
+
+pub(super) fn kotlin(tokens: &DesignTokens, type_prefix: &str) -> String {
+    let mut kotlin =
+        String::from("enum class TextCase { Upper, Lower, Title, SmallCaps, SmallCapsForced }\n\n");
+    kotlin.push_str("data class TypographyToken(\n");
+    kotlin.push_str("    val family: String? = null,\n");
+    kotlin.push_str("    val postScriptName: String? = null,\n");
+    kotlin.push_str("    val size: Float? = null,\n");
+    kotlin.push_str("    val weight: FontWeight? = null,\n");
+    kotlin.push_str("    val fontStyle: FontStyle = FontStyle.Normal,\n");
+    kotlin.push_str("    val lineHeight: Float? = null,\n");
+    kotlin.push_str("    val letterSpacing: Float? = null,\n");
+    kotlin.push_str("    val paragraphSpacing: Float? = null,\n");
+    kotlin.push_str("    val textCase: TextCase? = null,\n");
+    kotlin.push_str("    val textDecoration: TextDecoration? = null,\n");
+    kotlin.push_str("    val fontFeatureSettings: String? = null\n");
+    kotlin.push_str(")\n\n");
+
+    kotlin.push_str(&format!("object {}Typography {{\n", type_prefix));
+    for token in tokens.all_typography() {
+        let mut arguments = Vec::new();
+        if let Some(family) = &token.family {
+            arguments.push(format!("family = {}", quoted(family)));
+        }
+        if let Some(name) = &token.post_script_name {
+            arguments.push(format!("postScriptName = {}", quoted(name)));
+        }
+        if let Some(size) = token.size {
+            arguments.push(format!("size = {:.2}f", size));
+        }
+        if let Some(weight) = token.weight {
+            arguments.push(format!("weight = {}", kotlin_weight(weight)));
+        }
+        if token.italic {
+            arguments.push("fontStyle = FontStyle.Italic".to_string());
+        }
+        if let Some(line_height) = token.fixed_line_height() {
+            arguments.push(format!("lineHeight = {:.2}f", line_height));
+        }
+        if let Some(letter_spacing) = token.letter_spacing {
+            arguments.push(format!("letterSpacing = {:.2}f", letter_spacing));
+        }
+        if let Some(spacing) = token.paragraph_spacing {
+            arguments.push(format!("paragraphSpacing = {:.2}f", spacing));
+        }
+        if let Some(text_case) = token.text_case {
+            let case = match text_case {
+                TextCase::Upper => "Upper",
+                TextCase::Lower => "Lower",
+                TextCase::Title => "Title",
+                TextCase::SmallCaps => "SmallCaps",
+                TextCase::SmallCapsForced => "SmallCapsForced",
+            };
+            arguments.push(format!("textCase = TextCase.{}", case));
+        }
+        match token.text_decoration {
+            Some(TextDecoration::Underline) => {
+                arguments.push("textDecoration = TextDecoration.Underline".to_string())
+            }
+            Some(TextDecoration::Strikethrough) => {
+                arguments.push("textDecoration = TextDecoration.LineThrough".to_string())
+            }
+            None => {}
+        }
+        if let Some(settings) = feature_settings(&token.font_features) {
+            arguments.push(format!("fontFeatureSettings = {}", quoted(&settings)));
+        }
+        kotlin.push_str(&format!(
+            "    val {} = TypographyToken({})\n",
+            to_pascal_case(&token.name),
+            arguments.join(", ")
+        ));
+    }
+    kotlin.push_str("}\n");
+    kotlin
+}
+
+/// Style Dictionary value of a text style
+pub(super) fn style_dictionary(token: &TypographyToken) -> serde_json::Value {
+    let mut value = serde_json::json!({
+        "family": token.family,
+        "size": token.size,
+        "weight": token.weight,
+        "lineHeight": token.fixed_line_height(),
+        "letterSpacing": token.letter_spacing
+    });
+    let extras = [
+        ("postScriptName", serde_json::json!(token.post_script_name)),
+        (
+            "fontStyle",
+            serde_json::json!(token.italic.then_some("italic")),
+        ),
+        (
+            "lineHeightRatio",
+            serde_json::json!(token
+                .line_height_unit
+                .filter(|unit| *unit == LineHeightUnit::Percent)
+                .and(token.line_height_ratio())),
+        ),
+        (
+            "paragraphSpacing",
+            serde_json::json!(token.paragraph_spacing),
+        ),
+        ("textCase", serde_json::json!(token.text_case)),
+        ("textDecoration", serde_json::json!(token.text_decoration)),
+        (
+            "fontFeatureSettings",
+            serde_json::json!(feature_settings(&token.font_features)),
+        ),
+    ];
+    for (key, extra) in extras {
+        if !extra.is_null() {
+            value[key] = extra;
+        }
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn style() -> TypeStyle {
+        serde_json::from_value(serde_json::json!({
+            "fontFamily": "Inter",
+            "fontPostScriptName": "Inter-SemiBoldItalic",
+            "fontWeight": 600,
+            "fontSize": 16,
+            "italic": true,
+            "lineHeightPx": 24,
+            "lineHeightPercentFontSize": 150,
+            "lineHeightUnit": "FONT_SIZE_%",
+            "letterSpacing": 0.5,
+            "paragraphSpacing": 8,
+            "textCase": "UPPER",
+            "textDecoration": "UNDERLINE",
+            "opentypeFlags": { "TNUM": 1, "LIGA": 0 }
+        }))
+        .expect("style")
+    }
+
+    #[test]
+    fn maps_weights_to_platform_names() {
+        assert_eq!(swift_weight(600.0), ".semibold");
+        assert_eq!(kotlin_weight(600.0), "FontWeight.SemiBold");
+        assert_eq!(swift_weight(400.0), ".regular");
+        assert_eq!(kotlin_weight(400.0), "FontWeight.Normal");
+        assert_eq!(swift_weight(649.0), ".semibold");
+        assert_eq!(kotlin_weight(950.0), "FontWeight.Black");
+        assert_eq!(swift_weight(0.0), ".ultraLight");
+    }
+
+    #[test]
+    fn keeps_full_text_style_properties() {
+        let token = typography_token("Label".to_string(), None, &style());
+        assert_eq!(token.line_height_unit, Some(LineHeightUnit::Percent));
+        assert_eq!(token.line_height_ratio(), Some(1.5));
+        assert_eq!(token.text_case, Some(TextCase::Upper));
+        let tokens = DesignTokens {
+            typography: vec![token],
+            ..Default::default()
+        };
+
+        let css = css(&tokens, "--");
+        assert!(css.contains("--font-label-style: italic;"));
+        assert!(css.contains("--font-label-line-height: 1.5;"));
+        assert!(css.contains("--font-label-text-transform: uppercase;"));
+        assert!(css.contains("--font-label-text-decoration: underline;"));
+        assert!(css.contains("--font-label-feature-settings: 'liga' 0, 'tnum' 1;"));
+
+        let swift = swift(&tokens, "");
+        assert!(swift.contains(
+            "static let Label = TypographyToken(family: \"Inter\", postScriptName: \"Inter-SemiBoldItalic\", size: 16.00, weight: .semibold, italic: true, lineHeight: 24.00, letterSpacing: 0.50, paragraphSpacing: 8.00, textCase: .uppercase, underline: true, fontFeatures: [\"liga\": 0, \"tnum\": 1])"
+        ));
+
+        let kotlin = kotlin(&tokens, "");
+        assert!(kotlin.contains("weight = FontWeight.SemiBold, fontStyle = FontStyle.Italic"));
+        assert!(kotlin.contains("textDecoration = TextDecoration.Underline"));
+        assert!(kotlin.contains("fontFeatureSettings = \"'liga' 0, 'tnum' 1\""));
+    }
+
+    #[test]
+    fn auto_line_height_is_left_to_the_platform() {
+        let mut style = style();
+        style.line_height_unit = Some("INTRINSIC_%".to_string());
+        let token = typography_token("Body".to_string(), None, &style);
+        assert_eq!(token.line_height, Some(24.0));
+        assert_eq!(token.line_height_percent, None);
+        assert_eq!(token.fixed_line_height(), None);
+        let tokens = DesignTokens {
+            typography: vec![token],
+            ..Default::default()
+        };
+        assert!(css(&tokens, "--").contains("--font-body-line-height: normal;"));
+        assert!(!swift(&tokens, "").contains("lineHeight: 24"));
+    }
+}
@@ -0,0 +1,935 @@
+//! Figma Variables as per-mode design tokens
+//!
+//! Collections keep their modes (default mode first). Alias values are either
+//! resolved to concrete values or kept as references to the target variable.
+//! Flat formats share one namespace with style tokens, so variables get their
+//! own: `--var-*` properties, `var_*` Android resources, `var-*` Tailwind
+//! colors and `<Collection>Variables` Swift and Kotlin types.
+
+use super::{
+    android_color, css_color, format_px, kotlin_color, sanitize_token_name, to_pascal_case,
+};
+use crate::api::types::{LocalVariablesMeta, Variable, VariableCollection, VariableValue};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+const MAX_ALIAS_DEPTH: usize = 16;
+
+/// How alias variables are emitted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AliasMode {
+    /// Follow alias chains down to a concrete value
+    #[default]
+    Resolve,
+    /// Keep aliases as references to the target variable
+    Preserve,
+}
+
+/// A variable collection with its modes and variables
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VariableCollectionTokens {
+    pub name: String,
+    /// Mode names, default mode first
+    pub modes: Vec<String>,
+    pub variables: Vec<VariableToken>,
+}
+
+impl VariableCollectionTokens {
+    pub fn default_mode(&self) -> &str {
+        self.modes.first().map(String::as_str).unwrap_or_default()
+    }
+
+    /// Mode used for dark appearance (`values-night`, dynamic colors), if any
+    pub fn dark_mode(&self) -> Option<&str> {
+        self.modes
+            .iter()
+            .skip(1)
+            .map(String::as_str)
+            .find(|mode| is_dark_mode_name(mode))
+    }
+}
+
+/// A variable with a value per mode
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VariableToken {
+    /// Figma variable name, including `/` groups
+    pub name: String,
+    #[serde(rename = "type")]
+    pub token_type: VariableTokenType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scopes: Vec<String>,
+    /// Mode name -> value
+    pub values: BTreeMap<String, VariableTokenValue>,
+}
+
+impl VariableToken {
+    /// Whether a number should be emitted as a dimension (dp/px) rather than a plain float
+    pub fn is_dimension(&self) -> bool {
+        const DIMENSION_SCOPES: [&str; 10] = [
+            "ALL_SCOPES",
+            "CORNER_RADIUS",
+            "WIDTH_HEIGHT",
+            "GAP",
+            "STROKE_FLOAT",
+            "EFFECT_FLOAT",
+            "FONT_SIZE",
+            "LINE_HEIGHT",
+            "LETTER_SPACING",
+            "PARAGRAPH_SPACING",
+        ];
+        self.token_type == VariableTokenType::Number
+            && (self.scopes.is_empty()
+                || self
+                    .scopes
+                    .iter()
+                    .any(|scope| DIMENSION_SCOPES.contains(&scope.as_str())))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VariableTokenType {
+    Color,
+    Number,
+    String,
+    Boolean,
+}
+
+/// A concrete value or a reference to another variable
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum VariableTokenValue {
+    Color { hex: String, rgba: [f64; 4] },
+    Number(f64),
+    String(String),
+    Boolean(bool),
+    Alias { alias: VariableReference },
+}
+
+/// Target of a preserved alias
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VariableReference {
+    pub collection: String,
+    pub mode: String,
+    pub name: String,
+}
+
+/// Build per-mode tokens from the local variables response
+///
+/// Remote (library) variables are only used to resolve aliases. When
+/// `published` is given, only variables with those IDs are emitted. Returns
+/// the collections and a warning for every value that could not be resolved.
+pub fn build_variable_tokens(
+    meta: &LocalVariablesMeta,
+    published: Option<&HashSet<String>>,
+    alias_mode: AliasMode,
+) -> (Vec<VariableCollectionTokens>, Vec<String>) {
+    let resolver = Resolver { meta };
+    let mut warnings = Vec::new();
+    let mut by_collection: HashMap<&str, Vec<VariableToken>> = HashMap::new();
+
+    for variable in meta.variables.values() {
+        if variable.remote || published.is_some_and(|ids| !ids.contains(&variable.id)) {
+            continue;
+        }
+        let Some(collection) = meta
+            .variable_collections
+            .get(&variable.variable_collection_id)
+        else {
+            warnings.push(format!(
+                "Variable '{}' references unknown collection {}",
+                variable.name, variable.variable_collection_id
+            ));
+            continue;
+        };
+
+        let mut values = BTreeMap::new();
+        for mode in ordered_modes(collection) {
+            let value = match alias_mode {
+                AliasMode::Resolve => resolver.resolve(variable, &mode, 0),
+                AliasMode::Preserve => resolver.reference_or_value(variable, &mode),
+            };
+            match value {
+                Some(value) => {
+                    values.insert(mode, value);
+                }
+                None => warnings.push(format!(
+                    "Could not resolve '{}' in mode '{}' (alias to a variable outside this file?)",
+                    variable.name, mode
+                )),
+            }
+        }
+
+        by_collection
+            .entry(collection.id.as_str())
+            .or_default()
+            .push(VariableToken {
+                name: variable.name.clone(),
+                token_type: token_type(&variable.resolved_type),
+                description: variable.description.clone().filter(|d| !d.is_empty()),
+                scopes: variable.scopes.clone(),
+                values,
+            });
+    }
+
+    let mut collections: Vec<VariableCollectionTokens> = by_collection
+        .into_iter()
+        .filter_map(|(id, mut variables)| {
+            let collection = meta.variable_collections.get(id)?;
+            variables.sort_by(|a, b| a.name.cmp(&b.name));
+            Some(VariableCollectionTokens {
+                name: collection.name.clone(),
+                modes: ordered_modes(collection),
+                variables,
+            })
+        })
+        .collect();
+    collections.sort_by(|a, b| a.name.cmp(&b.name));
+    warnings.sort();
+
+    (collections, warnings)
+}
+
+struct Resolver<'a> {
+    meta: &'a LocalVariablesMeta,
+}
+
+impl Resolver<'_> {
+    /// Raw value of `variable` for the mode with this name (or its collection's default)
+    fn raw_value<'v>(&self, variable: &'v Variable, mode_name: &str) -> Option<&'v VariableValue> {
+        let collection = self
+            .meta
+            .variable_collections
+            .get(&variable.variable_collection_id)?;
+        let mode_id = collection
+            .modes
+            .iter()
+            .find(|mode| mode.name == mode_name)
+            .map(|mode| mode.mode_id.as_str())
+            .unwrap_or(collection.default_mode_id.as_str());
+        variable
+            .values_by_mode
+            .get(mode_id)
+            .or_else(|| variable.values_by_mode.get(&collection.default_mode_id))
+    }
+
+    fn resolve(
+        &self,
+        variable: &Variable,
+        mode_name: &str,
+        depth: usize,
+    ) -> Option<VariableTokenValue> {
+        match self.raw_value(variable, mode_name)? {
+            VariableValue::Alias(alias) => {
+                if depth >= MAX_ALIAS_DEPTH {
+                    return None;
+                }
+                let target = self.meta.variables.get(&alias.id)?;
+                self.resolve(target, mode_name, depth + 1)
+            }
+            value => Some(concrete_value(value)),
+        }
+    }
+
+    fn reference_or_value(
+        &self,
+        variable: &Variable,
+        mode_name: &str,
+    ) -> Option<VariableTokenValue> {
+        match self.raw_value(variable, mode_name)? {
+            VariableValue::Alias(alias) => {
+                let target = self.meta.variables.get(&alias.id)?;
+                let collection = self
+                    .meta
+                    .variable_collections
+                    .get(&target.variable_collection_id)?;
+                // Aliases into a collection with other modes follow Figma: same-named mode, else default
+                let mode = collection
+                    .modes
+                    .iter()
+                    .find(|mode| mode.name == mode_name)
+                    .or_else(|| {
+                        collection
+                            .modes
+                            .iter()
+                            .find(|mode| mode.mode_id == collection.default_mode_id)
+                    })?;
+                Some(VariableTokenValue::Alias {
+                    alias: VariableReference {
+                        collection: collection.name.clone(),
+                        mode: mode.name.clone(),
+                        name: target.name.clone(),
+                    },
+                })
+            }
+            value => Some(concrete_value(value)),
+        }
+    }
+}
+
+fn concrete_value(value: &VariableValue) -> VariableTokenValue {
+    match value {
+        VariableValue::Color(color) => VariableTokenValue::Color {
+            hex: color.to_hex(),
+            rgba: [color.r, color.g, color.b, color.a],
+        },
+        VariableValue::Float(number) => VariableTokenValue::Number(*number),
+        VariableValue::Boolean(flag) => VariableTokenValue::Boolean(*flag),
+        VariableValue::String(text) => VariableTokenValue::String(text.clone()),
+        VariableValue::Alias(_) => unreachable!("aliases are handled by the resolver"),
+    }
+}
+
+fn ordered_modes(collection: &VariableCollection) -> Vec<String> {
+    let mut modes: Vec<String> = collection
+        .modes
+        .iter()
+        .filter(|mode| mode.mode_id == collection.default_mode_id)
+        .map(|mode| mode.name.clone())
+        .collect();
+    modes.extend(
+        collection
+            .modes
+            .iter()
+            .filter(|mode| mode.mode_id != collection.default_mode_id)
+            .map(|mode| mode.name.clone()),
+    );
+    modes
+}
+
+fn token_type(resolved_type: &str) -> VariableTokenType {
+    match resolved_type {
+        "COLOR" => VariableTokenType::Color,
+        "FLOAT" => VariableTokenType::Number,
+        "BOOLEAN" => VariableTokenType::Boolean,
+        _ => VariableTokenType::String,
+    }
+}
+
+fn is_dark_mode_name(mode: &str) -> bool {
+    let lowered = mode.to_lowercase();
+    lowered.contains("dark") || lowered.contains("night")
+}
+
+fn is_light_mode_name(mode: &str) -> bool {
+    let lowered = mode.to_lowercase();
+    lowered.contains("light") || lowered.contains("day")
+}
+
+/// Values of a mode that differ from the default mode
+fn mode_overrides<'a>(
+    collection: &'a VariableCollectionTokens,
+    mode: &'a str,
+) -> impl Iterator<Item = (&'a VariableToken, &'a VariableTokenValue)> {
+    let default_mode = collection.default_mode();
+    collection.variables.iter().filter_map(move |variable| {
+        let value = variable.values.get(mode)?;
+        (variable.values.get(default_mode) != Some(value)).then_some((variable, value))
+    })
+}
+
+/// `{prefix}{Collection}Variables`, apart from the `{prefix}Colors`-style types of style tokens
+fn collection_type(type_prefix: &str, collection: &str) -> String {
+    format!("{}{}Variables", type_prefix, to_pascal_case(collection))
+}
+
+/// Name of a variable in flat formats, apart from style token names
+fn flat_name(name: &str) -> String {
+    format!("var-{}", sanitize_token_name(name))
+}
+
+fn android_name(name: &str) -> String {
+    format!("var_{}", sanitize_token_name(name))
+}
+
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+// CSS: default mode on :root, other modes as [data-theme="<mode>"] overrides
+
+pub(super) fn css_blocks(collections: &[VariableCollectionTokens], css_prefix: &str) -> String {
+    let mut css = String::new();
+    for collection in collections {
+        css.push_str(&format!(
+            "\n/* Variables: {} ({}) */\n:root {{\n",
+            collection.name,
+            collection.default_mode()
+        ));
+        for variable in &collection.variables {
+            if let Some(value) = variable.values.get(collection.default_mode()) {
+                css.push_str(&css_declaration(css_prefix, variable, value));
+            }
+        }
+        css.push_str("}\n");
+
+        for mode in collection.modes.iter().skip(1) {
+            css.push_str(&format!(
+                "\n[data-theme=\"{}\"] {{\n",
+                sanitize_token_name(mode)
+            ));
+            for (variable, value) in mode_overrides(collection, mode) {
+                css.push_str(&css_declaration(css_prefix, variable, value));
+            }
+            css.push_str("}\n");
+        }
+    }
+    css
+}
+
+fn css_declaration(
+    css_prefix: &str,
+    variable: &VariableToken,
+    value: &VariableTokenValue,
+) -> String {
+    let rendered = match value {
+        VariableTokenValue::Color { hex, rgba } => css_color(hex, rgba),
+        VariableTokenValue::Number(number) if variable.is_dimension() => {
+            format!("{}px", format_px(*number))
+        }
+        VariableTokenValue::Number(number) => format_px(*number),
+        VariableTokenValue::String(text) => quote(text),
+        VariableTokenValue::Boolean(flag) => flag.to_string(),
+        VariableTokenValue::Alias { alias } => {
+            format!("var({}{})", css_prefix, flat_name(&alias.name))
+        }
+    };
+    format!(
+        "  {}{}: {};\n",
+        css_prefix,
+        flat_name(&variable.name),
+        rendered
+    )
+}
+
+// Swift: one enum per mode, plus dynamic light/dark colors on UIKit platforms
+
+pub(super) fn swift(collections: &[VariableCollectionTokens], type_prefix: &str) -> String {
+    let mut swift = String::new();
+    let mut needs_dynamic_color = false;
+
+    for collection in collections {
+        swift.push_str(&format!(
+            "\nenum {} {{\n",
+            collection_type(type_prefix, &collection.name)
+        ));
+        for mode in &collection.modes {
+            swift.push_str(&format!("    enum {} {{\n", to_pascal_case(mode)));
+            for variable in &collection.variables {
+                if let Some(value) = variable.values.get(mode) {
+                    swift.push_str(&format!(
+                        "        static let {}{}\n",
+                        to_pascal_case(&variable.name),
+                        swift_value(type_prefix, variable, value)
+                    ));
+                }
+            }
+            swift.push_str("    }\n");
+        }
+
+        if let Some(dark) = collection.dark_mode() {
+            let light = collection
+                .modes
+                .iter()
+                .find(|mode| is_light_mode_name(mode))
+                .map(String::as_str)
+                .unwrap_or(collection.default_mode());
+            let colors: Vec<&VariableToken> = collection
+                .variables
+                .iter()
+                .filter(|variable| variable.token_type == VariableTokenType::Color)
+                .collect();
+            if !colors.is_empty() {
+                needs_dynamic_color = true;
+                swift.push_str("\n    #if canImport(UIKit)\n");
+                for variable in colors {
+                    let name = to_pascal_case(&variable.name);
+                    swift.push_str(&format!(
+                        "    static let {name} = Color(light: {light}.{name}, dark: {dark}.{name})\n",
+                        name = name,
+                        light = to_pascal_case(light),
+                        dark = to_pascal_case(dark)
+                    ));
+                }
+                swift.push_str("    #endif\n");
+            }
+        }
+        swift.push_str("}\n");
+    }
+
+    if needs_dynamic_color {
+        swift.push_str(
+            "\n#if canImport(UIKit)\nimport UIKit\n\nextension Color {\n    init(light: Color, dark: Color) {\n        self.init(UIColor { traits in\n            traits.userInterfaceStyle == .dark ? UIColor(dark) : UIColor(light)\n        })\n    }\n}\n#endif\n",
+        );
+    }
+    swift
+}
+
+fn swift_value(type_prefix: &str, variable: &VariableToken, value: &VariableTokenValue) -> String {
+    match value {
+        VariableTokenValue::Color { rgba, .. } => format!(
+            " = Color(red: {:.3}, green: {:.3}, blue: {:.3}, opacity: {:.3})",
+            rgba[0], rgba[1], rgba[2], rgba[3]
+        ),
+        VariableTokenValue::Number(number) if variable.is_dimension() => {
+            format!(": CGFloat = {}", format_px(*number))
+        }
+        VariableTokenValue::Number(number) => format!(": Double = {}", format_px(*number)),
+        VariableTokenValue::String(text) => format!(" = {}", quote(text)),
+        VariableTokenValue::Boolean(flag) => format!(" = {}", flag),
+        VariableTokenValue::Alias { alias } => format!(
+            " = {}.{}.{}",
+            collection_type(type_prefix, &alias.collection),
+            to_pascal_case(&alias.mode),
+            to_pascal_case(&alias.name)
+        ),
+    }
+}
+
+// Kotlin: one object per mode
+
+pub(super) fn kotlin(collections: &[VariableCollectionTokens], type_prefix: &str) -> String {
+    let mut kotlin = String::new();
+    for collection in collections {
+        kotlin.push_str(&format!(
+            "\nobject {} {{\n",
+            collection_type(type_prefix, &collection.name)
+        ));
+        for mode in &collection.modes {
+            kotlin.push_str(&format!("    object {} {{\n", to_pascal_case(mode)));
+            for variable in &collection.variables {
+                if let Some(value) = variable.values.get(mode) {
+                    kotlin.push_str(&format!(
+                        "        val {} = {}\n",
+                        to_pascal_case(&variable.name),
+                        kotlin_value(type_prefix, variable, value)
+                    ));
+                }
+            }
+            kotlin.push_str("    }\n");
+        }
+        kotlin.push_str("}\n");
+    }
+    kotlin
+}
+
+fn kotlin_value(type_prefix: &str, variable: &VariableToken, value: &VariableTokenValue) -> String {
+    match value {
+        VariableTokenValue::Color { hex, rgba } => kotlin_color(hex, rgba[3]),
+        VariableTokenValue::Number(number) if variable.is_dimension() => {
+            format!("{}.dp", format_px(*number))
+        }
+        VariableTokenValue::Number(number) => format!("{}f", format_px(*number)),
+        VariableTokenValue::String(text) => quote(text),
+        VariableTokenValue::Boolean(flag) => flag.to_string(),
+        VariableTokenValue::Alias { alias } => format!(
+            "{}.{}.{}",
+            collection_type(type_prefix, &alias.collection),
+            to_pascal_case(&alias.mode),
+            to_pascal_case(&alias.name)
+        ),
+    }
+}
+
+// Tailwind: color variables, non-default modes suffixed with the mode name
+
+pub(super) fn tailwind_colors(collections: &[VariableCollectionTokens]) -> String {
+    let mut output = String::new();
+    for collection in collections {
+        for variable in &collection.variables {
+            for (index, mode) in collection.modes.iter().enumerate() {
+                let Some(VariableTokenValue::Color { hex, rgba }) =
+                    resolved_color(collections, variable, mode)
+                else {
+                    continue;
+                };
+                let name = if index == 0 {
+                    flat_name(&variable.name)
+                } else {
+                    format!(
+                        "{}-{}",
+                        flat_name(&variable.name),
+                        sanitize_token_name(mode)
+                    )
+                };
+                output.push_str(&format!(
+                    "        '{}': '{}',\n",
+                    name,
+                    css_color(&hex, &rgba)
+                ));
+            }
+        }
+    }
+    output
+}
+
+/// Follow preserved aliases within the exported collections to a concrete color
+fn resolved_color(
+    collections: &[VariableCollectionTokens],
+    variable: &VariableToken,
+    mode: &str,
+) -> Option<VariableTokenValue> {
+    let mut value = variable.values.get(mode)?.clone();
+    for _ in 0..MAX_ALIAS_DEPTH {
+        match value {
+            VariableTokenValue::Alias { alias } => {
+                value = collections
+                    .iter()
+                    .find(|collection| collection.name == alias.collection)?
+                    .variables
+                    .iter()
+                    .find(|candidate| candidate.name == alias.name)?
+                    .values
+                    .get(&alias.mode)?
+                    .clone();
+            }
+            VariableTokenValue::Color { .. } => return Some(value),
+            _ => return None,
+        }
+    }
+    None
+}
+
+// Style Dictionary: variables.<collection>.<mode>.<name>, aliases as {references}
+
+pub(super) fn style_dictionary(collections: &[VariableCollectionTokens]) -> serde_json::Value {
+    let mut root = serde_json::Map::new();
+    for collection in collections {
+        let mut modes = serde_json::Map::new();
+        for mode in &collection.modes {
+            let mut entries = serde_json::Map::new();
+            for variable in &collection.variables {
+                let Some(value) = variable.values.get(mode) else {
+                    continue;
+                };
+                let rendered = match value {
+                    VariableTokenValue::Color { hex, rgba } => {
+                        serde_json::json!(css_color(hex, rgba))
+                    }
+                    VariableTokenValue::Number(number) if variable.is_dimension() => {
+                        serde_json::json!(format!("{}px", format_px(*number)))
+                    }
+                    VariableTokenValue::Number(number) => serde_json::json!(number),
+                    VariableTokenValue::String(text) => serde_json::json!(text),
+                    VariableTokenValue::Boolean(flag) => serde_json::json!(flag),
+                    VariableTokenValue::Alias { alias } => serde_json::json!(format!(
+                        "{{variables.{}.{}.{}}}",
+                        sanitize_token_name(&alias.collection),
+                        sanitize_token_name(&alias.mode),
+                        sanitize_token_name(&alias.name)
+                    )),
+                };
+                entries.insert(
+                    sanitize_token_name(&variable.name),
+                    serde_json::json!({ "value": rendered }),
+                );
+            }
+            modes.insert(
+                sanitize_token_name(mode),
+                serde_json::Value::Object(entries),
+            );
+        }
+        root.insert(
+            sanitize_token_name(&collection.name),
+            serde_json::Value::Object(modes),
+        );
+    }
+    serde_json::Value::Object(root)
+}
+
+// Android: default mode in values/, dark mode overrides in values-night/
+
+pub(super) fn android_resources(collections: &[VariableCollectionTokens], night: bool) -> String {
+    let mut xml = String::new();
+    for collection in collections {
+        let entries: Vec<(&VariableToken, &VariableTokenValue)> = if night {
+            match collection.dark_mode() {
+                Some(dark) => mode_overrides(collection, dark).collect(),
+                None => Vec::new(),
+            }
+        } else {
+            collection
+                .variables
+                .iter()
+                .filter_map(|variable| {
+                    variable
+                        .values
+                        .get(collection.default_mode())
+                        .map(|value| (variable, value))
+                })
+                .collect()
+        };
+        for (variable, value) in entries {
+            xml.push_str(&android_resource(collections, variable, value));
+        }
+    }
+    xml
+}
+
+fn android_resource(
+    collections: &[VariableCollectionTokens],
+    variable: &VariableToken,
+    value: &VariableTokenValue,
+) -> String {
+    let name = android_name(&variable.name);
+    match value {
+        VariableTokenValue::Color { hex, rgba } => {
+            format!(
+                "  <color name=\"{}\">{}</color>\n",
+                name,
+                android_color(hex, rgba[3])
+            )
+        }
+        VariableTokenValue::Number(number) if variable.is_dimension() => {
+            format!(
+                "  <dimen name=\"{}\">{}dp</dimen>\n",
+                name,
+                format_px(*number)
+            )
+        }
+        VariableTokenValue::Number(number) => format!(
+            "  <item name=\"{}\" type=\"dimen\" format=\"float\">{}</item>\n",
+            name,
+            format_px(*number)
+        ),
+        VariableTokenValue::String(text) => format!(
+            "  <string name=\"{}\">{}</string>\n",
+            name,
+            escape_xml(text)
+        ),
+        VariableTokenValue::Boolean(flag) => format!("  <bool name=\"{}\">{}</bool>\n", name, flag),
+        VariableTokenValue::Alias { alias } => {
+            let target_type = collections
+                .iter()
+                .filter(|collection| collection.name == alias.collection)
+                .flat_map(|collection| collection.variables.iter())
+                .find(|candidate| candidate.name == alias.name)
+                .unwrap_or(variable);
+            let (tag, reference_type) = match target_type.token_type {
+                VariableTokenType::Color => ("color", "color"),
+                VariableTokenType::Number if target_type.is_dimension() => ("dimen", "dimen"),
+                VariableTokenType::Number => ("item type=\"dimen\" format=\"float\"", "dimen"),
+                VariableTokenType::String => ("string", "string"),
+                VariableTokenType::Boolean => ("bool", "bool"),
+            };
+            let close = tag.split(' ').next().unwrap_or(tag);
+            format!(
+                "  <{} name=\"{}\">@{}/{}</{}>\n",
+                tag,
+                name,
+                reference_type,
+                android_name(&alias.name),
+                close
+            )
+        }
+    }
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('\'', "\\'")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_meta() -> LocalVariablesMeta {
+        serde_json::from_value(serde_json::json!({
+            "variables": {
+                "VariableID:1": {
+                    "id": "VariableID:1",
+                    "name": "blue/500",
+                    "variableCollectionId": "C:primitives",
+                    "resolvedType": "COLOR",
+                    "valuesByMode": { "p:0": { "r": 0.0, "g": 0.0, "b": 1.0, "a": 1.0 } }
+                },
+                "VariableID:2": {
+                    "id": "VariableID:2",
+                    "name": "gray/900",
+                    "variableCollectionId": "C:primitives",
+                    "resolvedType": "COLOR",
+                    "valuesByMode": { "p:0": { "r": 0.0, "g": 0.0, "b": 0.0, "a": 1.0 } }
+                },
+                "VariableID:3": {
+                    "id": "VariableID:3",
+                    "name": "color/accent",
+                    "variableCollectionId": "C:theme",
+                    "resolvedType": "COLOR",
+                    "valuesByMode": {
+                        "t:light": { "type": "VARIABLE_ALIAS", "id": "VariableID:1" },
+                        "t:dark": { "type": "VARIABLE_ALIAS", "id": "VariableID:2" }
+                    }
+                },
+                "VariableID:4": {
+                    "id": "VariableID:4",
+                    "name": "space/md",
+                    "variableCollectionId": "C:theme",
+                    "resolvedType": "FLOAT",
+                    "scopes": ["GAP"],
+                    "valuesByMode": { "t:light": 16, "t:dark": 16 }
+                },
+                "VariableID:5": {
+                    "id": "VariableID:5",
+                    "name": "remote/brand",
+                    "variableCollectionId": "C:theme",
+                    "resolvedType": "COLOR",
+                    "valuesByMode": {
+                        "t:light": { "type": "VARIABLE_ALIAS", "id": "VariableID:missing" }
+                    }
+                }
+            },
+            "variableCollections": {
+                "C:primitives": {
+                    "id": "C:primitives",
+                    "name": "Primitives",
+                    "modes": [{ "modeId": "p:0", "name": "Value" }],
+                    "defaultModeId": "p:0"
+                },
+                "C:theme": {
+                    "id": "C:theme",
+                    "name": "Theme",
+                    "modes": [
+                        { "modeId": "t:light", "name": "Light" },
+                        { "modeId": "t:dark", "name": "Dark" }
+                    ],
+                    "defaultModeId": "t:light"
+                }
+            }
+        }))
+        .expect("variables meta")
+    }
+
+    fn theme(collections: &[VariableCollectionTokens]) -> &VariableCollectionTokens {
+        collections
+            .iter()
+            .find(|collection| collection.name == "Theme")
+            .expect("theme collection")
+    }
+
+    #[test]
+    fn resolves_alias_chains_per_mode() {
+        let (collections, warnings) =
+            build_variable_tokens(&sample_meta(), None, AliasMode::Resolve);
+        let accent = &theme(&collections).variables[0];
+
+        assert_eq!(theme(&collections).modes, vec!["Light", "Dark"]);
+        assert_eq!(accent.name, "color/accent");
+        assert!(matches!(
+            &accent.values["Light"],
+            VariableTokenValue::Color { hex, .. } if hex == "#0000FF"
+        ));
+        assert!(matches!(
+            &accent.values["Dark"],
+            VariableTokenValue::Color { hex, .. } if hex == "#000000"
+        ));
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings
+            .iter()
+            .all(|warning| warning.contains("remote/brand")));
+    }
+
+    #[test]
+    fn preserves_aliases_as_references() {
+        let (collections, _) = build_variable_tokens(&sample_meta(), None, AliasMode::Preserve);
+        let accent = &theme(&collections).variables[0];
+
+        assert_eq!(
+            accent.values["Dark"],
+            VariableTokenValue::Alias {
+                alias: VariableReference {
+                    collection: "Primitives".to_string(),
+                    mode: "Value".to_string(),
+                    name: "gray/900".to_string(),
+                }
+            }
+        );
+
+        let css = css_blocks(&collections, "--");
+        assert!(css.contains("--var-color-accent: var(--var-blue-500);"));
+        assert!(
+            css.contains("[data-theme=\"dark\"] {\n  --var-color-accent: var(--var-gray-900);\n}")
+        );
+        assert!(!css.contains("[data-theme=\"dark\"] {\n  --var-space-md"));
+    }
+
+    #[test]
+    fn published_filter_limits_variables() {
+        let published: HashSet<String> = ["VariableID:4".to_string()].into_iter().collect();
+        let (collections, _) =
+            build_variable_tokens(&sample_meta(), Some(&published), AliasMode::Resolve);
+
+        assert_eq!(collections.len(), 1);
+        assert_eq!(collections[0].variables.len(), 1);
+        assert_eq!(collections[0].variables[0].name, "space/md");
+    }
+
+    #[test]
+    fn swift_and_android_emit_dark_mode_output() {
+        let (collections, _) = build_variable_tokens(&sample_meta(), None, AliasMode::Resolve);
+
+        let swift = swift(&collections, "Figma");
+        assert!(swift.contains("enum FigmaThemeVariables {"));
+        assert!(swift.contains("    enum Dark {"));
+        assert!(swift.contains(
+            "static let ColorAccent = Color(light: Light.ColorAccent, dark: Dark.ColorAccent)"
+        ));
+        assert!(swift.contains("static let SpaceMd: CGFloat = 16"));
+
+        let day = android_resources(&collections, false);
+        let night = android_resources(&collections, true);
+        assert!(day.contains("<color name=\"var_color-accent\">#0000FF</color>"));
+        assert!(day.contains("<dimen name=\"var_space-md\">16dp</dimen>"));
+        assert_eq!(
+            night,
+            "  <color name=\"var_color-accent\">#000000</color>\n"
+        );
+    }
+
+    #[test]
+    fn collections_do_not_clash_with_style_token_names() {
+        let (mut collections, _) = build_variable_tokens(&sample_meta(), None, AliasMode::Preserve);
+        let rename = |name: &str| match name {
+            "Theme" => "Colors".to_string(),
+            _ => "Spacing".to_string(),
+        };
+        for collection in &mut collections {
+            collection.name = rename(&collection.name);
+            for variable in &mut collection.variables {
+                for value in variable.values.values_mut() {
+                    if let VariableTokenValue::Alias { alias } = value {
+                        alias.collection = rename(&alias.collection);
+                    }
+                }
+            }
+        }
+        let tokens = super::super::DesignTokens {
+            variables: collections,
+            ..Default::default()
+        };
+
+        let swift = super::super::export_swift(&tokens, "");
+        for name in [
+            "enum FigmaColors {",
+            "enum FigmaColorsVariables {",
+            "enum FigmaSpacingVariables {",
+        ] {
+            assert_eq!(swift.matches(name).count(), 1, "{}", name);
+        }
+        assert!(swift.contains("= FigmaSpacingVariables.Value."));
+
+        let kotlin = super::super::export_kotlin(&tokens, "");
+        for name in [
+            "object FigmaColors {",
+            "object FigmaColorsVariables {",
+            "object FigmaSpacingVariables {",
+        ] {
+            assert_eq!(kotlin.matches(name).count(), 1, "{}", name);
+        }
+    }
+}
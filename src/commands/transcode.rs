@@ -0,0 +1,147 @@
+//! Local transcoding of exported PNGs into web formats
+//!
+//! The Figma API only renders PNG, JPG, SVG and PDF. WebP and AVIF exports
+//! (and JPEGs with an explicit `--quality`) are requested as PNG and
+//! re-encoded here before they are written to disk.
+
+use anyhow::{anyhow, bail, Context, Result};
+use image::codecs::avif::AvifEncoder;
+use image::codecs::jpeg::JpegEncoder;
+use image::{DynamicImage, ImageEncoder, RgbImage};
+
+/// Quality used when a transcoded format is requested without `--quality`
+pub const DEFAULT_QUALITY: u8 = 80;
+
+/// AVIF encoder speed (1 = slowest/smallest, 10 = fastest)
+const AVIF_SPEED: u8 = 6;
+
+/// Whether a format is produced locally rather than rendered by Figma
+pub fn needs_transcode(format: &str, quality: Option<u8>) -> bool {
+    match format {
+        "webp" | "avif" => true,
+        "jpg" => quality.is_some(),
+        _ => false,
+    }
+}
+
+/// Format to request from the Figma API for an output format
+pub fn source_format(format: &str, quality: Option<u8>) -> &str {
+    if needs_transcode(format, quality) {
+        "png"
+    } else {
+        format
+    }
+}
+
+/// Re-encode rendered PNG bytes as `format` at `quality` (1-100)
+///
+/// A quality of 100 writes lossless WebP; JPEG output is flattened onto white
+/// since the format has no alpha channel.
+pub fn transcode(source: &[u8], format: &str, quality: u8) -> Result<Vec<u8>> {
+    if !(1..=100).contains(&quality) {
+        bail!("Quality must be between 1 and 100");
+    }
+    let image = image::load_from_memory(source).context("Failed to decode exported image")?;
+    let mut out = Vec::new();
+
+    match format {
+        "webp" => {
+            let rgba = image.to_rgba8();
+            let encoder = webp::Encoder::from_rgba(&rgba, rgba.width(), rgba.height());
+            let encoded = if quality == 100 {
+                encoder.encode_lossless()
+            } else {
+                encoder.encode(f32::from(quality))
+            };
+            out.extend_from_slice(&encoded);
+        }
+        "avif" => {
+            let rgba = image.to_rgba8();
+            AvifEncoder::new_with_speed_quality(&mut out, AVIF_SPEED, quality).write_image(
+                &rgba,
+                rgba.width(),
+                rgba.height(),
+                image::ExtendedColorType::Rgba8,
+            )?;
+        }
+        "jpg" => {
+            let rgb = flatten_on_white(&image);
+            JpegEncoder::new_with_quality(&mut out, quality).write_image(
+                &rgb,
+                rgb.width(),
+                rgb.height(),
+                image::ExtendedColorType::Rgb8,
+            )?;
+        }
+        other => return Err(anyhow!("Cannot transcode to {}", other)),
+    }
+
+    Ok(out)
+}
+
+fn flatten_on_white(image: &DynamicImage) -> RgbImage {
+    let rgba = image.to_rgba8();
+    RgbImage::from_fn(rgba.width(), rgba.height(), |x, y| {
+        let [r, g, b, a] = rgba.get_pixel(x, y).0;
+        let alpha = u16::from(a);
+        let blend = |c: u8| ((u16::from(c) * alpha + 255 * (255 - alpha) + 127) / 255) as u8;
+        image::Rgb([blend(r), blend(g), blend(b)])
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageFormat, Rgba, RgbaImage};
+    use std::io::Cursor;
+
+    fn sample_png() -> Vec<u8> {
+        let image = RgbaImage::from_fn(16, 12, |x, y| {
+            if x < 8 {
+                Rgba([200, (y * 20) as u8, 40, 255])
+            } else {
+                Rgba([0, 0, 0, 0])
+            }
+        });
+        let mut bytes = Vec::new();
+        DynamicImage::ImageRgba8(image)
+            .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+            .expect("encode png");
+        bytes
+    }
+
+    #[test]
+    fn transcodes_png_to_each_target() {
+        let png = sample_png();
+        for (format, expected) in [
+            ("webp", ImageFormat::WebP),
+            ("avif", ImageFormat::Avif),
+            ("jpg", ImageFormat::Jpeg),
+        ] {
+            let bytes = transcode(&png, format, 70).expect(format);
+            assert_eq!(image::guess_format(&bytes).expect(format), expected);
+        }
+
+        let lossless = transcode(&png, "webp", 100).expect("lossless webp");
+        let decoded = image::load_from_memory(&lossless).expect("decode webp");
+        assert_eq!(decoded.to_rgba8().get_pixel(2, 3).0, [200, 60, 40, 255]);
+
+        let jpeg = transcode(&png, "jpg", 95).expect("jpeg");
+        let flattened = image::load_from_memory(&jpeg)
+            .expect("decode jpeg")
+            .to_rgb8();
+        assert!(flattened.get_pixel(14, 6).0.iter().all(|c| *c > 240));
+
+        assert!(transcode(&png, "webp", 0).is_err());
+        assert!(transcode(&png, "svg", 80).is_err());
+    }
+
+    #[test]
+    fn requests_png_only_for_transcoded_formats() {
+        assert_eq!(source_format("webp", None), "png");
+        assert_eq!(source_format("avif", Some(50)), "png");
+        assert_eq!(source_format("jpg", Some(60)), "png");
+        assert_eq!(source_format("jpg", None), "jpg");
+        assert_eq!(source_format("svg", Some(60)), "svg");
+    }
+}
@@ -0,0 +1,893 @@
+//! SVG to Android VectorDrawable conversion for Android exports
+//!
+//! Handles what Figma's SVG export produces for icons: paths and basic
+//! shapes, inherited fill and stroke attributes, translate/rotate/scale
+//! transforms, clip paths, and linear and circular radial gradients (as
+//! `aapt:attr` gradients, API 24+). Group opacity is applied to each path in
+//! the group. Masks, filters, images, text, dashed strokes, skewed transforms
+//! and elliptical or angular gradients are reported as unsupported, so the
+//! caller can fall back to PNG densities.
+
+use anyhow::{anyhow, bail, Result};
+use roxmltree::{Document, Node};
+use std::collections::{BTreeSet, HashMap};
+
+const INDENT: &str = "    ";
+
+/// Android resource name for an asset: lowercase snake_case starting with a letter
+pub fn android_resource_name(name: &str) -> String {
+    let mut out = String::new();
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            out.push(c.to_ascii_lowercase());
+        } else if !out.ends_with('_') {
+            out.push('_');
+        }
+    }
+    let out = out.trim_matches('_');
+    match out.chars().next() {
+        None => "asset".to_string(),
+        Some(first) if first.is_ascii_digit() => format!("asset_{}", out),
+        Some(_) => out.to_string(),
+    }
+}
+
+/// Convert an SVG document to VectorDrawable XML
+///
+/// Fails with a list of the unsupported features when the drawable would not
+/// match the SVG.
+pub fn svg_to_vector_drawable(svg: &str) -> Result<String> {
+    let document = Document::parse(svg).map_err(|err| anyhow!("invalid SVG: {}", err))?;
+    let root = document.root_element();
+    if root.tag_name().name() != "svg" {
+        bail!("invalid SVG: root element is <{}>", root.tag_name().name());
+    }
+
+    let view_box: Option<Vec<f64>> = root.attribute("viewBox").map(|view_box| {
+        view_box
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|part| !part.is_empty())
+            .filter_map(|part| part.parse().ok())
+            .collect()
+    });
+    let length = |name: &str| root.attribute(name).and_then(parse_length);
+    let (viewport_width, viewport_height) = match view_box.as_deref() {
+        Some([_, _, width, height]) => (*width, *height),
+        _ => (
+            length("width").ok_or_else(|| anyhow!("SVG has no width or viewBox"))?,
+            length("height").ok_or_else(|| anyhow!("SVG has no height or viewBox"))?,
+        ),
+    };
+    if let Some([x, y, _, _]) = view_box.as_deref() {
+        if *x != 0.0 || *y != 0.0 {
+            bail!("unsupported SVG features: offset viewBox");
+        }
+    }
+
+    let mut converter = Converter {
+        ids: document
+            .descendants()
+            .filter_map(|node| node.attribute("id").map(|id| (id, node)))
+            .collect(),
+        unsupported: BTreeSet::new(),
+        gradients: false,
+    };
+    let mut body = String::new();
+    // Presentation attributes on <svg> (Figma sets fill="none") apply to everything
+    let style = converter.style(root, &Style::default());
+    converter.children(root, &style, 1, &mut body);
+    if !converter.unsupported.is_empty() {
+        let features: Vec<&str> = converter.unsupported.iter().map(String::as_str).collect();
+        bail!("unsupported SVG features: {}", features.join(", "));
+    }
+
+    let mut xml =
+        String::from("<vector xmlns:android=\"http://schemas.android.com/apk/res/android\"\n");
+    if converter.gradients {
+        xml.push_str(&format!(
+            "{}xmlns:aapt=\"http://schemas.android.com/aapt\"\n",
+            INDENT
+        ));
+    }
+    xml.push_str(&format!(
+        "{i}android:width=\"{}dp\"\n{i}android:height=\"{}dp\"\n{i}android:viewportWidth=\"{}\"\n{i}android:viewportHeight=\"{}\">\n",
+        number(length("width").unwrap_or(viewport_width)),
+        number(length("height").unwrap_or(viewport_height)),
+        number(viewport_width),
+        number(viewport_height),
+        i = INDENT
+    ));
+    xml.push_str(&body);
+    xml.push_str("</vector>\n");
+    Ok(xml)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Paint {
+    None,
+    Color([u8; 3], f64),
+    /// Gradient reference from `url(#id)`
+    Url(String),
+}
+
+/// Presentation attributes, inherited down the tree
+#[derive(Clone, Debug)]
+struct Style {
+    fill: Paint,
+    fill_opacity: f64,
+    even_odd: bool,
+    stroke: Paint,
+    stroke_width: f64,
+    stroke_opacity: f64,
+    line_cap: Option<String>,
+    line_join: Option<String>,
+    miter_limit: Option<f64>,
+    /// Product of the `opacity` of the element and its ancestors
+    opacity: f64,
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Self {
+            fill: Paint::Color([0, 0, 0], 1.0),
+            fill_opacity: 1.0,
+            even_odd: false,
+            stroke: Paint::None,
+            stroke_width: 1.0,
+            stroke_opacity: 1.0,
+            line_cap: None,
+            line_join: None,
+            miter_limit: None,
+            opacity: 1.0,
+        }
+    }
+}
+
+/// 2D affine transform in SVG order: `[a, b, c, d, e, f]`
+type Matrix = [f64; 6];
+
+const IDENTITY: Matrix = [1.0, 0.0, 0.0, 1.0, 0.0, 0.0];
+
+struct Converter<'a, 'input> {
+    ids: HashMap<&'a str, Node<'a, 'input>>,
+    unsupported: BTreeSet<String>,
+    gradients: bool,
+}
+
+impl<'a, 'input> Converter<'a, 'input> {
+    fn children(&mut self, node: Node<'a, 'input>, style: &Style, depth: usize, out: &mut String) {
+        for child in node.children().filter(Node::is_element) {
+            self.element(child, style, depth, out);
+        }
+    }
+
+    fn element(&mut self, node: Node<'a, 'input>, parent: &Style, depth: usize, out: &mut String) {
+        let tag = node.tag_name().name();
+        match tag {
+            "defs" | "title" | "desc" | "metadata" | "clipPath" | "linearGradient"
+            | "radialGradient" => return,
+            "g" | "path" | "rect" | "circle" | "ellipse" | "line" | "polygon" | "polyline" => {}
+            "mask" | "filter" | "image" | "text" | "foreignObject" | "pattern" | "use"
+            | "symbol" | "switch" => {
+                self.unsupported.insert(tag_feature(tag));
+                return;
+            }
+            other => {
+                self.unsupported.insert(format!("<{}> element", other));
+                return;
+            }
+        }
+        if attribute(node, "display") == Some("none") {
+            return;
+        }
+        if attribute(node, "mask").is_some_and(|mask| mask != "none") {
+            self.unsupported.insert("masks".to_string());
+        }
+        if attribute(node, "filter").is_some_and(|filter| filter != "none") {
+            self.unsupported.insert("filters".to_string());
+        }
+        let style = self.style(node, parent);
+
+        // Transforms and clips need a wrapping group
+        let mut group = Vec::new();
+        if let Some(transform) = attribute(node, "transform") {
+            match parse_transform(transform).and_then(decompose) {
+                Some(attributes) => group.extend(attributes),
+                None => {
+                    self.unsupported.insert("skewed transforms".to_string());
+                }
+            }
+        }
+        let clip = attribute(node, "clip-path")
+            .filter(|clip| *clip != "none")
+            .and_then(|clip| self.clip_path(clip));
+        let wrapped = !group.is_empty() || clip.is_some();
+        let inner = if wrapped { depth + 1 } else { depth };
+        if wrapped {
+            open_tag(out, depth, "group", &group, false);
+            if let Some(clip) = clip {
+                open_tag(
+                    out,
+                    inner,
+                    "clip-path",
+                    &[("pathData".to_string(), clip)],
+                    true,
+                );
+            }
+        }
+        if tag == "g" {
+            self.children(node, &style, inner, out);
+        } else if let Some(data) = shape_data(node) {
+            self.path(&data, &style, inner, out);
+        }
+        if wrapped {
+            out.push_str(&format!("{}</group>\n", INDENT.repeat(depth)));
+        }
+    }
+
+    fn style(&mut self, node: Node, parent: &Style) -> Style {
+        let mut style = parent.clone();
+        let number = |name: &str| attribute(node, name).and_then(parse_length);
+        if let Some(fill) = attribute(node, "fill") {
+            style.fill = self.paint(fill);
+        }
+        if let Some(opacity) = number("fill-opacity") {
+            style.fill_opacity = opacity;
+        }
+        if let Some(rule) = attribute(node, "fill-rule") {
+            style.even_odd = rule == "evenodd";
+        }
+        if let Some(stroke) = attribute(node, "stroke") {
+            style.stroke = self.paint(stroke);
+        }
+        if let Some(width) = number("stroke-width") {
+            style.stroke_width = width;
+        }
+        if let Some(opacity) = number("stroke-opacity") {
+            style.stroke_opacity = opacity;
+        }
+        if let Some(cap) = attribute(node, "stroke-linecap") {
+            style.line_cap = Some(cap.to_string());
+        }
+        if let Some(join) = attribute(node, "stroke-linejoin") {
+            style.line_join = Some(join.to_string());
+        }
+        if let Some(limit) = number("stroke-miterlimit") {
+            style.miter_limit = Some(limit);
+        }
+        if attribute(node, "stroke-dasharray").is_some_and(|dashes| dashes != "none") {
+            self.unsupported.insert("dashed strokes".to_string());
+        }
+        // Not inherited in SVG; multiplied into the paths of the group here
+        style.opacity = parent.opacity * number("opacity").unwrap_or(1.0);
+        style
+    }
+
+    fn paint(&mut self, value: &str) -> Paint {
+        let value = value.trim();
+        if let Some(id) = url_id(value) {
+            return Paint::Url(id.to_string());
+        }
+        match parse_color(value) {
+            Some(Some((rgb, alpha))) => Paint::Color(rgb, alpha),
+            Some(None) => Paint::None,
+            None => {
+                self.unsupported.insert(format!("color {}", value));
+                Paint::None
+            }
+        }
+    }
+
+    /// Path data of a `clipPath`, as the union of its shapes
+    fn clip_path(&mut self, reference: &str) -> Option<String> {
+        let Some(clip) = url_id(reference).and_then(|id| self.ids.get(id).copied()) else {
+            self.unsupported.insert("missing clip path".to_string());
+            return None;
+        };
+        let mut data = Vec::new();
+        for shape in clip.children().filter(Node::is_element) {
+            let offset = match attribute(shape, "transform").map(parse_transform) {
+                None => Some((0.0, 0.0)),
+                Some(Some([a, b, c, d, e, f])) if [a, b, c, d] == [1.0, 0.0, 0.0, 1.0] => {
+                    Some((e, f))
+                }
+                Some(_) => None,
+            };
+            match (offset, shape_data_at(shape, offset.unwrap_or_default())) {
+                (Some(_), Some(shape_data)) => data.push(shape_data),
+                _ => {
+                    self.unsupported
+                        .insert("transformed or non-shape clip paths".to_string());
+                }
+            }
+        }
+        (!data.is_empty()).then(|| data.join(" "))
+    }
+
+    fn path(&mut self, data: &str, style: &Style, depth: usize, out: &mut String) {
+        let mut attributes = vec![("pathData".to_string(), data.to_string())];
+        let mut gradients = Vec::new();
+
+        match &style.fill {
+            Paint::None => {}
+            Paint::Color(rgb, alpha) => {
+                attributes.push(("fillColor".to_string(), hex(*rgb)));
+                push_alpha(
+                    &mut attributes,
+                    "fillAlpha",
+                    alpha * style.fill_opacity * style.opacity,
+                );
+            }
+            Paint::Url(id) => {
+                if let Some(gradient) = self.gradient(id) {
+                    gradients.push(("fillColor", gradient));
+                    push_alpha(
+                        &mut attributes,
+                        "fillAlpha",
+                        style.fill_opacity * style.opacity,
+                    );
+                }
+            }
+        }
+        if style.even_odd && style.fill != Paint::None {
+            attributes.push(("fillType".to_string(), "evenOdd".to_string()));
+        }
+
+        if style.stroke != Paint::None && style.stroke_width > 0.0 {
+            match &style.stroke {
+                Paint::Color(rgb, alpha) => {
+                    attributes.push(("strokeColor".to_string(), hex(*rgb)));
+                    push_alpha(
+                        &mut attributes,
+                        "strokeAlpha",
+                        alpha * style.stroke_opacity * style.opacity,
+                    );
+                }
+                Paint::Url(id) => {
+                    if let Some(gradient) = self.gradient(id) {
+                        gradients.push(("strokeColor", gradient));
+                        push_alpha(
+                            &mut attributes,
+                            "strokeAlpha",
+                            style.stroke_opacity * style.opacity,
+                        );
+                    }
+                }
+                Paint::None => {}
+            }
+            attributes.push(("strokeWidth".to_string(), number(style.stroke_width)));
+            if let Some(cap) = &style.line_cap {
+                attributes.push(("strokeLineCap".to_string(), cap.clone()));
+            }
+            if let Some(join) = &style.line_join {
+                attributes.push(("strokeLineJoin".to_string(), join.clone()));
+            }
+            if let Some(limit) = style.miter_limit {
+                attributes.push(("strokeMiterLimit".to_string(), number(limit)));
+            }
+        }
+
+        if gradients.is_empty() {
+            open_tag(out, depth, "path", &attributes, true);
+            return;
+        }
+        self.gradients = true;
+        open_tag(out, depth, "path", &attributes, false);
+        for (property, gradient) in gradients {
+            let pad = INDENT.repeat(depth + 1);
+            out.push_str(&format!(
+                "{}<aapt:attr name=\"android:{}\">\n",
+                pad, property
+            ));
+            for line in gradient.lines() {
+                out.push_str(&format!("{}{}{}\n", pad, INDENT, line));
+            }
+            out.push_str(&format!("{}</aapt:attr>\n", pad));
+        }
+        out.push_str(&format!("{}</path>\n", INDENT.repeat(depth)));
+    }
+
+    /// `<gradient>` element for a linear or circular radial SVG gradient
+    fn gradient(&mut self, id: &str) -> Option<String> {
+        let Some(node) = self.ids.get(id).copied() else {
+            self.unsupported.insert("missing gradient".to_string());
+            return None;
+        };
+        let kind = node.tag_name().name();
+        if kind != "linearGradient" && kind != "radialGradient" {
+            self.unsupported.insert(tag_feature(kind));
+            return None;
+        }
+        if node.attribute("gradientUnits") != Some("userSpaceOnUse") {
+            self.unsupported
+                .insert("gradients in bounding box units".to_string());
+            return None;
+        }
+        let transform = match node.attribute("gradientTransform").map(parse_transform) {
+            None => IDENTITY,
+            Some(Some(matrix)) => matrix,
+            Some(None) => {
+                self.unsupported
+                    .insert("invalid gradient transforms".to_string());
+                return None;
+            }
+        };
+        // Only uniform scales keep the gradient shape when the points are mapped
+        let [a, b, c, d, ..] = transform;
+        let scale = a.hypot(b);
+        if (a * c + b * d).abs() > 1e-6 || (c.hypot(d) - scale).abs() > 1e-6 * scale.max(1.0) {
+            self.unsupported.insert(match kind {
+                "radialGradient" => "elliptical radial gradients".to_string(),
+                _ => "skewed linear gradients".to_string(),
+            });
+            return None;
+        }
+        let coordinate = |name: &str, default: f64| {
+            node.attribute(name)
+                .and_then(parse_length)
+                .unwrap_or(default)
+        };
+
+        let mut attributes = Vec::new();
+        if kind == "linearGradient" {
+            let (start_x, start_y) =
+                apply(&transform, coordinate("x1", 0.0), coordinate("y1", 0.0));
+            let (end_x, end_y) = apply(&transform, coordinate("x2", 1.0), coordinate("y2", 0.0));
+            attributes.push(("type".to_string(), "linear".to_string()));
+            attributes.push(("startX".to_string(), number(start_x)));
+            attributes.push(("startY".to_string(), number(start_y)));
+            attributes.push(("endX".to_string(), number(end_x)));
+            attributes.push(("endY".to_string(), number(end_y)));
+        } else {
+            let (cx, cy) = (coordinate("cx", 0.0), coordinate("cy", 0.0));
+            if coordinate("fx", cx) != cx || coordinate("fy", cy) != cy {
+                self.unsupported
+                    .insert("focal radial gradients".to_string());
+                return None;
+            }
+            let (center_x, center_y) = apply(&transform, cx, cy);
+            attributes.push(("type".to_string(), "radial".to_string()));
+            attributes.push(("centerX".to_string(), number(center_x)));
+            attributes.push(("centerY".to_string(), number(center_y)));
+            attributes.push((
+                "gradientRadius".to_string(),
+                number(coordinate("r", 1.0) * scale),
+            ));
+        }
+
+        let mut gradient = String::new();
+        open_tag(&mut gradient, 0, "gradient", &attributes, false);
+        for stop in node
+            .children()
+            .filter(|child| child.tag_name().name() == "stop")
+        {
+            let offset = attribute(stop, "offset")
+                .and_then(|offset| match offset.strip_suffix('%') {
+                    Some(percent) => percent.trim().parse::<f64>().ok().map(|p| p / 100.0),
+                    None => offset.trim().parse().ok(),
+                })
+                .unwrap_or(0.0);
+            let (rgb, alpha) = match attribute(stop, "stop-color").map(parse_color) {
+                None => ([0, 0, 0], 1.0),
+                Some(Some(Some(color))) => color,
+                Some(Some(None)) => ([0, 0, 0], 0.0),
+                Some(None) => {
+                    self.unsupported.insert("gradient stop colors".to_string());
+                    return None;
+                }
+            };
+            let opacity = attribute(stop, "stop-opacity")
+                .and_then(parse_length)
+                .unwrap_or(1.0);
+            open_tag(
+                &mut gradient,
+                1,
+                "item",
+                &[
+                    ("offset".to_string(), number(offset)),
+                    ("color".to_string(), argb(rgb, alpha * opacity)),
+                ],
+                true,
+            );
+        }
+        gradient.push_str("</gradient>\n");
+        Some(gradient)
+    }
+}
+
+fn tag_feature(tag: &str) -> String {
+    match tag {
+        "mask" => "masks",
+        "filter" => "filters",
+        "image" => "embedded images",
+        "text" => "text",
+        "foreignObject" => "foreignObject (angular or diamond gradients)",
+        "pattern" => "pattern fills",
+        _ => return format!("<{}> element", tag),
+    }
+    .to_string()
+}
+
+/// Attribute value, with `style="name: value"` taking precedence
+fn attribute<'a>(node: Node<'a, '_>, name: &str) -> Option<&'a str> {
+    node.attribute("style")
+        .and_then(|style| {
+            style.split(';').find_map(|declaration| {
+                let (key, value) = declaration.split_once(':')?;
+                (key.trim() == name).then(|| value.trim())
+            })
+        })
+        .or_else(|| node.attribute(name))
+}
+
+fn url_id(value: &str) -> Option<&str> {
+    value
+        .trim()
+        .strip_prefix("url(")?
+        .split(')')
+        .next()?
+        .trim()
+        .trim_matches(|c| c == '\'' || c == '"')
+        .strip_prefix('#')
+}
+
+/// Number in user units; `px` is accepted, percentages are not
+fn parse_length(value: &str) -> Option<f64> {
+    value.trim().trim_end_matches("px").trim().parse().ok()
+}
+
+/// `Some(None)` for `none`/`transparent`, `None` when the color is not understood
+fn parse_color(value: &str) -> Option<Option<([u8; 3], f64)>> {
+    let value = value.trim().to_ascii_lowercase();
+    let named = match value.as_str() {
+        "none" | "transparent" => return Some(None),
+        "black" => Some([0, 0, 0]),
+        "white" => Some([255, 255, 255]),
+        "red" => Some([255, 0, 0]),
+        "lime" => Some([0, 255, 0]),
+        "blue" => Some([0, 0, 255]),
+        _ => None,
+    };
+    if let Some(rgb) = named {
+        return Some(Some((rgb, 1.0)));
+    }
+    if let Some(hex) = value.strip_prefix('#') {
+        let expanded: String = match hex.len() {
+            3 | 4 => hex.chars().flat_map(|c| [c, c]).collect(),
+            6 | 8 => hex.to_string(),
+            _ => return None,
+        };
+        let channel = |index: usize| u8::from_str_radix(expanded.get(index..index + 2)?, 16).ok();
+        let rgb = [channel(0)?, channel(2)?, channel(4)?];
+        let alpha = match expanded.len() {
+            8 => f64::from(channel(6)?) / 255.0,
+            _ => 1.0,
+        };
+        return Some(Some((rgb, alpha)));
+    }
+    let inner = value
+        .strip_prefix("rgba(")
+        .or_else(|| value.strip_prefix("rgb("))?
+        .strip_suffix(')')?;
+    let parts: Vec<f64> = inner
+        .split(',')
+        .map(|part| part.trim().parse().ok())
+        .collect::<Option<_>>()?;
+    match parts.as_slice() {
+        [r, g, b] => Some(Some(([*r as u8, *g as u8, *b as u8], 1.0))),
+        [r, g, b, a] => Some(Some(([*r as u8, *g as u8, *b as u8], *a))),
+        _ => None,
+    }
+}
+
+fn multiply(m: &Matrix, n: &Matrix) -> Matrix {
+    [
+        m[0] * n[0] + m[2] * n[1],
+        m[1] * n[0] + m[3] * n[1],
+        m[0] * n[2] + m[2] * n[3],
+        m[1] * n[2] + m[3] * n[3],
+        m[0] * n[4] + m[2] * n[5] + m[4],
+        m[1] * n[4] + m[3] * n[5] + m[5],
+    ]
+}
+
+fn apply(m: &Matrix, x: f64, y: f64) -> (f64, f64) {
+    (m[0] * x + m[2] * y + m[4], m[1] * x + m[3] * y + m[5])
+}
+
+/// Parse an SVG transform list into one matrix
+fn parse_transform(value: &str) -> Option<Matrix> {
+    let mut matrix = IDENTITY;
+    for function in value.split(')').map(str::trim).filter(|f| !f.is_empty()) {
+        let (name, arguments) = function.split_once('(')?;
+        let args: Vec<f64> = arguments
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|part| !part.is_empty())
+            .map(|part| part.parse().ok())
+            .collect::<Option<_>>()?;
+        let next = match (name.trim().trim_start_matches(','), args.as_slice()) {
+            ("matrix", [a, b, c, d, e, f]) => [*a, *b, *c, *d, *e, *f],
+            ("translate", [x]) => [1.0, 0.0, 0.0, 1.0, *x, 0.0],
+            ("translate", [x, y]) => [1.0, 0.0, 0.0, 1.0, *x, *y],
+            ("scale", [s]) => [*s, 0.0, 0.0, *s, 0.0, 0.0],
+            ("scale", [x, y]) => [*x, 0.0, 0.0, *y, 0.0, 0.0],
+            ("rotate", [angle, rest @ ..]) => {
+                let (sin, cos) = angle.to_radians().sin_cos();
+                let rotation = [cos, sin, -sin, cos, 0.0, 0.0];
+                match rest {
+                    [] => rotation,
+                    [cx, cy] => multiply(
+                        &multiply(&[1.0, 0.0, 0.0, 1.0, *cx, *cy], &rotation),
+                        &[1.0, 0.0, 0.0, 1.0, -cx, -cy],
+                    ),
+                    _ => return None,
+                }
+            }
+            ("skewX", [angle]) => [1.0, 0.0, angle.to_radians().tan(), 1.0, 0.0, 0.0],
+            ("skewY", [angle]) => [1.0, angle.to_radians().tan(), 0.0, 1.0, 0.0, 0.0],
+            _ => return None,
+        };
+        matrix = multiply(&matrix, &next);
+    }
+    Some(matrix)
+}
+
+/// Group attributes for a matrix; VectorDrawable groups scale, then rotate,
+/// then translate, so skews cannot be expressed
+fn decompose(matrix: Matrix) -> Option<Vec<(String, String)>> {
+    let [a, b, c, d, e, f] = matrix;
+    let scale_x = a.hypot(b);
+    if scale_x == 0.0 || (a * c + b * d).abs() > 1e-6 * scale_x.max(1.0) {
+        return None;
+    }
+    let scale_y = (a * d - b * c) / scale_x;
+    let rotation = b.atan2(a).to_degrees();
+    let mut attributes = Vec::new();
+    let mut push = |name: &str, value: f64, default: f64| {
+        if (value - default).abs() > 1e-6 {
+            attributes.push((name.to_string(), number(value)));
+        }
+    };
+    push("rotation", rotation, 0.0);
+    push("scaleX", scale_x, 1.0);
+    push("scaleY", scale_y, 1.0);
+    push("translateX", e, 0.0);
+    push("translateY", f, 0.0);
+    Some(attributes)
+}
+
+/// Path data of a shape element
+fn shape_data(node: Node) -> Option<String> {
+    shape_data_at(node, (0.0, 0.0))
+}
+
+/// Path data of a shape element moved by `offset`
+fn shape_data_at(node: Node, (dx, dy): (f64, f64)) -> Option<String> {
+    let value = |name: &str| node.attribute(name).and_then(parse_length);
+    let get = |name: &str| value(name).unwrap_or(0.0);
+    let data = match node.tag_name().name() {
+        "path" if dx == 0.0 && dy == 0.0 => node
+            .attribute("d")?
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" "),
+        "rect" => {
+            let (x, y) = (get("x") + dx, get("y") + dy);
+            let (width, height) = (get("width"), get("height"));
+            let rx = value("rx").or(value("ry")).unwrap_or(0.0).min(width / 2.0);
+            let ry = value("ry").or(value("rx")).unwrap_or(0.0).min(height / 2.0);
+            if rx <= 0.0 || ry <= 0.0 {
+                format!(
+                    "M{},{}H{}V{}H{}Z",
+                    number(x),
+                    number(y),
+                    number(x + width),
+                    number(y + height),
+                    number(x)
+                )
+            } else {
+                let arc = |x: f64, y: f64| {
+                    format!(
+                        "A{},{} 0 0 1 {},{}",
+                        number(rx),
+                        number(ry),
+                        number(x),
+                        number(y)
+                    )
+                };
+                format!(
+                    "M{},{}H{}{}V{}{}H{}{}V{}{}Z",
+                    number(x + rx),
+                    number(y),
+                    number(x + width - rx),
+                    arc(x + width, y + ry),
+                    number(y + height - ry),
+                    arc(x + width - rx, y + height),
+                    number(x + rx),
+                    arc(x, y + height - ry),
+                    number(y + ry),
+                    arc(x + rx, y)
+                )
+            }
+        }
+        "circle" | "ellipse" => {
+            let (cx, cy) = (get("cx") + dx, get("cy") + dy);
+            let (rx, ry) = match node.tag_name().name() {
+                "circle" => (get("r"), get("r")),
+                _ => (get("rx"), get("ry")),
+            };
+            let arc = |x: f64| {
+                format!(
+                    "A{},{} 0 1 0 {},{}",
+                    number(rx),
+                    number(ry),
+                    number(x),
+                    number(cy)
+                )
+            };
+            format!(
+                "M{},{}{}{}Z",
+                number(cx - rx),
+                number(cy),
+                arc(cx + rx),
+                arc(cx - rx)
+            )
+        }
+        "line" => format!(
+            "M{},{}L{},{}",
+            number(get("x1") + dx),
+            number(get("y1") + dy),
+            number(get("x2") + dx),
+            number(get("y2") + dy)
+        ),
+        "polygon" | "polyline" => {
+            let numbers: Vec<f64> = node
+                .attribute("points")?
+                .split(|c: char| c.is_whitespace() || c == ',')
+                .filter(|part| !part.is_empty())
+                .filter_map(|part| part.parse().ok())
+                .collect();
+            let points: Vec<String> = numbers
+                .chunks_exact(2)
+                .map(|point| format!("{},{}", number(point[0] + dx), number(point[1] + dy)))
+                .collect();
+            if points.is_empty() {
+                return None;
+            }
+            let close = if node.tag_name().name() == "polygon" {
+                "Z"
+            } else {
+                ""
+            };
+            format!("M{}{}", points.join("L"), close)
+        }
+        _ => return None,
+    };
+    Some(data)
+}
+
+fn push_alpha(attributes: &mut Vec<(String, String)>, name: &str, alpha: f64) {
+    if alpha < 1.0 {
+        attributes.push((name.to_string(), number(alpha.max(0.0))));
+    }
+}
+
+fn hex([r, g, b]: [u8; 3]) -> String {
+    format!("#{:02X}{:02X}{:02X}", r, g, b)
+}
+
+fn argb(rgb: [u8; 3], alpha: f64) -> String {
+    format!(
+        "#{:02X}{}",
+        (alpha.clamp(0.0, 1.0) * 255.0).round() as u8,
+        hex(rgb).trim_start_matches('#')
+    )
+}
+
+/// Number with at most 4 decimals and no trailing zeros
+fn number(value: f64) -> String {
+    let formatted = format!("{:.4}", value);
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+    if trimmed == "-0" {
+        "0".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// Write `<tag android:name="value" ...>`, one attribute per line like Android Studio
+fn open_tag(
+    out: &mut String,
+    depth: usize,
+    tag: &str,
+    attributes: &[(String, String)],
+    close: bool,
+) {
+    let pad = INDENT.repeat(depth);
+    let end = if close { "/>" } else { ">" };
+    if attributes.is_empty() {
+        out.push_str(&format!("{}<{}{}\n", pad, tag, end));
+        return;
+    }
+    out.push_str(&format!("{}<{}", pad, tag));
+    for (name, value) in attributes {
+        out.push_str(&format!(
+            "\n{}{}android:{}=\"{}\"",
+            pad,
+            INDENT,
+            name,
+            value.replace('&', "&amp;").replace('"', "&quot;")
+        ));
+    }
+    out.push_str(end);
+    out.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_resources_in_snake_case() {
+        assert_eq!(android_resource_name("Icon/Arrow Left"), "icon_arrow_left");
+        assert_eq!(android_resource_name("ic--Close.24"), "ic_close_24");
+        assert_eq!(android_resource_name("1-2"), "asset_1_2");
+        assert_eq!(android_resource_name("---"), "asset");
+    }
+
+    #[test]
+    fn converts_figma_icon_svg() {
+        let svg = r##"<svg width="24" height="24" viewBox="0 0 24 24" fill="none" xmlns="http://www.w3.org/2000/svg">
+<g clip-path="url(#clip0_1_2)" opacity="0.5">
+<rect x="2" y="2" width="20" height="20" rx="4" fill="#FF0000"/>
+<path d="M12 6L18 18H6Z" stroke="white" stroke-width="2" stroke-linecap="round" fill-rule="evenodd"/>
+<circle cx="12" cy="12" r="3" fill="url(#paint0_linear_1_2)" transform="translate(1 2)"/>
+</g>
+<defs>
+<linearGradient id="paint0_linear_1_2" x1="9" y1="12" x2="15" y2="12" gradientUnits="userSpaceOnUse">
+<stop stop-color="#0000FF"/>
+<stop offset="1" stop-color="#0000FF" stop-opacity="0"/>
+</linearGradient>
+<clipPath id="clip0_1_2">
+<rect width="24" height="24" fill="white"/>
+</clipPath>
+</defs>
+</svg>"##;
+        let xml = svg_to_vector_drawable(svg).expect("convert");
+        assert!(xml.contains("xmlns:aapt=\"http://schemas.android.com/aapt\""));
+        assert!(xml.contains("android:viewportWidth=\"24\""));
+        assert!(xml.contains("<clip-path\n            android:pathData=\"M0,0H24V24H0Z\"/>"));
+        assert!(xml.contains("android:pathData=\"M6,2H18A4,4 0 0 1 22,6"));
+        assert!(
+            xml.contains("android:fillColor=\"#FF0000\"\n            android:fillAlpha=\"0.5\"")
+        );
+        // fill="none" is inherited from the root, so the stroke-only path has no fill
+        assert!(xml.contains(
+            "android:pathData=\"M12 6L18 18H6Z\"\n            android:strokeColor=\"#FFFFFF\"\n            android:strokeAlpha=\"0.5\"\n            android:strokeWidth=\"2\"\n            android:strokeLineCap=\"round\"/>"
+        ));
+        assert!(xml.contains("android:translateX=\"1\""));
+        assert!(xml.contains("<aapt:attr name=\"android:fillColor\">"));
+        assert!(xml.contains("android:startX=\"9\""));
+        assert!(xml.contains("android:color=\"#000000FF\""));
+    }
+
+    #[test]
+    fn reports_unsupported_features() {
+        let svg = r##"<svg width="24" height="24" viewBox="0 0 24 24" fill="none" xmlns="http://www.w3.org/2000/svg">
+<mask id="mask0" x="0" y="0" width="24" height="24"><rect width="24" height="24" fill="white"/></mask>
+<g mask="url(#mask0)" filter="url(#filter0)">
+<path d="M0 0H24V24H0Z" fill="url(#paint0_radial)"/>
+</g>
+<defs>
+<radialGradient id="paint0_radial" cx="0" cy="0" r="1" gradientUnits="userSpaceOnUse" gradientTransform="translate(12 12) scale(12 6)">
+<stop stop-color="white"/>
+</radialGradient>
+</defs>
+</svg>"##;
+        let error = svg_to_vector_drawable(svg).expect_err("unsupported");
+        assert_eq!(
+            error.to_string(),
+            "unsupported SVG features: elliptical radial gradients, filters, masks"
+        );
+    }
+}
@@ -0,0 +1,195 @@
+//! Xcode asset catalog (`.xcassets`) output for iOS exports
+//!
+//! Raster exports become `<name>.imageset` folders with one universal image per
+//! scale; SVG and PDF exports become single-scale vector imagesets. With
+//! `--colorsets`, named color styles become `.colorset` entries under a `Colors`
+//! folder, so the catalog can be committed into an Xcode project as-is.
+
+use crate::commands::tokens::{sanitize_token_name, ColorToken};
+use crate::output;
+use anyhow::Result;
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Catalog folder written inside the export directory
+pub const CATALOG_NAME: &str = "Assets.xcassets";
+
+fn info() -> Value {
+    json!({ "author": "fgm", "version": 1 })
+}
+
+fn write_contents(dir: &Path, contents: &Value) -> Result<()> {
+    fs::create_dir_all(dir)?;
+    fs::write(
+        dir.join("Contents.json"),
+        serde_json::to_string_pretty(contents)? + "\n",
+    )?;
+    Ok(())
+}
+
+/// Create the catalog folder with its root `Contents.json`
+pub fn create_catalog(output: &Path) -> Result<PathBuf> {
+    let catalog = output.join(CATALOG_NAME);
+    write_contents(&catalog, &json!({ "info": info() }))?;
+    Ok(catalog)
+}
+
+/// Folder of the imageset for an asset name
+pub fn image_set_dir(catalog: &Path, name: &str) -> PathBuf {
+    catalog.join(format!("{}.imageset", name))
+}
+
+/// `Contents.json` of a raster imageset: `(scale, filename)` pairs like `(2, "icon@2x.png")`
+pub fn image_set_contents(images: &[(u32, String)]) -> Value {
+    let images: Vec<Value> = images
+        .iter()
+        .map(|(scale, filename)| {
+            json!({
+                "filename": filename,
+                "idiom": "universal",
+                "scale": format!("{}x", scale)
+            })
+        })
+        .collect();
+    json!({ "images": images, "info": info() })
+}
+
+/// `Contents.json` of a single-scale SVG or PDF imageset
+pub fn vector_image_set_contents(filename: &str) -> Value {
+    json!({
+        "images": [{ "filename": filename, "idiom": "universal" }],
+        "info": info(),
+        "properties": { "preserves-vector-representation": true }
+    })
+}
+
+pub fn write_image_set(dir: &Path, contents: &Value) -> Result<()> {
+    write_contents(dir, contents)
+}
+
+/// `Contents.json` of a colorset for an sRGB color token
+pub fn color_set_contents(color: &ColorToken) -> Value {
+    let [red, green, blue] = color.rgb;
+    json!({
+        "colors": [{
+            "color": {
+                "color-space": "srgb",
+                "components": {
+                    "alpha": format!("{:.3}", color.rgba[3]),
+                    "blue": format!("0x{:02X}", blue),
+                    "green": format!("0x{:02X}", green),
+                    "red": format!("0x{:02X}", red)
+                }
+            },
+            "idiom": "universal"
+        }],
+        "info": info()
+    })
+}
+
+/// Write one colorset per color under `Colors/`, returning how many were written
+///
+/// Styles whose names sanitize to the same colorset (`Brand/Primary` and
+/// `Brand Primary`) get a numeric suffix instead of overwriting each other.
+pub fn write_color_sets(catalog: &Path, colors: &[ColorToken]) -> Result<usize> {
+    if colors.is_empty() {
+        return Ok(0);
+    }
+    let folder = catalog.join("Colors");
+    write_contents(&folder, &json!({ "info": info() }))?;
+    let mut used = HashSet::new();
+    for color in colors {
+        let base = sanitize_token_name(&color.name);
+        let mut name = base.clone();
+        let mut suffix = 2;
+        while !used.insert(name.clone()) {
+            name = format!("{}-{}", base, suffix);
+            suffix += 1;
+        }
+        if name != base {
+            output::print_warning(&format!(
+                "Color style {} is written as {}.colorset; {}.colorset is taken",
+                color.name, name, base
+            ));
+        }
+        let dir = folder.join(format!("{}.colorset", name));
+        write_contents(&dir, &color_set_contents(color))?;
+    }
+    Ok(colors.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn writes_image_and_color_sets() {
+        let raster = image_set_contents(&[
+            (1, "icon.png".to_string()),
+            (2, "icon@2x.png".to_string()),
+            (3, "icon@3x.png".to_string()),
+        ]);
+        assert_eq!(raster["images"][1]["filename"], "icon@2x.png");
+        assert_eq!(raster["images"][1]["scale"], "2x");
+        assert_eq!(raster["images"][2]["idiom"], "universal");
+
+        let vector = vector_image_set_contents("icon.pdf");
+        assert_eq!(vector["images"].as_array().map(Vec::len), Some(1));
+        assert!(vector["images"][0].get("scale").is_none());
+        assert_eq!(
+            vector["properties"]["preserves-vector-representation"],
+            true
+        );
+
+        let color = ColorToken {
+            name: "Brand/Primary".to_string(),
+            hex: "#112233".to_string(),
+            rgb: [17, 34, 51],
+            rgba: [0.067, 0.133, 0.2, 0.5],
+            description: None,
+        };
+        let dir = tempdir().expect("tempdir");
+        let catalog = create_catalog(dir.path()).expect("catalog");
+        assert_eq!(
+            write_color_sets(&catalog, std::slice::from_ref(&color)).expect("colors"),
+            1
+        );
+        let written: Value = serde_json::from_str(
+            &fs::read_to_string(catalog.join("Colors/brand-primary.colorset/Contents.json"))
+                .expect("colorset"),
+        )
+        .expect("json");
+        let components = &written["colors"][0]["color"]["components"];
+        assert_eq!(components["red"], "0x11");
+        assert_eq!(components["blue"], "0x33");
+        assert_eq!(components["alpha"], "0.500");
+        assert!(catalog.join("Contents.json").exists());
+    }
+
+    #[test]
+    fn colliding_color_names_get_separate_colorsets() {
+        let color = |name: &str, red: u8| ColorToken {
+            name: name.to_string(),
+            hex: format!("#{:02X}0000", red),
+            rgb: [red, 0, 0],
+            rgba: [red as f64 / 255.0, 0.0, 0.0, 1.0],
+            description: None,
+        };
+        let dir = tempdir().expect("tempdir");
+        let catalog = create_catalog(dir.path()).expect("catalog");
+        let colors = [color("Brand/Primary", 0x11), color("Brand Primary", 0x22)];
+        assert_eq!(write_color_sets(&catalog, &colors).expect("colors"), 2);
+
+        let red = |name: &str| {
+            let path = catalog.join(format!("Colors/{}.colorset/Contents.json", name));
+            let written: Value =
+                serde_json::from_str(&fs::read_to_string(path).expect("colorset")).expect("json");
+            written["colors"][0]["color"]["components"]["red"].clone()
+        };
+        assert_eq!(red("brand-primary"), "0x11");
+        assert_eq!(red("brand-primary-2"), "0x22");
+    }
+}
@@ -5,7 +5,7 @@ use std::fs;
 use std::path::PathBuf;
 
 /// Application configuration
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Default)]
 pub struct Config {
     /// Figma token (fallback when keychain unavailable)
     /// WARNING: Stored in plaintext - prefer keychain storage
@@ -17,6 +17,8 @@ pub struct Config {
     pub export: ExportConfig,
     #[serde(default)]
     pub tokens: TokensConfig,
+    #[serde(default)]
+    pub api: ApiConfig,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -43,6 +45,19 @@ pub struct TokensConfig {
     pub css_prefix: String,
     #[serde(default = "default_swift_prefix")]
     pub swift_prefix: String,
+    /// Color notation for CSS-based token formats: hex, hsl, oklch, display-p3
+    #[serde(default = "default_color_space")]
+    pub color_space: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct ApiConfig {
+    /// REST API base URL override (e.g. a local mock server)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    /// Origin that image download URLs are rewritten onto
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_host: Option<String>,
 }
 
 fn default_output_format() -> String {
@@ -69,15 +84,8 @@ fn default_swift_prefix() -> String {
     "Figma".to_string()
 }
 
-impl Default for Config {
-    fn default() -> Self {
-        Self {
-            figma_token: None,
-            defaults: DefaultsConfig::default(),
-            export: ExportConfig::default(),
-            tokens: TokensConfig::default(),
-        }
-    }
+fn default_color_space() -> String {
+    "hex".to_string()
 }
 
 impl Default for DefaultsConfig {
@@ -105,6 +113,7 @@ impl Default for TokensConfig {
         Self {
             css_prefix: default_css_prefix(),
             swift_prefix: default_swift_prefix(),
+            color_space: default_color_space(),
         }
     }
 }
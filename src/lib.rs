@@ -0,0 +1,80 @@
+//! fgm - Figma CLI library
+//!
+//! The `fgm` binary is a thin CLI over this crate. Embedders can use the
+//! Figma API client, cache, URL parser, image diff engine, token model and
+//! report renderers directly instead of shelling out and parsing JSON.
+//!
+//! ```no_run
+//! use fgm::{FigmaClient, FigmaUrl};
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let url = FigmaUrl::parse("https://www.figma.com/design/abc123/MyFile")?;
+//! let client = FigmaClient::new(std::env::var("FIGMA_TOKEN")?)?;
+//! let file = client.get_file(&url.file_key).await?;
+//! let tokens = fgm::tokens::extract_design_tokens(&file);
+//! println!("{}", fgm::tokens::export_css(&tokens, "--", fgm::tokens::ColorSpace::Hex));
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod api;
+pub mod auth;
+pub mod config;
+pub mod reporting;
+
+// CLI plumbing used by the `fgm` binary; not part of the stable API.
+#[doc(hidden)]
+pub mod cli;
+#[doc(hidden)]
+pub mod commands;
+#[doc(hidden)]
+pub mod output;
+#[doc(hidden)]
+pub mod project;
+#[doc(hidden)]
+pub mod select;
+pub(crate) mod util;
+#[doc(hidden)]
+pub mod watch;
+
+/// Pixel diff engine used by `compare` and `compare-url`
+pub mod diff {
+    pub use crate::cli::DiffMethod;
+    pub use crate::commands::compare::{
+        calculate_diff, calculate_diff_internal, compare_images, compare_rgba, generate_diff_image,
+        render_diff_image, render_rgba_diff, DiffOptions, DiffResult,
+    };
+    pub use crate::commands::perceptual::SsimMap;
+    pub use crate::commands::regions::{attribute_regions, cluster_regions, DiffRegion};
+}
+
+/// Accessibility checks used by `audit`
+pub mod audit {
+    pub use crate::cli::WcagLevel;
+    pub use crate::commands::audit::{
+        apca_contrast, audit_contrast, contrast_ratio, contrast_report, ContrastFinding,
+    };
+}
+
+/// Design token model, extraction, exporters and diffing used by `tokens`
+pub mod tokens {
+    pub use crate::cli::{ColorSpace, TokenFormat};
+    pub use crate::commands::tokens::{
+        build_variable_tokens, diff_report, diff_tokens, export_android_night_xml,
+        export_android_xml, export_css, export_dtcg, export_json, export_kotlin,
+        export_style_dictionary, export_swift, export_tailwind, extract_design_tokens,
+        extract_radii, extract_spacing_scale, extract_stroke_widths, extract_unnamed_paints,
+        kotlin_weight, load_tokens_file, read_dtcg, render_tokens, swift_weight, AliasMode,
+        BlurKind, BlurToken, ColorToken, DesignTokens, DiffSource, DimensionToken, GradientKind,
+        GradientStop, GradientToken, ImagePaintToken, LineHeightUnit, ShadowLayer, ShadowToken,
+        SpacingScale, SpacingToken, TextCase, TextDecoration, TokenChange, TokenChangeKind,
+        TypographyToken, VariableCollectionTokens, VariableReference, VariableToken,
+        VariableTokenType, VariableTokenValue,
+    };
+}
+
+pub use api::{
+    create_shared_cache, CacheKey, CacheStats, CacheTTL, FigmaCache, FigmaClient, FigmaEndpoints,
+    FigmaUrl,
+};
+pub use reporting::{ReportFormat, ReportItem, ReportStatus, ReportSummary};
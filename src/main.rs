@@ -1,18 +1,8 @@
-mod api;
-mod auth;
-mod cli;
-mod commands;
-mod config;
-mod output;
-mod project;
-mod reporting;
-mod select;
-mod watch;
-
 use anyhow::Result;
 use clap::Parser;
-use cli::{Cli, Commands};
-use output::{OutputFormat, Verbosity};
+use fgm::cli::{Cli, Commands};
+use fgm::output::{OutputFormat, Verbosity};
+use fgm::{api, auth, commands, config, output};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -58,6 +48,10 @@ async fn main() -> Result<()> {
 
     output::init(format, verbosity, !cli.no_color);
     auth::set_keychain_enabled(!cli.no_keychain);
+    api::fixtures::set_global_mode(api::fixtures::FixtureMode::from_flags(
+        cli.record.clone(),
+        cli.replay.clone(),
+    ));
 
     if let Some(err) = config_error {
         output::print_warning(&format!("Failed to load config ({}), using defaults", err));
@@ -75,6 +69,7 @@ async fn main() -> Result<()> {
         Commands::Compare(args) => commands::compare::run(args).await,
         Commands::CompareUrl(args) => commands::compare_url::run(args).await,
         Commands::Tokens { command } => commands::tokens::run(command).await,
+        Commands::Audit { command } => commands::audit::run(command).await,
         Commands::Components { command } => commands::components::run(command).await,
         Commands::Preview(args) => commands::preview::run(args).await,
         Commands::Snapshot { command } => commands::snapshot::run(command).await,
@@ -4,31 +4,21 @@ use std::sync::OnceLock;
 use tabled::{Table, Tabled};
 
 /// Output format for CLI results
-#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
 pub enum OutputFormat {
+    #[default]
     Table,
     Json,
 }
 
-impl Default for OutputFormat {
-    fn default() -> Self {
-        OutputFormat::Table
-    }
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum Verbosity {
     Quiet,
+    #[default]
     Normal,
     Verbose,
 }
 
-impl Default for Verbosity {
-    fn default() -> Self {
-        Verbosity::Normal
-    }
-}
-
 #[derive(Debug, Clone, Copy)]
 pub struct OutputSettings {
     pub format: OutputFormat,
@@ -0,0 +1,25 @@
+//! Hashes that stay the same across Rust releases and platforms
+//!
+//! Used where a hash ends up on disk or in generated code, such as fixture
+//! file names and names of unnamed tokens.
+
+/// 64-bit FNV-1a; unlike `DefaultHasher`, its output never changes
+pub fn fnv1a64(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_reference_values() {
+        assert_eq!(fnv1a64(b""), 0xcbf29ce484222325);
+        assert_eq!(fnv1a64(b"a"), 0xaf63dc4c8601ec8c);
+    }
+}